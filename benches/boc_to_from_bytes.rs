@@ -2,7 +2,7 @@ use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use criterion::{criterion_group, criterion_main, Criterion};
 use std::{hint::black_box, ops::Deref, sync::LazyLock};
-use ton_lib_core::boc::BOC;
+use ton_lib_core::boc::{BOCLazy, BOC};
 use tonlib_core::cell::BagOfCells;
 
 const ITERATIONS_COUNT: usize = 100;
@@ -30,6 +30,31 @@ fn boc_from_bytes_tonlib() {
     }
 }
 
+fn boc_from_bytes_tonlib_lazy() {
+    for _ in 0..ITERATIONS_COUNT {
+        let boc = BOCLazy::new(BOC_BYTES.deref()).unwrap();
+        let root = boc.single_root().unwrap().to_cell().unwrap();
+        black_box(root);
+    }
+}
+
+// reads only root data, without decoding the rest of cells
+fn boc_read_root_tonlib_lazy() {
+    for _ in 0..ITERATIONS_COUNT {
+        let boc = BOCLazy::new(BOC_BYTES.deref()).unwrap();
+        let value = boc.single_root().unwrap().parser().read_num::<u32>(32).unwrap();
+        black_box(value);
+    }
+}
+
+fn boc_read_root_tonlib() {
+    for _ in 0..ITERATIONS_COUNT {
+        let root = BOC::from_bytes(BOC_BYTES.deref()).unwrap().single_root().unwrap();
+        let value = root.parser().read_num::<u32>(32).unwrap();
+        black_box(value);
+    }
+}
+
 fn boc_to_bytes_tonlib_core() {
     for _ in 0..ITERATIONS_COUNT {
         let bytes = &BOC_TOBLIB_CORE.serialize(false).unwrap();
@@ -47,6 +72,10 @@ fn boc_to_bytes_tonlib() {
 fn benchmark_functions(c: &mut Criterion) {
     c.bench_function("boc_from_bytes_tonlib_core", |b| b.iter(boc_from_bytes_tonlib_core));
     c.bench_function("boc_from_bytes_tonlib", |b| b.iter(boc_from_bytes_tonlib));
+    c.bench_function("boc_from_bytes_tonlib_lazy", |b| b.iter(boc_from_bytes_tonlib_lazy));
+
+    c.bench_function("boc_read_root_tonlib", |b| b.iter(boc_read_root_tonlib));
+    c.bench_function("boc_read_root_tonlib_lazy", |b| b.iter(boc_read_root_tonlib_lazy));
 
     c.bench_function("boc_to_bytes_tonlib_core", |b| b.iter(boc_to_bytes_tonlib_core));
     c.bench_function("boc_to_bytes_tonlib", |b| b.iter(boc_to_bytes_tonlib));
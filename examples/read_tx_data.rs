@@ -4,7 +4,8 @@ use ton_lib_core::traits::tlb::TLB;
 fn main() -> anyhow::Result<()> {
     let tx = Tx::from_boc_b64("te6ccgECDwEAAyMAA7V/Oy1C2bSh+pr2Y7iPgChnHVuE6STxcizXfflEci6HEXAAA3jaQYuMNP15jk0x4BtquQXT7aFs9+cMSzCJDueGkAjuCiUmvoWwAAN42XddvDaLXNxQADRhiJXIAQIDAgHgBAUAgnI8FKD7OtD1genXkm3QY34gAIOUyxzNzAgLGKJxMwDVSeQ8+O4m1Q0n8yN3OH58saGJGWFY5/Epok0nVXBnX9gmAhcERUkAvrwgGGDDUBEGBwGxaAGUNZ+lRrv95/ZoC1zuReDcrA4uppZycwKGUKrui0EDhwA87LULZtKH6mvZjuI+AKGcdW4TpJPFyLNd9+URyLocRdAL68IABg+gCAAAbxtIMXGE0WubisAIAQHfCQCeQH0MB6EgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABvyYk/MEwYqGQAAAAAAAIAAAAAAAOvq7lSqeeQPq4S7on7nrJ8ilrbQGUqVDipwwXEfBNAHkGQReQBpg+KfqUAAAAAAAAAACBdyAE1LIZrc0Q9+OgouBfc1eCvbQIt2V7Ie1MoIDuAjEhIOQAyhrP0qNd/vP7NAWudyLwblYHF1NLOTmBQyhVd0WggcMIDDgKxaAHnZahbNpQ/U17MdxHwBQzjq3CdJJ4uRZrvvyiORdDiLwAQlkV/lIsvt/DRxKd2ulwIAn6nfMHKXmn+iBAAs02AaVALiV6ABhiojgAAbxtIMXGI0Wubi+AKCwIBNAwNAaUXjUUZAAAAAAAAAAAgXcgBlDWfpUa7/ef2aAtc7kXg3KwOLqaWcnMChlCq7otBA4cAMoaz9KjXf7z+zQFrnci8G5WBxdTSzk5gUMoVXdFoIHDEBw4IQgKPRS16Tf10BmtoI2UXclntBXNENb52tf1L1divK3w9aACHAIATUshmtzRD346Ci4F9zV4K9tAi3ZXsh7UyggO4CMSEg5ACxE6mUtQJKFnGfaROTKOt1lZbDiiX1kCixRv7Nw2Id/oADgAAAAA5MzQ=")?;
     dbg!(&tx);
-    dbg!(tx.msgs.in_msg.as_ref().unwrap().cell_hash()?);
-    dbg!(&tx.msgs.out_msgs[0].cell_hash()?);
+    let msgs = tx.msgs.get()?;
+    dbg!(msgs.in_msg.as_ref().unwrap().cell_hash()?);
+    dbg!(&msgs.out_msgs[0].cell_hash()?);
     Ok(())
 }
@@ -1,10 +1,13 @@
 use crate::block_tlb::{Coins, CurrencyCollection, StateInit};
 use crate::tlb_adapters::TLBRef;
-use ton_lib_core::cell::{TonCellRef, TonHash};
+use ton_lib_core::cell::{CellStorageStats, TonCellRef, TonCellUtils, TonHash};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
 use ton_lib_core::types::tlb_core::{MsgAddressInt, VarLenBytes};
 use ton_lib_core::TLBDerive;
 
 #[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShardAccount {
     #[tlb_derive(adapter = "TLBRef")]
     pub account: MaybeAccount,
@@ -15,6 +18,7 @@ pub struct ShardAccount {
 // https://github.com/ton-blockchain/ton/blob/59a8cf0ae5c3062d14ec4c89a04fee80b5fd05c1/crypto/block/block.tlb#L259
 // intentionally implemented as enum - Account can't be used directly
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaybeAccount {
     None(AccountNone),
     #[rustfmt::skip]
@@ -22,10 +26,12 @@ pub enum MaybeAccount {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0, bits_len = 1)]
 pub struct AccountNone;
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b1, bits_len = 1)]
 pub struct Account {
     pub addr: MsgAddressInt,
@@ -34,12 +40,14 @@ pub struct Account {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StorageUsed {
     pub cells: VarLenBytes<u64, 3>,
     pub bits: VarLenBytes<u64, 3>,
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StorageInfo {
     pub used: StorageUsed,
     pub storage_extra: MaybeStorageExtraInfo,
@@ -48,6 +56,7 @@ pub struct StorageInfo {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccountStorage {
     pub last_tx_lt: u64,
     pub balance: CurrencyCollection,
@@ -55,22 +64,26 @@ pub struct AccountStorage {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaybeStorageExtraInfo {
     None(StorageExtraInfoNone),
     Info(StorageExtraInfo),
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b000, bits_len = 3)]
 pub struct StorageExtraInfoNone;
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b001, bits_len = 3)]
 pub struct StorageExtraInfo {
     pub dict_hash: TonHash,
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccountState {
     Uninit(AccountStateUninit),
     Frozen(AccountStateFrozen),
@@ -78,46 +91,39 @@ pub enum AccountState {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b00, bits_len = 2)]
 pub struct AccountStateUninit;
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b01, bits_len = 2)]
 pub struct AccountStateFrozen {
     pub state_hash: TonHash,
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b1, bits_len = 1)]
 pub struct AccountStateActive {
     pub state_init: StateInit,
 }
 
 // https://github.com/ton-blockchain/ton/blob/ed4682066978f69ffa38dd98912ca77d4f660f66/crypto/block/block.tlb#L271
-#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[derive(Debug, Default, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccountStatus {
-    Uninit(AccountStatusUninit),
-    Frozen(AccountStatusFrozen),
-    Active(AccountStatusActive),
-    NonExist(AccountStatusNotExist),
+    #[tlb_derive(prefix = 0b00, bits_len = 2)]
+    Uninit,
+    #[tlb_derive(prefix = 0b01, bits_len = 2)]
+    Frozen,
+    #[tlb_derive(prefix = 0b10, bits_len = 2)]
+    Active,
+    #[default]
+    #[tlb_derive(prefix = 0b11, bits_len = 2)]
+    NonExist,
 }
 
-#[derive(Debug, Clone, PartialEq, TLBDerive)]
-#[tlb_derive(prefix = 0b00, bits_len = 2)]
-pub struct AccountStatusUninit;
-
-#[derive(Debug, Clone, PartialEq, TLBDerive)]
-#[tlb_derive(prefix = 0b01, bits_len = 2)]
-pub struct AccountStatusFrozen;
-
-#[derive(Debug, Clone, PartialEq, TLBDerive)]
-#[tlb_derive(prefix = 0b10, bits_len = 2)]
-pub struct AccountStatusActive;
-
-#[derive(Debug, Clone, PartialEq, TLBDerive)]
-#[tlb_derive(prefix = 0b11, bits_len = 2)]
-pub struct AccountStatusNotExist;
-
 impl ShardAccount {
     pub const NON_EXIST: ShardAccount = ShardAccount {
         account: MaybeAccount::None(AccountNone),
@@ -126,8 +132,24 @@ impl ShardAccount {
     };
 }
 
-impl Default for AccountStatus {
-    fn default() -> Self { AccountStatus::NonExist(AccountStatusNotExist) }
+impl StorageUsed {
+    pub fn new(cells: u64, bits: u64) -> Self {
+        Self {
+            cells: VarLenBytes::new_min_len(cells),
+            bits: VarLenBytes::new_min_len(bits),
+        }
+    }
+}
+
+impl From<CellStorageStats> for StorageUsed {
+    fn from(stats: CellStorageStats) -> Self { Self::new(stats.cells, stats.bits) }
+}
+
+impl AccountStorage {
+    /// Unique cells & bits of account_storage cell, including the root - the way the node computes StorageInfo.used
+    pub fn storage_used(&self) -> Result<StorageUsed, TLCoreError> {
+        Ok(TonCellUtils::storage_stats([&self.to_cell()?])?.into())
+    }
 }
 
 impl Default for MaybeAccount {
@@ -249,6 +271,8 @@ mod tests {
         };
 
         assert_eq!(expected, shard_account);
+        let account = shard_account.account.as_account().unwrap();
+        assert_eq!(account.storage.storage_used()?, account.storage_stat.used);
 
         assert_eq!(
             shard_account.cell_hash()?,
@@ -301,6 +325,8 @@ mod tests {
         };
 
         assert_eq!(expected, shard_account);
+        let account = shard_account.account.as_account().unwrap();
+        assert_eq!(account.storage.storage_used()?, account.storage_stat.used);
         assert_eq!(
             shard_account.cell_hash()?,
             TonHash::from_str("355BCC314569D5A3627E374F709464D3F9E0126CDB71DAB860DF18C6867C40D4")?
@@ -4,6 +4,7 @@ use ton_lib_core::constants::{TON_MASTERCHAIN, TON_SHARD_FULL};
 use ton_lib_core::TLBDerive;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockIdExt {
     pub shard_ident: ShardIdent,
     pub seqno: u32,
@@ -1,19 +1,20 @@
 use crate::block_tlb::block_types::block_id_ext::BlockIdExt;
-use crate::block_tlb::block_types::block_prev_info::{BlockPrevInfoAfterMerge, PrevBlockInfo};
+use crate::block_tlb::block_types::block_prev_info::{PrevBlockInfo, PrevBlockInfoRef};
 use crate::block_tlb::GlobalVersion;
 use crate::block_tlb::ShardIdent;
-use ton_lib_core::cell::{CellBuilder, CellParser, TonHash};
+use crate::tlb_adapters::TLBRef;
+use ton_lib_core::cell::TonHash;
 use ton_lib_core::error::TLCoreError;
-use ton_lib_core::traits::tlb::{TLBPrefix, TLB};
 use ton_lib_core::TLBDerive;
 
 const GEN_SOFTWARE_EXISTS_FLAG: u8 = 1;
 
-/// Struct doesn't check invariant during read/write. So you're free to build incorrect block_info cell
-/// For example, set is_master == false and master_ref != None
-/// Don't do it and everything will be fine
+/// Optional fields are written according to flags: writing fails if flag is set, but field is None,
+/// and field is skipped if flag is not set. Other invariants are not checked
 // https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb#L457
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x9bc7a987, bits_len = 32)]
 pub struct BlockInfo {
     pub version: u32,
     pub not_master: bool,
@@ -35,13 +36,18 @@ pub struct BlockInfo {
     pub gen_catchain_seqno: u32,
     pub min_ref_mc_seqno: u32,
     pub prev_key_block_seqno: u32,
+    #[tlb_derive(if = "self.flags & GEN_SOFTWARE_EXISTS_FLAG != 0")]
     pub gen_software: Option<GlobalVersion>,
+    #[tlb_derive(if = "self.not_master", adapter = "TLBRef")]
     pub master_ref: Option<ExtBlockRef>,
+    #[tlb_derive(adapter = "PrevBlockInfoRef::new(self.after_merge)")]
     pub prev_ref: PrevBlockInfo,
+    #[tlb_derive(if = "self.vert_seqno_incr", adapter = "TLBRef")]
     pub prev_vert_ref: Option<ExtBlockRef>,
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtBlockRef {
     pub end_lt: u64,
     pub seqno: u32,
@@ -77,126 +83,13 @@ impl BlockInfo {
     }
 }
 
-impl TLB for BlockInfo {
-    const PREFIX: TLBPrefix = TLBPrefix::new(0x9bc7a987, 32);
-
-    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
-        let version = parser.read_num(32)?;
-        let not_master = parser.read_bit()?;
-        let after_merge = parser.read_bit()?;
-        let before_split = parser.read_bit()?;
-        let after_split = parser.read_bit()?;
-        let want_split = parser.read_bit()?;
-        let want_merge = parser.read_bit()?;
-        let key_block = parser.read_bit()?;
-        let vert_seqno_incr = parser.read_bit()?;
-        let flags = parser.read_num(8)?;
-        let seqno = parser.read_num(32)?;
-        let vert_seqno = parser.read_num(32)?;
-        let shard = ShardIdent::read(parser)?;
-        let gen_utime = parser.read_num(32)?;
-        let start_lt = parser.read_num(64)?;
-        let end_lt = parser.read_num(64)?;
-        let gen_validator_list_has_short: u32 = parser.read_num(32)?;
-        let gen_catchain_seqno: u32 = parser.read_num(32)?;
-        let min_ref_mc_seqno: u32 = parser.read_num(32)?;
-        let prev_key_block_seqno: u32 = parser.read_num(32)?;
-
-        let gen_software = if (flags & GEN_SOFTWARE_EXISTS_FLAG) != 0 {
-            Some(GlobalVersion::read(parser)?)
-        } else {
-            None
-        };
-        let master_ref = if not_master {
-            Some(ExtBlockRef::read(&mut parser.read_next_ref()?.parser())?)
-        } else {
-            None
-        };
-
-        let mut pref_ref_parser = parser.read_next_ref()?.parser();
-        let prev_ref = if after_merge {
-            PrevBlockInfo::AfterMerge(BlockPrevInfoAfterMerge::read(&mut pref_ref_parser)?)
-        } else {
-            PrevBlockInfo::Regular(ExtBlockRef::read(&mut pref_ref_parser)?)
-        };
-
-        let prev_vert_ref = if vert_seqno_incr {
-            Some(ExtBlockRef::read(&mut parser.read_next_ref()?.parser())?)
-        } else {
-            None
-        };
-
-        Ok(Self {
-            version,
-            not_master,
-            after_merge,
-            before_split,
-            after_split,
-            want_split,
-            want_merge,
-            key_block,
-            vert_seqno_incr,
-            flags,
-            seqno,
-            vert_seqno,
-            shard,
-            gen_utime,
-            start_lt,
-            end_lt,
-            gen_validator_list_has_short,
-            gen_catchain_seqno,
-            min_ref_mc_seqno,
-            prev_key_block_seqno,
-            gen_software,
-            master_ref,
-            prev_ref,
-            prev_vert_ref,
-        })
-    }
-
-    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
-        self.version.write(builder)?;
-        self.not_master.write(builder)?;
-        self.after_merge.write(builder)?;
-        self.before_split.write(builder)?;
-        self.after_split.write(builder)?;
-        self.want_split.write(builder)?;
-        self.want_merge.write(builder)?;
-        self.key_block.write(builder)?;
-        self.vert_seqno_incr.write(builder)?;
-        self.flags.write(builder)?;
-        self.seqno.write(builder)?;
-        self.vert_seqno.write(builder)?;
-        self.shard.write(builder)?;
-        self.gen_utime.write(builder)?;
-        self.start_lt.write(builder)?;
-        self.end_lt.write(builder)?;
-        self.gen_validator_list_has_short.write(builder)?;
-        self.gen_catchain_seqno.write(builder)?;
-        self.min_ref_mc_seqno.write(builder)?;
-        self.prev_key_block_seqno.write(builder)?;
-        if let Some(gen_software) = &self.gen_software {
-            gen_software.write(builder)?;
-        }
-        if let Some(master_ref) = &self.master_ref {
-            builder.write_ref(master_ref.to_cell_ref()?)?;
-        }
-
-        builder.write_ref(self.prev_ref.to_cell_ref()?)?;
-        if let Some(prev_vert_ref) = &self.prev_vert_ref {
-            builder.write_ref(prev_vert_ref.to_cell_ref()?)?;
-        }
-
-        Ok(())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::block_tlb::Block;
     use crate::block_tlb::_test_block_data::{MASTER_BLOCK_BOC_HEX, SHARD_BLOCK_BOC_HEX};
+    use crate::block_tlb::{Block, BlockPrevInfoAfterMerge};
     use std::str::FromStr;
+    use ton_lib_core::traits::tlb::TLB;
 
     #[test]
     fn test_block_tlb_block_info_master_key_block() -> anyhow::Result<()> {
@@ -346,6 +239,28 @@ mod tests {
             }
         );
 
+        block_info.after_merge = true;
+        assert_eq!(BlockInfo::from_cell(&block_info.to_cell()?)?, block_info);
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_tlb_block_info_flags() -> anyhow::Result<()> {
+        let mut block_info = BlockInfo {
+            flags: GEN_SOFTWARE_EXISTS_FLAG,
+            ..Default::default()
+        };
+        assert!(block_info.to_cell().is_err());
+
+        block_info.gen_software = Some(GlobalVersion {
+            version: 10,
+            capabilities: 494,
+        });
+        assert_eq!(BlockInfo::from_cell(&block_info.to_cell()?)?, block_info);
+
+        // gen_software is skipped if flag is not set
+        block_info.flags = 0;
+        assert_eq!(BlockInfo::from_cell(&block_info.to_cell()?)?.gen_software, None);
         Ok(())
     }
 }
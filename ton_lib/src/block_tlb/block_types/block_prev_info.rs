@@ -1,14 +1,19 @@
 use crate::block_tlb::block_types::block_info::ExtBlockRef;
 use crate::tlb_adapters::TLBRef;
+use ton_lib_core::cell::{CellBuilder, CellParser};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
 use ton_lib_core::TLBDerive;
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrevBlockInfo {
     Regular(ExtBlockRef),
     AfterMerge(BlockPrevInfoAfterMerge), // is not tested
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockPrevInfoAfterMerge {
     #[tlb_derive(adapter = "TLBRef")]
     pub prev1: ExtBlockRef,
@@ -26,3 +31,23 @@ impl Default for PrevBlockInfo {
         })
     }
 }
+
+/// Reads `^(BlkPrevInfo after_merge)`: variant is selected by `after_merge` flag of BlockInfo
+/// use `#[tlb_derive(adapter = "PrevBlockInfoRef::new(self.after_merge)")]`
+pub struct PrevBlockInfoRef {
+    after_merge: bool,
+}
+
+impl PrevBlockInfoRef {
+    pub fn new(after_merge: bool) -> Self { Self { after_merge } }
+    pub fn read(&self, parser: &mut CellParser) -> Result<PrevBlockInfo, TLCoreError> {
+        let mut ref_parser = parser.read_next_ref_parser()?;
+        match self.after_merge {
+            true => Ok(PrevBlockInfo::AfterMerge(BlockPrevInfoAfterMerge::read(&mut ref_parser)?)),
+            false => Ok(PrevBlockInfo::Regular(ExtBlockRef::read(&mut ref_parser)?)),
+        }
+    }
+    pub fn write(&self, builder: &mut CellBuilder, val: &PrevBlockInfo) -> Result<(), TLCoreError> {
+        builder.write_ref(val.to_cell_ref()?)
+    }
+}
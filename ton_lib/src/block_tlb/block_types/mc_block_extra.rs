@@ -2,22 +2,27 @@ use crate::block_tlb::block_types::block_id_ext::BlockIdExt;
 use crate::block_tlb::ShardDescr;
 use crate::block_tlb::{ConfigParams, CurrencyCollection};
 use crate::block_tlb::{ShardIdent, ShardPfx};
-use crate::tlb_adapters::{BinTree, DictKeyAdapterInto, DictValAdapterTLB, TLBHashMapE};
+use crate::tlb_adapters::{BinTree, DictKeyAdapterInto, DictValAdapterTLB, TLBHashMapAugE, TLBHashMapE};
 use std::collections::HashMap;
 use ton_lib_core::cell::{CellBuilder, CellParser, TonCell, TonCellRef, TonHash};
 use ton_lib_core::error::TLCoreError;
-use ton_lib_core::traits::tlb::{TLBPrefix, TLB};
 use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blame/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb#L593
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xcca5, bits_len = 16)]
 pub struct MCBlockExtra {
     pub key_block: bool,
+    #[tlb_derive(adapter = "ShardHashesAdapter")]
     pub shard_hashes: HashMap<i32, HashMap<ShardPfx, ShardDescr>>, // wc_id -> shard_pfx -> ShardDescr
-    pub shard_fees: Option<TonCellRef>,
-    shard_fees_crated: ShardFeesCreated, // this is a mock to read/write cell properly while we don't support a fair HashmapAugE
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterInto, DictValAdapterTLB, DictValAdapterTLB, _, _, _>::new(96, ShardFeeCreated::merge)"
+    )]
+    pub shard_fees: HashMap<u128, (ShardFeeCreated, ShardFeeCreated)>, // (wc_id . shard) -> (fees, fees)
     // https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb#L597
     pub ref_data: TonCellRef,
+    #[tlb_derive(if = "self.key_block")]
     pub config: Option<ConfigParams>,
 }
 
@@ -38,61 +43,52 @@ impl MCBlockExtra {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, TLBDerive)]
-pub struct ShardFeesCreated {
+// _ fees:CurrencyCollection create:CurrencyCollection = ShardFeeCreated;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardFeeCreated {
     pub fees: CurrencyCollection,
     pub create: CurrencyCollection,
 }
 
-impl TLB for MCBlockExtra {
-    const PREFIX: TLBPrefix = TLBPrefix::new(0xcca5, 16);
-    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
-        let key_block = TLB::read(parser)?;
+impl ShardFeeCreated {
+    /// ShardFees augmentation
+    pub fn merge(&self, other: &Self) -> Result<Self, TLCoreError> {
+        Ok(Self {
+            fees: self.fees.checked_add(&other.fees)?,
+            create: self.create.checked_add(&other.create)?,
+        })
+    }
+}
+
+// wc_id -> BinTree of ShardDescr
+pub(crate) struct ShardHashesAdapter;
+
+impl ShardHashesAdapter {
+    pub(crate) fn read(
+        &self,
+        parser: &mut CellParser,
+    ) -> Result<HashMap<i32, HashMap<ShardPfx, ShardDescr>>, TLCoreError> {
         let shards_dict = TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, u32, TonCellRef>::new(32);
         let mut shard_hashes = HashMap::new();
         for (wc_id, cell_ref) in shards_dict.read(parser)? {
             let cur_hashes = BinTree::<DictValAdapterTLB, _>::read(&mut cell_ref.parser())?;
             shard_hashes.insert(wc_id as i32, cur_hashes);
         }
-        let shard_fees = TLB::read(parser)?;
-        let shard_fees_crated = TLB::read(parser)?;
-        let ref_data = TLB::read(parser)?;
-
-        let config = match key_block {
-            true => Some(TLB::read(parser)?),
-            false => None,
-        };
-        Ok(Self {
-            key_block,
-            shard_hashes,
-            shard_fees,
-            shard_fees_crated,
-            ref_data,
-            config,
-        })
+        Ok(shard_hashes)
     }
 
-    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
-        self.key_block.write(builder)?;
+    pub(crate) fn write(
+        &self,
+        builder: &mut CellBuilder,
+        shard_hashes: &HashMap<i32, HashMap<ShardPfx, ShardDescr>>,
+    ) -> Result<(), TLCoreError> {
         let mut shards_dict = HashMap::<u32, TonCellRef>::new();
-        for (wc_id, shards) in &self.shard_hashes {
+        for (wc_id, shards) in shard_hashes {
             let mut val_builder = TonCell::builder();
             BinTree::<DictValAdapterTLB, _>::write(&mut val_builder, shards)?;
             shards_dict.insert(*wc_id as u32, val_builder.build_ref()?);
         }
-        TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32).write(builder, &shards_dict)?;
-        self.shard_fees.write(builder)?;
-        self.shard_fees_crated.write(builder)?;
-        self.ref_data.write(builder)?;
-        if self.key_block {
-            match &self.config {
-                Some(config) => config.write(builder)?,
-                None => {
-                    let err_msg = "MCBlockExtra has key_block=true, but config_types is None".to_string();
-                    return Err(TLCoreError::TLBWrongData(err_msg));
-                }
-            }
-        }
-        Ok(())
+        TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32).write(builder, &shards_dict)
     }
 }
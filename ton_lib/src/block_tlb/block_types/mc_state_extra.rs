@@ -0,0 +1,139 @@
+use crate::block_tlb::block_types::mc_block_extra::ShardHashesAdapter;
+use crate::block_tlb::{ConfigParams, CurrencyCollection, ExtBlockRef, ShardDescr, ShardPfx};
+use crate::tlb_adapters::{DictKeyAdapterInto, DictKeyAdapterTonHash, DictValAdapterNum, DictValAdapterTLB};
+use crate::tlb_adapters::{TLBHashMapAugE, TLBHashMapE, TLBRef};
+use std::collections::HashMap;
+use ton_lib_core::bail_tl_core;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// masterchain_state_extra#cc26 shard_hashes:ShardHashes config:ConfigParams
+//   ^[ flags:(## 16) { flags <= 1 } validator_info:ValidatorInfo prev_blocks:OldMcBlocksInfo
+//     after_key_block:Bool last_key_block:(Maybe ExtBlkRef) block_create_stats:(flags . 0)?BlockCreateStats ]
+//   global_balance:CurrencyCollection = McStateExtra;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xcc26, bits_len = 16)]
+pub struct MCStateExtra {
+    #[tlb_derive(adapter = "ShardHashesAdapter")]
+    pub shard_hashes: HashMap<i32, HashMap<ShardPfx, ShardDescr>>, // wc_id -> shard_pfx -> ShardDescr
+    pub config: ConfigParams,
+    #[tlb_derive(adapter = "TLBRef")]
+    pub info: MCStateExtraInfo,
+    pub global_balance: CurrencyCollection,
+}
+
+// anonymous ^[...] cell of McStateExtra
+/// block_create_stats is written according to flags: writing fails if flag is set, but field is None,
+/// and field is skipped if flag is not set. `{ flags <= 1 }` is not checked
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MCStateExtraInfo {
+    pub flags: u16,
+    pub validator_info: ValidatorInfo,
+    #[tlb_derive(adapter = "old_mc_blocks_adapter()")]
+    pub prev_blocks: HashMap<u32, (KeyExtBlockRef, KeyMaxLt)>, // mc_seqno -> (block_ref, max_lt)
+    pub after_key_block: bool,
+    pub last_key_block: Option<ExtBlockRef>,
+    #[tlb_derive(if = "self.flags & BLOCK_CREATE_STATS_FLAG != 0")]
+    pub block_create_stats: Option<BlockCreateStats>,
+}
+
+const BLOCK_CREATE_STATS_FLAG: u16 = 1;
+
+// _ (HashmapAugE 32 KeyExtBlkRef KeyMaxLt) = OldMcBlocksInfo;
+type OldMcBlocksAdapter =
+    TLBHashMapAugE<DictKeyAdapterInto, DictValAdapterTLB, DictValAdapterTLB, u32, KeyExtBlockRef, KeyMaxLt>;
+
+fn old_mc_blocks_adapter() -> OldMcBlocksAdapter { OldMcBlocksAdapter::new(32, KeyMaxLt::merge) }
+
+// validator_info$_ validator_list_hash_short:uint32 catchain_seqno:uint32 nx_cc_updated:Bool = ValidatorInfo;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorInfo {
+    pub validator_list_hash_short: u32,
+    pub catchain_seqno: u32,
+    pub nx_cc_updated: bool,
+}
+
+// _ key:Bool blk_ref:ExtBlkRef = KeyExtBlkRef;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyExtBlockRef {
+    pub key: bool,
+    pub block_ref: ExtBlockRef,
+}
+
+// _ key:Bool max_end_lt:uint64 = KeyMaxLt;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyMaxLt {
+    pub key: bool,
+    pub max_end_lt: u64,
+}
+
+impl KeyMaxLt {
+    /// OldMcBlocksInfo augmentation
+    pub fn merge(&self, other: &Self) -> Result<Self, TLCoreError> {
+        Ok(Self {
+            key: self.key || other.key,
+            max_end_lt: self.max_end_lt.max(other.max_end_lt),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockCreateStats {
+    Regular(BlockCreateStatsRegular),
+    Ext(BlockCreateStatsExt),
+}
+
+// block_create_stats#17 counters:(HashmapE 256 CreatorStats) = BlockCreateStats;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x17, bits_len = 8)]
+pub struct BlockCreateStatsRegular {
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub counters: HashMap<TonHash, CreatorStats>,
+}
+
+// block_create_stats_ext#34 counters:(HashmapAugE 256 CreatorStats uint32) = BlockCreateStats;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x34, bits_len = 8)]
+pub struct BlockCreateStatsExt {
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterNum<32>, _, _, _>::new(256, creators_count_merge)"
+    )]
+    pub counters: HashMap<TonHash, (CreatorStats, u32)>, // creator_pubkey -> (stats, creators_count)
+}
+
+// creators count in the subtree
+fn creators_count_merge(left: &u32, right: &u32) -> Result<u32, TLCoreError> {
+    match left.checked_add(*right) {
+        Some(sum) => Ok(sum),
+        None => bail_tl_core!("creators count overflow: {left} + {right}"),
+    }
+}
+
+// creator_info#4 mc_blocks:Counters shard_blocks:Counters = CreatorStats;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x4, bits_len = 4)]
+pub struct CreatorStats {
+    pub mc_blocks: Counters,
+    pub shard_blocks: Counters,
+}
+
+// counters#_ last_updated:uint32 total:uint64 cnt2048:uint64 cnt65536:uint64 = Counters;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Counters {
+    pub last_updated: u32,
+    pub total: u64,
+    pub cnt2048: u64,
+    pub cnt65536: u64,
+}
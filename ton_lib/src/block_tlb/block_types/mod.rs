@@ -4,6 +4,8 @@ mod block_id_ext;
 mod block_info;
 mod block_prev_info;
 mod mc_block_extra;
+mod mc_state_extra;
+mod value_flow;
 
 pub use block::*;
 pub use block_extra::*;
@@ -11,3 +13,5 @@ pub use block_id_ext::*;
 pub use block_info::*;
 pub use block_prev_info::*;
 pub use mc_block_extra::*;
+pub use mc_state_extra::*;
+pub use value_flow::*;
@@ -0,0 +1,80 @@
+use crate::block_tlb::CurrencyCollection;
+use crate::tlb_adapters::TLBRef;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValueFlow {
+    // value_flow#b8e48dfb ^[ from_prev_blk:CurrencyCollection to_next_blk:CurrencyCollection
+    //     imported:CurrencyCollection exported:CurrencyCollection ]
+    //   fees_collected:CurrencyCollection
+    //   ^[ fees_imported:CurrencyCollection recovered:CurrencyCollection
+    //     created:CurrencyCollection minted:CurrencyCollection ] = ValueFlow;
+    #[tlb_derive(prefix = 0xb8e48dfb, bits_len = 32)]
+    V1 {
+        #[tlb_derive(adapter = "TLBRef")]
+        transfers: ValueFlowTransfers,
+        fees_collected: CurrencyCollection,
+        #[tlb_derive(adapter = "TLBRef")]
+        issued: ValueFlowIssued,
+    },
+    // value_flow_v2#3ebf98b7 ^[ ... ] fees_collected:CurrencyCollection burned:CurrencyCollection ^[ ... ] = ValueFlow;
+    #[tlb_derive(prefix = 0x3ebf98b7, bits_len = 32)]
+    V2 {
+        #[tlb_derive(adapter = "TLBRef")]
+        transfers: ValueFlowTransfers,
+        fees_collected: CurrencyCollection,
+        burned: CurrencyCollection,
+        #[tlb_derive(adapter = "TLBRef")]
+        issued: ValueFlowIssued,
+    },
+}
+
+// first anonymous ^[...] cell of ValueFlow
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueFlowTransfers {
+    pub from_prev_blk: CurrencyCollection,
+    pub to_next_blk: CurrencyCollection,
+    pub imported: CurrencyCollection,
+    pub exported: CurrencyCollection,
+}
+
+// second anonymous ^[...] cell of ValueFlow
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueFlowIssued {
+    pub fees_imported: CurrencyCollection,
+    pub recovered: CurrencyCollection,
+    pub created: CurrencyCollection,
+    pub minted: CurrencyCollection,
+}
+
+impl ValueFlow {
+    pub fn transfers(&self) -> &ValueFlowTransfers {
+        match self {
+            ValueFlow::V1 { transfers, .. } | ValueFlow::V2 { transfers, .. } => transfers,
+        }
+    }
+
+    pub fn fees_collected(&self) -> &CurrencyCollection {
+        match self {
+            ValueFlow::V1 { fees_collected, .. } | ValueFlow::V2 { fees_collected, .. } => fees_collected,
+        }
+    }
+
+    /// Some for value_flow_v2 only
+    pub fn burned(&self) -> Option<&CurrencyCollection> {
+        match self {
+            ValueFlow::V1 { .. } => None,
+            ValueFlow::V2 { burned, .. } => Some(burned),
+        }
+    }
+
+    pub fn issued(&self) -> &ValueFlowIssued {
+        match self {
+            ValueFlow::V1 { issued, .. } | ValueFlow::V2 { issued, .. } => issued,
+        }
+    }
+}
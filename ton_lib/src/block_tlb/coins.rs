@@ -6,16 +6,19 @@ use num_traits::ToPrimitive;
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
+use ton_lib_core::bail_tl_core;
 use ton_lib_core::error::TLCoreError;
 use ton_lib_core::types::tlb_core::VarLenBytes;
 use ton_lib_core::TLBDerive;
 
 /// https://github.com/ton-blockchain/ton/blob/050a984163a53df16fb03f66cc445c34bfed48ed/crypto/block/block.tlb#L116
 #[derive(Clone, Debug, PartialEq, Eq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coins(VarLenBytes<u128, 4>);
 
 /// https://github.com/ton-blockchain/ton/blob/050a984163a53df16fb03f66cc445c34bfed48ed/crypto/block/block.tlb#L124
 #[derive(Default, Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurrencyCollection {
     pub grams: Coins,
     #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32)")]
@@ -27,6 +30,8 @@ impl Coins {
         data: 0u128,
         bits_len: 0,
     });
+    /// `VarUInteger 16` stores up to 15 bytes
+    const MAX_BITS_LEN: u32 = 120;
 
     pub fn new<T: Into<u128>>(amount: T) -> Self {
         let amount = amount.into();
@@ -62,12 +67,39 @@ impl Coins {
 }
 
 impl CurrencyCollection {
+    /// `VarUInteger 32` stores up to 31 bytes
+    const OTHER_MAX_BITS_LEN: u64 = 248;
+
     pub fn new<T: Into<u128>>(grams: T) -> Self {
         Self {
             grams: Coins::new(grams),
             other: Default::default(),
         }
     }
+
+    /// Sums grams and other currencies. Used to merge extras of augmented dicts
+    ///
+    /// Fails if grams don't fit `VarUInteger 16` (120 bits) or any other currency doesn't fit `VarUInteger 32` (248 bits)
+    pub fn checked_add(&self, other: &Self) -> Result<Self, TLCoreError> {
+        let grams = match self.grams.checked_add(*other.grams) {
+            Some(grams) if grams >> Coins::MAX_BITS_LEN == 0 => grams,
+            _ => bail_tl_core!("grams overflow: {} + {}", *self.grams, *other.grams),
+        };
+        let mut result = Self::new(grams);
+        result.other = self.other.clone();
+        for (id, amount) in &other.other {
+            let sum = match result.other.get(id) {
+                Some(cur_amount) => &cur_amount.data + &amount.data,
+                None => amount.data.clone(),
+            };
+            if sum.bits() > Self::OTHER_MAX_BITS_LEN {
+                bail_tl_core!("extra currency {id} overflow: {sum}");
+            }
+            let bits_len = sum.bits().div_ceil(8) as usize * 8;
+            result.other.insert(*id, VarLenBytes::new(sum, bits_len));
+        }
+        Ok(result)
+    }
 }
 
 mod traits_impl {
@@ -130,4 +162,30 @@ mod tests {
         assert_eq!(cell_serial, cell);
         Ok(())
     }
+
+    #[test]
+    fn test_currency_collection_checked_add() -> anyhow::Result<()> {
+        let mut first = CurrencyCollection::new(100u32);
+        first.other.insert(1, VarLenBytes::new(BigUint::from(255u32), 8));
+        let mut second = CurrencyCollection::new(200u32);
+        second.other.insert(1, VarLenBytes::new(BigUint::from(1u32), 8));
+        second.other.insert(2, VarLenBytes::new(BigUint::from(5u32), 8));
+
+        let sum = first.checked_add(&second)?;
+        assert_eq!(sum.grams, 300u32.into());
+        assert_eq!(sum.other[&1], VarLenBytes::new(BigUint::from(256u32), 16));
+        assert_eq!(sum.other[&2], VarLenBytes::new(BigUint::from(5u32), 8));
+        assert!(CurrencyCollection::new(u128::MAX).checked_add(&second).is_err());
+
+        let max_grams = CurrencyCollection::new((1u128 << 120) - 1);
+        assert!(max_grams.checked_add(&CurrencyCollection::new(0u32)).is_ok());
+        assert!(max_grams.checked_add(&CurrencyCollection::new(1u32)).is_err());
+
+        let mut max_other = CurrencyCollection::new(0u32);
+        let max_value = (BigUint::from(1u32) << 248) - 1u32;
+        max_other.other.insert(1, VarLenBytes::new(max_value, 248));
+        assert!(max_other.checked_add(&CurrencyCollection::new(0u32)).is_ok());
+        assert!(max_other.checked_add(&second).is_err());
+        Ok(())
+    }
 }
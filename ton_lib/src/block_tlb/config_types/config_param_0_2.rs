@@ -0,0 +1,24 @@
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ config_addr:bits256 = ConfigParam 0;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam0 {
+    pub config_addr: TonHash,
+}
+
+// _ elector_addr:bits256 = ConfigParam 1;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam1 {
+    pub elector_addr: TonHash,
+}
+
+// _ minter_addr:bits256 = ConfigParam 2; // ConfigParam 0 is used if absent
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam2 {
+    pub minter_addr: TonHash,
+}
@@ -0,0 +1,140 @@
+use crate::tlb_adapters::ConstLen;
+use crate::tlb_adapters::{DictKeyAdapterInto, DictValAdapterTLB, TLBHashMapE};
+use std::collections::HashMap;
+use ton_lib_core::cell::{CellBuilder, CellParser, TonHash};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ workchains:(HashmapE 32 WorkchainDescr) = ConfigParam 12;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam12 {
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32)")]
+    pub workchains: HashMap<u32, WorkchainDescr>, // wc_id -> descr
+}
+
+// workchain#a6 enabled_since:uint32 actual_min_split:(## 8) min_split:(## 8) max_split:(## 8)
+//   basic:(## 1) active:Bool accept_msgs:Bool flags:(## 13) { flags = 0 }
+//   zerostate_root_hash:bits256 zerostate_file_hash:bits256
+//   version:uint32 format:(WorkchainFormat basic) = WorkchainDescr;
+// workchain_v2#a7 ... format:(WorkchainFormat basic) split_merge_timings:WcSplitMergeTimings = WorkchainDescr;
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkchainDescr {
+    pub enabled_since: u32,
+    pub actual_min_split: u8,
+    pub min_split: u8,
+    pub max_split: u8,
+    pub basic: bool,
+    pub active: bool,
+    pub accept_msgs: bool,
+    pub flags: u16,
+    pub zerostate_root_hash: TonHash,
+    pub zerostate_file_hash: TonHash,
+    pub version: u32,
+    pub format: WorkchainFormat,
+    pub split_merge_timings: Option<WcSplitMergeTimings>, // Some for workchain_v2 only
+}
+
+impl WorkchainDescr {
+    const PREFIX_V1: u8 = 0xa6;
+    const PREFIX_V2: u8 = 0xa7;
+}
+
+// there are 2 constructors, which differ by `split_merge_timings` field only
+// so implement TLB manually, including prefix handling
+impl TLB for WorkchainDescr {
+    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+        let is_v2 = match parser.read_num::<u8>(8)? {
+            Self::PREFIX_V1 => false,
+            Self::PREFIX_V2 => true,
+            x => return Err(TLCoreError::TLBWrongData(format!("Invalid WorkchainDescr prefix: {x:x}"))),
+        };
+        Ok(Self {
+            enabled_since: TLB::read(parser)?,
+            actual_min_split: TLB::read(parser)?,
+            min_split: TLB::read(parser)?,
+            max_split: TLB::read(parser)?,
+            basic: TLB::read(parser)?,
+            active: TLB::read(parser)?,
+            accept_msgs: TLB::read(parser)?,
+            flags: parser.read_num(13)?,
+            zerostate_root_hash: TLB::read(parser)?,
+            zerostate_file_hash: TLB::read(parser)?,
+            version: TLB::read(parser)?,
+            format: TLB::read(parser)?,
+            split_merge_timings: match is_v2 {
+                true => Some(TLB::read(parser)?),
+                false => None,
+            },
+        })
+    }
+
+    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+        let prefix = match self.split_merge_timings {
+            Some(_) => Self::PREFIX_V2,
+            None => Self::PREFIX_V1,
+        };
+        builder.write_num(&prefix, 8)?;
+        self.enabled_since.write(builder)?;
+        self.actual_min_split.write(builder)?;
+        self.min_split.write(builder)?;
+        self.max_split.write(builder)?;
+        self.basic.write(builder)?;
+        self.active.write(builder)?;
+        self.accept_msgs.write(builder)?;
+        builder.write_num(&self.flags, 13)?;
+        self.zerostate_root_hash.write(builder)?;
+        self.zerostate_file_hash.write(builder)?;
+        self.version.write(builder)?;
+        self.format.write(builder)?;
+        if let Some(timings) = &self.split_merge_timings {
+            timings.write(builder)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorkchainFormat {
+    Basic(WorkchainFormatBasic),
+    Ext(WorkchainFormatExt),
+}
+
+// wfmt_basic#1 vm_version:int32 vm_mode:uint64 = WorkchainFormat 1;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x1, bits_len = 4)]
+pub struct WorkchainFormatBasic {
+    pub vm_version: i32,
+    pub vm_mode: u64,
+}
+
+// wfmt_ext#0 min_addr_len:(## 12) max_addr_len:(## 12) addr_len_step:(## 12) workchain_type_id:(## 32) = WorkchainFormat 0;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x0, bits_len = 4)]
+pub struct WorkchainFormatExt {
+    #[tlb_derive(bits_len = 12)]
+    pub min_addr_len: u16,
+    #[tlb_derive(bits_len = 12)]
+    pub max_addr_len: u16,
+    #[tlb_derive(bits_len = 12)]
+    pub addr_len_step: u16,
+    pub workchain_type_id: u32,
+}
+
+// wc_split_merge_timings#0 split_merge_delay:uint32 split_merge_interval:uint32
+//   min_split_merge_interval:uint32 max_split_merge_delay:uint32 = WcSplitMergeTimings;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x0, bits_len = 4)]
+pub struct WcSplitMergeTimings {
+    pub split_merge_delay: u32,
+    pub split_merge_interval: u32,
+    pub min_split_merge_interval: u32,
+    pub max_split_merge_delay: u32,
+}
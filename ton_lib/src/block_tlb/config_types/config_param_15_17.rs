@@ -0,0 +1,33 @@
+use crate::block_tlb::Coins;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ validators_elected_for:uint32 elections_start_before:uint32
+//   elections_end_before:uint32 stake_held_for:uint32 = ConfigParam 15;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam15 {
+    pub validators_elected_for: u32,
+    pub elections_start_before: u32,
+    pub elections_end_before: u32,
+    pub stake_held_for: u32,
+}
+
+// _ max_validators:(## 16) max_main_validators:(## 16) min_validators:(## 16) = ConfigParam 16;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam16 {
+    pub max_validators: u16,
+    pub max_main_validators: u16,
+    pub min_validators: u16,
+}
+
+// _ min_stake:Grams max_stake:Grams min_total_stake:Grams max_stake_factor:uint32 = ConfigParam 17;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigParam17 {
+    pub min_stake: Coins,
+    pub max_stake: Coins,
+    pub min_total_stake: Coins,
+    pub max_stake_factor: u32,
+}
@@ -7,6 +7,7 @@ use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blame/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb#L698
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigParam18 {
     #[tlb_derive(adapter = "TLBHashMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32)")]
     pub storage_prices: HashMap<u32, StoragePrices>,
@@ -22,6 +23,7 @@ impl ConfigParam18 {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0xcc, bits_len = 8)]
 pub struct StoragePrices {
     pub utime_since: u32,
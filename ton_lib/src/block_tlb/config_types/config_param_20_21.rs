@@ -0,0 +1,111 @@
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// config_mc_gas_prices#_ GasLimitsPrices = ConfigParam 20;
+// config_gas_prices#_ GasLimitsPrices = ConfigParam 21;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GasLimitsPrices {
+    // gas_prices#dd gas_price:uint64 gas_limit:uint64 gas_credit:uint64 block_gas_limit:uint64
+    //   freeze_due_limit:uint64 delete_due_limit:uint64 = GasLimitsPrices;
+    #[tlb_derive(prefix = 0xdd, bits_len = 8)]
+    Regular {
+        gas_price: u64,
+        gas_limit: u64,
+        gas_credit: u64,
+        block_gas_limit: u64,
+        freeze_due_limit: u64,
+        delete_due_limit: u64,
+    },
+    // gas_prices_ext#de gas_price:uint64 gas_limit:uint64 special_gas_limit:uint64 gas_credit:uint64
+    //   block_gas_limit:uint64 freeze_due_limit:uint64 delete_due_limit:uint64 = GasLimitsPrices;
+    #[tlb_derive(prefix = 0xde, bits_len = 8)]
+    Ext {
+        gas_price: u64,
+        gas_limit: u64,
+        special_gas_limit: u64,
+        gas_credit: u64,
+        block_gas_limit: u64,
+        freeze_due_limit: u64,
+        delete_due_limit: u64,
+    },
+    // gas_flat_pfx#d1 flat_gas_limit:uint64 flat_gas_price:uint64 other:GasLimitsPrices = GasLimitsPrices;
+    #[tlb_derive(prefix = 0xd1, bits_len = 8)]
+    FlatPfx {
+        flat_gas_limit: u64,
+        flat_gas_price: u64,
+        other: Box<GasLimitsPrices>,
+    },
+}
+
+// accessors look through gas_flat_pfx to the wrapped constructor
+impl GasLimitsPrices {
+    /// (flat_gas_limit, flat_gas_price), zeros if prices are not wrapped into gas_flat_pfx
+    pub fn flat_gas(&self) -> (u64, u64) {
+        match self {
+            GasLimitsPrices::FlatPfx {
+                flat_gas_limit,
+                flat_gas_price,
+                ..
+            } => (*flat_gas_limit, *flat_gas_price),
+            _ => (0, 0),
+        }
+    }
+
+    pub fn gas_price(&self) -> u64 {
+        match self {
+            GasLimitsPrices::Regular { gas_price, .. } | GasLimitsPrices::Ext { gas_price, .. } => *gas_price,
+            GasLimitsPrices::FlatPfx { other, .. } => other.gas_price(),
+        }
+    }
+
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            GasLimitsPrices::Regular { gas_limit, .. } | GasLimitsPrices::Ext { gas_limit, .. } => *gas_limit,
+            GasLimitsPrices::FlatPfx { other, .. } => other.gas_limit(),
+        }
+    }
+
+    /// Some for gas_prices_ext only
+    pub fn special_gas_limit(&self) -> Option<u64> {
+        match self {
+            GasLimitsPrices::Regular { .. } => None,
+            GasLimitsPrices::Ext { special_gas_limit, .. } => Some(*special_gas_limit),
+            GasLimitsPrices::FlatPfx { other, .. } => other.special_gas_limit(),
+        }
+    }
+
+    pub fn gas_credit(&self) -> u64 {
+        match self {
+            GasLimitsPrices::Regular { gas_credit, .. } | GasLimitsPrices::Ext { gas_credit, .. } => *gas_credit,
+            GasLimitsPrices::FlatPfx { other, .. } => other.gas_credit(),
+        }
+    }
+
+    pub fn block_gas_limit(&self) -> u64 {
+        match self {
+            GasLimitsPrices::Regular { block_gas_limit, .. } | GasLimitsPrices::Ext { block_gas_limit, .. } => {
+                *block_gas_limit
+            }
+            GasLimitsPrices::FlatPfx { other, .. } => other.block_gas_limit(),
+        }
+    }
+
+    pub fn freeze_due_limit(&self) -> u64 {
+        match self {
+            GasLimitsPrices::Regular { freeze_due_limit, .. } | GasLimitsPrices::Ext { freeze_due_limit, .. } => {
+                *freeze_due_limit
+            }
+            GasLimitsPrices::FlatPfx { other, .. } => other.freeze_due_limit(),
+        }
+    }
+
+    pub fn delete_due_limit(&self) -> u64 {
+        match self {
+            GasLimitsPrices::Regular { delete_due_limit, .. } | GasLimitsPrices::Ext { delete_due_limit, .. } => {
+                *delete_due_limit
+            }
+            GasLimitsPrices::FlatPfx { other, .. } => other.delete_due_limit(),
+        }
+    }
+}
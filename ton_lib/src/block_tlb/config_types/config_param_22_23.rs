@@ -0,0 +1,91 @@
+use ton_lib_core::cell::{CellBuilder, CellParser};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// block_limits#5d bytes:ParamLimits gas:ParamLimits lt_delta:ParamLimits = BlockLimits;
+// block_limits_v2#5e bytes:ParamLimits gas:ParamLimits lt_delta:ParamLimits
+//   collated_data:ParamLimits imported_msg_queue:ImportedMsgQueueLimits = BlockLimits;
+//
+// config_mc_block_limits#_ BlockLimits = ConfigParam 22;
+// config_block_limits#_ BlockLimits = ConfigParam 23;
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockLimits {
+    pub bytes: ParamLimits,
+    pub gas: ParamLimits,
+    pub lt_delta: ParamLimits,
+    pub v2_limits: Option<BlockLimitsV2>, // Some for block_limits_v2 only
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockLimitsV2 {
+    pub collated_data: ParamLimits,
+    pub imported_msg_queue: ImportedMsgQueueLimits,
+}
+
+impl BlockLimits {
+    const PREFIX_V1: u8 = 0x5d;
+    const PREFIX_V2: u8 = 0x5e;
+}
+
+// there are 2 constructors, v2 just adds fields to the end
+// so implement TLB manually, including prefix handling
+impl TLB for BlockLimits {
+    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+        let is_v2 = match parser.read_num::<u8>(8)? {
+            Self::PREFIX_V1 => false,
+            Self::PREFIX_V2 => true,
+            x => return Err(TLCoreError::TLBWrongData(format!("Invalid BlockLimits prefix: {x:x}"))),
+        };
+        Ok(Self {
+            bytes: TLB::read(parser)?,
+            gas: TLB::read(parser)?,
+            lt_delta: TLB::read(parser)?,
+            v2_limits: match is_v2 {
+                true => Some(BlockLimitsV2 {
+                    collated_data: TLB::read(parser)?,
+                    imported_msg_queue: TLB::read(parser)?,
+                }),
+                false => None,
+            },
+        })
+    }
+
+    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+        let prefix = match self.v2_limits {
+            Some(_) => Self::PREFIX_V2,
+            None => Self::PREFIX_V1,
+        };
+        builder.write_num(&prefix, 8)?;
+        self.bytes.write(builder)?;
+        self.gas.write(builder)?;
+        self.lt_delta.write(builder)?;
+        if let Some(v2_limits) = &self.v2_limits {
+            v2_limits.collated_data.write(builder)?;
+            v2_limits.imported_msg_queue.write(builder)?;
+        }
+        Ok(())
+    }
+}
+
+// param_limits#c3 underload:# soft_limit:# { underload <= soft_limit } hard_limit:# { soft_limit <= hard_limit } = ParamLimits;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xc3, bits_len = 8)]
+pub struct ParamLimits {
+    pub underload: u32,
+    pub soft_limit: u32,
+    pub hard_limit: u32,
+}
+
+// imported_msg_queue_limits#d3 max_bytes:# max_msgs:# = ImportedMsgQueueLimits;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xd3, bits_len = 8)]
+pub struct ImportedMsgQueueLimits {
+    pub max_bytes: u32,
+    pub max_msgs: u32,
+}
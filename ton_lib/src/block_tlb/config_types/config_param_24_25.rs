@@ -0,0 +1,19 @@
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// msg_forward_prices#ea lump_price:uint64 bit_price:uint64 cell_price:uint64
+//   ihr_price_factor:uint32 first_frac:uint16 next_frac:uint16 = MsgForwardPrices;
+//
+// config_mc_fwd_prices#_ MsgForwardPrices = ConfigParam 24;
+// config_fwd_prices#_ MsgForwardPrices = ConfigParam 25;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xea, bits_len = 8)]
+pub struct MsgForwardPrices {
+    pub lump_price: u64,
+    pub bit_price: u64,
+    pub cell_price: u64,
+    pub ihr_price_factor: u32,
+    pub first_frac: u16,
+    pub next_frac: u16,
+}
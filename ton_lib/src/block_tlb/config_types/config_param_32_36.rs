@@ -0,0 +1,138 @@
+use crate::tlb_adapters::{DictKeyAdapterInto, DictValAdapterTLB, TLBHashMap, TLBHashMapE};
+use std::collections::HashMap;
+use ton_lib_core::cell::{CellBuilder, CellParser, TonHash};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// validators#11 utime_since:uint32 utime_until:uint32 total:(## 16) main:(## 16) { main <= total } { main >= 1 }
+//   list:(Hashmap 16 ValidatorDescr) = ValidatorSet;
+// validators_ext#12 utime_since:uint32 utime_until:uint32 total:(## 16) main:(## 16) { main <= total } { main >= 1 }
+//   total_weight:uint64 list:(HashmapE 16 ValidatorDescr) = ValidatorSet;
+//
+// _ prev_validators:ValidatorSet = ConfigParam 32;
+// _ prev_temp_validators:ValidatorSet = ConfigParam 33;
+// _ cur_validators:ValidatorSet = ConfigParam 34;
+// _ cur_temp_validators:ValidatorSet = ConfigParam 35;
+// _ next_validators:ValidatorSet = ConfigParam 36;
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorSet {
+    pub utime_since: u32,
+    pub utime_until: u32,
+    pub total: u16,
+    pub main: u16,
+    pub total_weight: Option<u64>, // Some for validators_ext only
+    pub list: HashMap<u16, ValidatorDescr>,
+}
+
+impl ValidatorSet {
+    const PREFIX_REGULAR: u8 = 0x11;
+    const PREFIX_EXT: u8 = 0x12;
+}
+
+// there are 2 constructors, which differ by `total_weight` field and list emptiness only
+// so implement TLB manually, including prefix handling
+impl TLB for ValidatorSet {
+    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+        let is_ext = match parser.read_num::<u8>(8)? {
+            Self::PREFIX_REGULAR => false,
+            Self::PREFIX_EXT => true,
+            x => return Err(TLCoreError::TLBWrongData(format!("Invalid ValidatorSet prefix: {x:x}"))),
+        };
+        let utime_since = TLB::read(parser)?;
+        let utime_until = TLB::read(parser)?;
+        let total = TLB::read(parser)?;
+        let main = TLB::read(parser)?;
+        let (total_weight, list) = match is_ext {
+            true => {
+                let total_weight = TLB::read(parser)?;
+                (Some(total_weight), TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(16).read(parser)?)
+            }
+            false => (None, TLBHashMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(16).read(parser)?),
+        };
+        Ok(Self {
+            utime_since,
+            utime_until,
+            total,
+            main,
+            total_weight,
+            list,
+        })
+    }
+
+    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+        let prefix = match self.total_weight {
+            Some(_) => Self::PREFIX_EXT,
+            None => Self::PREFIX_REGULAR,
+        };
+        builder.write_num(&prefix, 8)?;
+        self.utime_since.write(builder)?;
+        self.utime_until.write(builder)?;
+        self.total.write(builder)?;
+        self.main.write(builder)?;
+        match &self.total_weight {
+            Some(total_weight) => {
+                total_weight.write(builder)?;
+                TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(16).write(builder, &self.list)
+            }
+            None => TLBHashMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(16).write(builder, &self.list),
+        }
+    }
+}
+
+// validator#53 public_key:SigPubKey weight:uint64 = ValidatorDescr;
+// validator_addr#73 public_key:SigPubKey weight:uint64 adnl_addr:bits256 = ValidatorDescr;
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorDescr {
+    pub public_key: SigPubKey,
+    pub weight: u64,
+    pub adnl_addr: Option<TonHash>, // Some for validator_addr only
+}
+
+impl ValidatorDescr {
+    const PREFIX_REGULAR: u8 = 0x53;
+    const PREFIX_ADDR: u8 = 0x73;
+}
+
+impl TLB for ValidatorDescr {
+    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+        let has_addr = match parser.read_num::<u8>(8)? {
+            Self::PREFIX_REGULAR => false,
+            Self::PREFIX_ADDR => true,
+            x => return Err(TLCoreError::TLBWrongData(format!("Invalid ValidatorDescr prefix: {x:x}"))),
+        };
+        Ok(Self {
+            public_key: TLB::read(parser)?,
+            weight: TLB::read(parser)?,
+            adnl_addr: match has_addr {
+                true => Some(TLB::read(parser)?),
+                false => None,
+            },
+        })
+    }
+
+    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+        let prefix = match self.adnl_addr {
+            Some(_) => Self::PREFIX_ADDR,
+            None => Self::PREFIX_REGULAR,
+        };
+        builder.write_num(&prefix, 8)?;
+        self.public_key.write(builder)?;
+        self.weight.write(builder)?;
+        if let Some(adnl_addr) = &self.adnl_addr {
+            adnl_addr.write(builder)?;
+        }
+        Ok(())
+    }
+}
+
+// ed25519_pubkey#8e81278a pubkey:bits256 = SigPubKey;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x8e81278a, bits_len = 32)]
+pub struct SigPubKey {
+    pub pubkey: TonHash,
+}
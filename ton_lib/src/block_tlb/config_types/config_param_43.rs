@@ -0,0 +1,157 @@
+use crate::block_tlb::{CommonMsgInfo, Msg, StorageUsed, StorageUsedShort};
+use ton_lib_core::bail_tl_core;
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ SizeLimitsConfig = ConfigParam 43;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SizeLimitsConfig {
+    V1(SizeLimitsConfigV1),
+    V2(SizeLimitsConfigV2),
+}
+
+// size_limits_config#01 max_msg_bits:uint32 max_msg_cells:uint32 max_library_cells:uint32 max_vm_data_depth:uint16
+//   max_ext_msg_size:uint32 max_ext_msg_depth:uint16 = SizeLimitsConfig;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x01, bits_len = 8)]
+pub struct SizeLimitsConfigV1 {
+    pub max_msg_bits: u32,
+    pub max_msg_cells: u32,
+    pub max_library_cells: u32,
+    pub max_vm_data_depth: u16,
+    pub max_ext_msg_size: u32,
+    pub max_ext_msg_depth: u16,
+}
+
+// size_limits_config_v2#02 max_msg_bits:uint32 max_msg_cells:uint32 max_library_cells:uint32 max_vm_data_depth:uint16
+//   max_ext_msg_size:uint32 max_ext_msg_depth:uint16 max_acc_state_cells:uint32 max_acc_state_bits:uint32
+//   max_acc_public_libraries:uint32 defer_out_queue_size_limit:uint32 max_msg_extra_currencies:uint32
+//   max_acc_fixed_prefix_length:uint8 = SizeLimitsConfig;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x02, bits_len = 8)]
+pub struct SizeLimitsConfigV2 {
+    pub max_msg_bits: u32,
+    pub max_msg_cells: u32,
+    pub max_library_cells: u32,
+    pub max_vm_data_depth: u16,
+    pub max_ext_msg_size: u32,
+    pub max_ext_msg_depth: u16,
+    pub max_acc_state_cells: u32,
+    pub max_acc_state_bits: u32,
+    pub max_acc_public_libraries: u32,
+    pub defer_out_queue_size_limit: u32,
+    pub max_msg_extra_currencies: u32,
+    pub max_acc_fixed_prefix_length: u8,
+}
+
+// V2-only limits are filled with the node defaults for V1
+#[rustfmt::skip]
+impl SizeLimitsConfig {
+    pub fn max_msg_bits(&self) -> u32 { match self { Self::V1(v1) => v1.max_msg_bits, Self::V2(v2) => v2.max_msg_bits } }
+    pub fn max_msg_cells(&self) -> u32 { match self { Self::V1(v1) => v1.max_msg_cells, Self::V2(v2) => v2.max_msg_cells } }
+    pub fn max_ext_msg_size(&self) -> u32 { match self { Self::V1(v1) => v1.max_ext_msg_size, Self::V2(v2) => v2.max_ext_msg_size } }
+    pub fn max_ext_msg_depth(&self) -> u16 { match self { Self::V1(v1) => v1.max_ext_msg_depth, Self::V2(v2) => v2.max_ext_msg_depth } }
+    pub fn max_acc_state_cells(&self) -> u32 { match self { Self::V1(_) => 1 << 16, Self::V2(v2) => v2.max_acc_state_cells } }
+    pub fn max_acc_state_bits(&self) -> u32 { match self { Self::V1(_) => (1 << 16) * 1023, Self::V2(v2) => v2.max_acc_state_bits } }
+    pub fn max_msg_extra_currencies(&self) -> u32 { match self { Self::V1(_) => 2, Self::V2(v2) => v2.max_msg_extra_currencies } }
+}
+
+impl SizeLimitsConfig {
+    /// Checks outbound message the same way as action phase does
+    pub fn check_msg(&self, msg: &Msg, extra_currency_v2: bool) -> Result<StorageUsedShort, TLCoreError> {
+        if let CommonMsgInfo::Int(info) = &msg.info {
+            let extra_currencies = info.value.other.len();
+            if extra_currencies > self.max_msg_extra_currencies() as usize {
+                let max = self.max_msg_extra_currencies();
+                bail_tl_core!("Too many extra currencies in msg: {extra_currencies} > {max}");
+            }
+        }
+        let size = msg.size(extra_currency_v2)?;
+        if *size.cells > self.max_msg_cells() as u64 || *size.bits > self.max_msg_bits() as u64 {
+            let (cells, bits) = (*size.cells, *size.bits);
+            let (max_cells, max_bits) = (self.max_msg_cells(), self.max_msg_bits());
+            bail_tl_core!("Msg is too large: cells={cells}, bits={bits}, max_cells={max_cells}, max_bits={max_bits}");
+        }
+        Ok(size)
+    }
+
+    /// Checks inbound external message BOC the same way as the node does before accepting it
+    pub fn check_ext_msg(&self, msg: &Msg) -> Result<(), TLCoreError> {
+        let boc_size = msg.to_boc()?.len();
+        if boc_size > self.max_ext_msg_size() as usize {
+            bail_tl_core!("External msg is too large: {boc_size} > {}", self.max_ext_msg_size());
+        }
+        let depth = msg.to_cell()?.depth()?;
+        if depth > self.max_ext_msg_depth() {
+            bail_tl_core!("External msg is too deep: {depth} > {}", self.max_ext_msg_depth());
+        }
+        Ok(())
+    }
+
+    /// Checks account storage against max_acc_state_cells & max_acc_state_bits
+    pub fn check_acc_state(&self, used: &StorageUsed) -> Result<(), TLCoreError> {
+        if *used.cells > self.max_acc_state_cells() as u64 || *used.bits > self.max_acc_state_bits() as u64 {
+            let (cells, bits) = (*used.cells, *used.bits);
+            let (max_cells, max_bits) = (self.max_acc_state_cells(), self.max_acc_state_bits());
+            bail_tl_core!(
+                "Account state is too large: cells={cells}, bits={bits}, max_cells={max_cells}, max_bits={max_bits}"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_tlb::CommonMsgInfoInt;
+    use std::collections::HashMap;
+    use ton_lib_core::cell::TonCell;
+    use ton_lib_core::types::tlb_core::VarLenBytes;
+
+    fn size_limits() -> SizeLimitsConfig {
+        SizeLimitsConfig::V1(SizeLimitsConfigV1 {
+            max_msg_bits: 1 << 21,
+            max_msg_cells: 2,
+            max_library_cells: 1000,
+            max_vm_data_depth: 512,
+            max_ext_msg_size: 65535,
+            max_ext_msg_depth: 1,
+        })
+    }
+
+    #[test]
+    fn test_size_limits_check_msg() -> anyhow::Result<()> {
+        let limits = size_limits();
+        let body = TonCell::from_boc_hex("b5ee9c7201010201000900010412340100045678")?;
+        let mut msg = Msg::new(CommonMsgInfoInt::default(), body.clone());
+        assert_eq!(limits.check_msg(&msg, true)?, StorageUsedShort::new(2, 32));
+        assert!(limits.check_ext_msg(&msg).is_err()); // depth = 2
+
+        let CommonMsgInfo::Int(info) = &mut msg.info else {
+            unreachable!()
+        };
+        info.value.other = HashMap::from_iter((0..3).map(|id| (id, VarLenBytes::new(1u32, 8))));
+        assert!(limits.check_msg(&msg, true).is_err());
+
+        let mut builder = TonCell::builder();
+        builder.write_ref(body.into_ref())?;
+        let msg = Msg::new(CommonMsgInfoInt::default(), builder.build()?);
+        assert!(limits.check_msg(&msg, true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_size_limits_check_acc_state() -> anyhow::Result<()> {
+        let limits = size_limits();
+        assert!(limits.check_acc_state(&StorageUsed::new(1 << 16, 1000)).is_ok());
+        assert!(limits.check_acc_state(&StorageUsed::new(1 << 16, (1 << 16) * 1023 + 1)).is_err());
+        assert!(limits.check_acc_state(&StorageUsed::new((1 << 16) + 1, 0)).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::bail_tl;
+use crate::error::TLError;
+use crate::tlb_adapters::{DictKeyAdapter, DictValAdapterTLB, TLBHashMapE};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::types::TonAddress;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// suspended_address_list#00 addresses:(HashmapE 288 Unit) suspended_until:uint32 = SuspendedAddressList;
+// _ SuspendedAddressList = ConfigParam 44;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x00, bits_len = 8)]
+pub struct SuspendedAddressList {
+    #[tlb_derive(adapter = "TLBHashMapE::<SuspendedAddressKeyAdapter, DictValAdapterTLB, _, _>::new(288)")]
+    pub addresses: HashMap<TonAddress, ()>,
+    pub suspended_until: u32,
+}
+
+// key is [ wc:int32 addr:uint256 ]
+struct SuspendedAddressKeyAdapter;
+
+impl DictKeyAdapter<TonAddress> for SuspendedAddressKeyAdapter {
+    fn make_key(src_key: &TonAddress) -> Result<BigUint, TLError> {
+        let wc = BigUint::from(src_key.workchain as u32);
+        Ok((wc << TonHash::BITS_LEN) | BigUint::from_bytes_be(src_key.hash.as_slice()))
+    }
+
+    fn extract_key(dict_key: &BigUint) -> Result<TonAddress, TLError> {
+        let mut hash_bytes = [0u8; TonHash::BYTES_LEN];
+        let addr_bytes = (dict_key & ((BigUint::from(1u32) << TonHash::BITS_LEN) - 1u32)).to_bytes_be();
+        hash_bytes[TonHash::BYTES_LEN - addr_bytes.len()..].copy_from_slice(&addr_bytes);
+        let Some(wc) = (dict_key >> TonHash::BITS_LEN).to_u32() else {
+            bail_tl!("suspended address key is too long: {dict_key}");
+        };
+        Ok(TonAddress::new(wc as i32, TonHash::from(hash_bytes)))
+    }
+}
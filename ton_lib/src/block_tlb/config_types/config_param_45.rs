@@ -0,0 +1,23 @@
+use crate::tlb_adapters::{DictKeyAdapterTonHash, DictValAdapterTLB, TLBHashMapE};
+use std::collections::HashMap;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// precompiled_contracts_config#c0 list:(HashmapE 256 PrecompiledSmc) = PrecompiledContractsConfig;
+// _ PrecompiledContractsConfig = ConfigParam 45;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xc0, bits_len = 8)]
+pub struct PrecompiledContractsConfig {
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub list: HashMap<TonHash, PrecompiledSmc>, // code_hash -> smc
+}
+
+// precompiled_smc#b0 gas_usage:uint64 = PrecompiledSmc;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0xb0, bits_len = 8)]
+pub struct PrecompiledSmc {
+    pub gas_usage: u64,
+}
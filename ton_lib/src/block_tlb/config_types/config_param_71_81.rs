@@ -0,0 +1,74 @@
+use crate::block_tlb::Coins;
+use crate::tlb_adapters::{DictKeyAdapterTonHash, DictValAdapterTLB, TLBHashMapE, TLBRef};
+use std::collections::HashMap;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// oracle_bridge_params#_ bridge_address:bits256 oracle_mutlisig_address:bits256 oracles:(HashmapE 256 uint256)
+//   external_chain_address:bits256 = OracleBridgeParams;
+// _ OracleBridgeParams = ConfigParam 71; // Ethereum bridge
+// _ OracleBridgeParams = ConfigParam 72; // Binance Smart Chain bridge
+// _ OracleBridgeParams = ConfigParam 73; // Polygon bridge
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleBridgeParams {
+    pub bridge_address: TonHash,
+    pub oracle_multisig_address: TonHash,
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub oracles: HashMap<TonHash, TonHash>,
+    pub external_chain_address: TonHash,
+}
+
+// _ JettonBridgeParams = ConfigParam 79; // ETH->TON token bridge
+// _ JettonBridgeParams = ConfigParam 80; // BNB->TON token bridge
+// _ JettonBridgeParams = ConfigParam 81; // Polygon->TON token bridge
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JettonBridgeParams {
+    V0(JettonBridgeParamsV0),
+    V1(JettonBridgeParamsV1),
+}
+
+// jetton_bridge_params_v0#00 bridge_address:bits256 oracles_address:bits256 oracles:(HashmapE 256 uint256)
+//   state_flags:uint8 burn_bridge_fee:Coins = JettonBridgeParams;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x00, bits_len = 8)]
+pub struct JettonBridgeParamsV0 {
+    pub bridge_address: TonHash,
+    pub oracles_address: TonHash,
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub oracles: HashMap<TonHash, TonHash>,
+    pub state_flags: u8,
+    pub burn_bridge_fee: Coins,
+}
+
+// jetton_bridge_params_v1#01 bridge_address:bits256 oracles_address:bits256 oracles:(HashmapE 256 uint256)
+//   state_flags:uint8 prices:^JettonBridgePrices external_chain_address:bits256 = JettonBridgeParams;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x01, bits_len = 8)]
+pub struct JettonBridgeParamsV1 {
+    pub bridge_address: TonHash,
+    pub oracles_address: TonHash,
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub oracles: HashMap<TonHash, TonHash>,
+    pub state_flags: u8,
+    #[tlb_derive(adapter = "TLBRef")]
+    pub prices: JettonBridgePrices,
+    pub external_chain_address: TonHash,
+}
+
+// jetton_bridge_prices#_ bridge_burn_fee:Coins bridge_mint_fee:Coins wallet_min_tons_for_storage:Coins
+//   wallet_gas_consumption:Coins minter_min_tons_for_storage:Coins discover_gas_consumption:Coins = JettonBridgePrices;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JettonBridgePrices {
+    pub bridge_burn_fee: Coins,
+    pub bridge_mint_fee: Coins,
+    pub wallet_min_tons_for_storage: Coins,
+    pub wallet_gas_consumption: Coins,
+    pub minter_min_tons_for_storage: Coins,
+    pub discover_gas_consumption: Coins,
+}
@@ -1,8 +1,14 @@
 use ton_lib_core::TLBDerive;
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0xc4, bits_len = 8)]
 pub struct GlobalVersion {
     pub version: u32,
     pub capabilities: u64,
 }
+
+impl GlobalVersion {
+    /// Since version 10 extra currencies dict is not counted in msg size
+    pub fn extra_currency_v2(&self) -> bool { self.version >= 10 }
+}
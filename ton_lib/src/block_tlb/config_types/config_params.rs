@@ -1,4 +1,4 @@
-use crate::block_tlb::{ConfigParam18, GlobalVersion};
+use crate::block_tlb::*;
 use crate::tlb_adapters::{DictKeyAdapterInto, DictValAdapterTLB, TLBHashMap};
 use parking_lot::RwLock;
 use std::collections::HashMap;
@@ -10,20 +10,91 @@ use ton_lib_core::traits::tlb::TLB;
 
 // https://github.com/ton-blockchain/ton/blame/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb#L543
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigParams {
     pub config_addr: TonHash,
     pub config: HashMap<u32, TonCellRef>,
-    storage_prices: RwLock<Option<Arc<ConfigParam18>>>,
-    global_version: RwLock<Option<Arc<GlobalVersion>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cache: ConfigParamsCache,
+}
+
+type ParamCache<T> = RwLock<Option<Arc<T>>>;
+
+#[derive(Debug, Default)]
+struct ConfigParamsCache {
+    config_contract: ParamCache<ConfigParam0>,
+    elector_contract: ParamCache<ConfigParam1>,
+    minter_contract: ParamCache<ConfigParam2>,
+    global_version: ParamCache<GlobalVersion>,
+    workchains: ParamCache<ConfigParam12>,
+    election_timings: ParamCache<ConfigParam15>,
+    validators_count: ParamCache<ConfigParam16>,
+    stakes_config: ParamCache<ConfigParam17>,
+    storage_prices: ParamCache<ConfigParam18>,
+    mc_gas_prices: ParamCache<GasLimitsPrices>,
+    gas_prices: ParamCache<GasLimitsPrices>,
+    mc_block_limits: ParamCache<BlockLimits>,
+    block_limits: ParamCache<BlockLimits>,
+    mc_fwd_prices: ParamCache<MsgForwardPrices>,
+    fwd_prices: ParamCache<MsgForwardPrices>,
+    prev_validators: ParamCache<ValidatorSet>,
+    prev_temp_validators: ParamCache<ValidatorSet>,
+    cur_validators: ParamCache<ValidatorSet>,
+    cur_temp_validators: ParamCache<ValidatorSet>,
+    next_validators: ParamCache<ValidatorSet>,
+    size_limits: ParamCache<SizeLimitsConfig>,
+    suspended_addresses: ParamCache<SuspendedAddressList>,
+    precompiled_contracts: ParamCache<PrecompiledContractsConfig>,
+    eth_bridge: ParamCache<OracleBridgeParams>,
+    bsc_bridge: ParamCache<OracleBridgeParams>,
+    polygon_bridge: ParamCache<OracleBridgeParams>,
+    eth_jetton_bridge: ParamCache<JettonBridgeParams>,
+    bnb_jetton_bridge: ParamCache<JettonBridgeParams>,
+    polygon_jetton_bridge: ParamCache<JettonBridgeParams>,
 }
 
 #[rustfmt::skip]
 impl ConfigParams {
     // lazy_load for params
-    pub fn storage_prices(&self) -> Result<Arc<ConfigParam18>, TLCoreError> { self.load_param(18, &self.storage_prices) }
-    pub fn global_version(&self) -> Result<Arc<GlobalVersion>, TLCoreError> { self.load_param(8, &self.global_version) }
+    pub fn config_contract(&self) -> Result<Arc<ConfigParam0>, TLCoreError> { self.load_param(0, &self.cache.config_contract) }
+    pub fn elector_contract(&self) -> Result<Arc<ConfigParam1>, TLCoreError> { self.load_param(1, &self.cache.elector_contract) }
+    pub fn global_version(&self) -> Result<Arc<GlobalVersion>, TLCoreError> { self.load_param(8, &self.cache.global_version) }
+    pub fn workchains(&self) -> Result<Arc<ConfigParam12>, TLCoreError> { self.load_param(12, &self.cache.workchains) }
+    pub fn election_timings(&self) -> Result<Arc<ConfigParam15>, TLCoreError> { self.load_param(15, &self.cache.election_timings) }
+    pub fn validators_count(&self) -> Result<Arc<ConfigParam16>, TLCoreError> { self.load_param(16, &self.cache.validators_count) }
+    pub fn stakes_config(&self) -> Result<Arc<ConfigParam17>, TLCoreError> { self.load_param(17, &self.cache.stakes_config) }
+    pub fn storage_prices(&self) -> Result<Arc<ConfigParam18>, TLCoreError> { self.load_param(18, &self.cache.storage_prices) }
+    pub fn mc_gas_prices(&self) -> Result<Arc<GasLimitsPrices>, TLCoreError> { self.load_param(20, &self.cache.mc_gas_prices) }
+    pub fn gas_prices(&self) -> Result<Arc<GasLimitsPrices>, TLCoreError> { self.load_param(21, &self.cache.gas_prices) }
+    pub fn mc_block_limits(&self) -> Result<Arc<BlockLimits>, TLCoreError> { self.load_param(22, &self.cache.mc_block_limits) }
+    pub fn block_limits(&self) -> Result<Arc<BlockLimits>, TLCoreError> { self.load_param(23, &self.cache.block_limits) }
+    pub fn mc_fwd_prices(&self) -> Result<Arc<MsgForwardPrices>, TLCoreError> { self.load_param(24, &self.cache.mc_fwd_prices) }
+    pub fn fwd_prices(&self) -> Result<Arc<MsgForwardPrices>, TLCoreError> { self.load_param(25, &self.cache.fwd_prices) }
+    pub fn prev_validators(&self) -> Result<Arc<ValidatorSet>, TLCoreError> { self.load_param(32, &self.cache.prev_validators) }
+    pub fn prev_temp_validators(&self) -> Result<Arc<ValidatorSet>, TLCoreError> { self.load_param(33, &self.cache.prev_temp_validators) }
+    pub fn cur_validators(&self) -> Result<Arc<ValidatorSet>, TLCoreError> { self.load_param(34, &self.cache.cur_validators) }
+    pub fn cur_temp_validators(&self) -> Result<Arc<ValidatorSet>, TLCoreError> { self.load_param(35, &self.cache.cur_temp_validators) }
+    pub fn next_validators(&self) -> Result<Arc<ValidatorSet>, TLCoreError> { self.load_param(36, &self.cache.next_validators) }
+    pub fn size_limits(&self) -> Result<Arc<SizeLimitsConfig>, TLCoreError> { self.load_param(43, &self.cache.size_limits) }
+    pub fn suspended_addresses(&self) -> Result<Arc<SuspendedAddressList>, TLCoreError> { self.load_param(44, &self.cache.suspended_addresses) }
+    pub fn precompiled_contracts(&self) -> Result<Arc<PrecompiledContractsConfig>, TLCoreError> { self.load_param(45, &self.cache.precompiled_contracts) }
+    pub fn eth_bridge(&self) -> Result<Arc<OracleBridgeParams>, TLCoreError> { self.load_param(71, &self.cache.eth_bridge) }
+    pub fn bsc_bridge(&self) -> Result<Arc<OracleBridgeParams>, TLCoreError> { self.load_param(72, &self.cache.bsc_bridge) }
+    pub fn polygon_bridge(&self) -> Result<Arc<OracleBridgeParams>, TLCoreError> { self.load_param(73, &self.cache.polygon_bridge) }
+    pub fn eth_jetton_bridge(&self) -> Result<Arc<JettonBridgeParams>, TLCoreError> { self.load_param(79, &self.cache.eth_jetton_bridge) }
+    pub fn bnb_jetton_bridge(&self) -> Result<Arc<JettonBridgeParams>, TLCoreError> { self.load_param(80, &self.cache.bnb_jetton_bridge) }
+    pub fn polygon_jetton_bridge(&self) -> Result<Arc<JettonBridgeParams>, TLCoreError> { self.load_param(81, &self.cache.polygon_jetton_bridge) }
+
+    /// ConfigParam 0 is used if ConfigParam 2 is absent
+    pub fn minter_contract(&self) -> Result<Arc<ConfigParam2>, TLCoreError> {
+        if !self.config.contains_key(&2) {
+            let minter_addr = self.config_contract()?.config_addr.clone();
+            return Ok(Arc::new(ConfigParam2 { minter_addr }));
+        }
+        self.load_param(2, &self.cache.minter_contract)
+    }
 
-    fn load_param<T: TLB>(&self, index: u32, dst: &RwLock<Option<Arc<T>>>) -> Result<Arc<T>, TLCoreError> {
+    fn load_param<T: TLB>(&self, index: u32, dst: &ParamCache<T>) -> Result<Arc<T>, TLCoreError> {
         if let Some(param) = dst.read().deref() {
             return Ok(param.clone());
         }
@@ -50,8 +121,7 @@ impl Clone for ConfigParams {
         Self {
             config_addr: self.config_addr.clone(),
             config: self.config.clone(),
-            storage_prices: RwLock::new(None),
-            global_version: RwLock::new(None),
+            cache: ConfigParamsCache::default(),
         }
     }
 }
@@ -59,9 +129,8 @@ impl Clone for ConfigParams {
 impl TLB for ConfigParams {
     fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
         let config_addr = TLB::read(parser)?;
-        let config_ref = parser.read_next_ref()?;
-        let config =
-            TLBHashMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32).read(&mut config_ref.parser())?;
+        let config = TLBHashMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32)
+            .read(&mut parser.read_next_ref_parser()?)?;
         Ok(Self {
             config_addr,
             config,
@@ -81,10 +150,14 @@ impl TLB for ConfigParams {
 #[cfg(test)]
 mod tests {
     use crate::block_tlb::_test_block_data::CONFIG_BOC_HEX;
-    use crate::block_tlb::{ConfigParam18, ConfigParams, GlobalVersion, StoragePrices};
+    use crate::block_tlb::*;
     use std::collections::HashMap;
     use std::ops::Deref;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use ton_lib_core::cell::{MerkleProof, TonCell, VisitedCells};
     use ton_lib_core::traits::tlb::TLB;
+    use ton_lib_core::types::TonAddress;
 
     #[test]
     fn test_config_params() -> anyhow::Result<()> {
@@ -124,4 +197,119 @@ mod tests {
 
         Ok(())
     }
+
+    fn assert_param_roundtrip<T: TLB>(config: &ConfigParams, index: u32, param: Arc<T>) -> anyhow::Result<()> {
+        assert_eq!(&param.to_cell()?, config.config[&index].deref());
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_params_typed_roundtrip() -> anyhow::Result<()> {
+        let config = ConfigParams::from_boc_hex(CONFIG_BOC_HEX)?;
+        assert_param_roundtrip(&config, 0, config.config_contract()?)?;
+        assert_param_roundtrip(&config, 1, config.elector_contract()?)?;
+        assert_param_roundtrip(&config, 2, config.minter_contract()?)?;
+        assert_param_roundtrip(&config, 12, config.workchains()?)?;
+        assert_param_roundtrip(&config, 15, config.election_timings()?)?;
+        assert_param_roundtrip(&config, 16, config.validators_count()?)?;
+        assert_param_roundtrip(&config, 17, config.stakes_config()?)?;
+        assert_param_roundtrip(&config, 20, config.mc_gas_prices()?)?;
+        assert_param_roundtrip(&config, 21, config.gas_prices()?)?;
+        assert_param_roundtrip(&config, 22, config.mc_block_limits()?)?;
+        assert_param_roundtrip(&config, 23, config.block_limits()?)?;
+        assert_param_roundtrip(&config, 24, config.mc_fwd_prices()?)?;
+        assert_param_roundtrip(&config, 25, config.fwd_prices()?)?;
+        assert_param_roundtrip(&config, 32, config.prev_validators()?)?;
+        assert_param_roundtrip(&config, 34, config.cur_validators()?)?;
+        assert_param_roundtrip(&config, 44, config.suspended_addresses()?)?;
+        assert_param_roundtrip(&config, 45, config.precompiled_contracts()?)?;
+        assert_param_roundtrip(&config, 71, config.eth_bridge()?)?;
+        assert_param_roundtrip(&config, 72, config.bsc_bridge()?)?;
+        assert_param_roundtrip(&config, 79, config.eth_jetton_bridge()?)?;
+
+        assert_eq!(config.config_contract()?.config_addr, config.config_addr);
+        assert!(config.workchains()?.workchains.contains_key(&0));
+        assert!(matches!(*config.gas_prices()?, GasLimitsPrices::FlatPfx { .. }));
+        assert_eq!(config.cur_validators()?.list.len(), config.cur_validators()?.total as usize);
+        assert!(config.size_limits().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_param_2_fallback() -> anyhow::Result<()> {
+        let mut config = ConfigParams::from_boc_hex(CONFIG_BOC_HEX)?;
+        assert_ne!(config.minter_contract()?.minter_addr, config.config_addr);
+
+        config.config.remove(&2);
+        assert_eq!(config.minter_contract()?.minter_addr, config.config_addr);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_params_same_type_real_data() -> anyhow::Result<()> {
+        // mainnet config has no temporary & next validators and no Polygon/BNB bridges,
+        // so they are filled with real params of the same type
+        let mut config = ConfigParams::from_boc_hex(CONFIG_BOC_HEX)?;
+        for (index, src_index) in [(33, 32), (35, 34), (36, 34), (73, 72), (80, 79), (81, 79)] {
+            let cell = config.config[&src_index].clone();
+            config.config.insert(index, cell);
+        }
+        assert_param_roundtrip(&config, 33, config.prev_temp_validators()?)?;
+        assert_param_roundtrip(&config, 35, config.cur_temp_validators()?)?;
+        assert_param_roundtrip(&config, 36, config.next_validators()?)?;
+        assert_param_roundtrip(&config, 73, config.polygon_bridge()?)?;
+        assert_param_roundtrip(&config, 80, config.bnb_jetton_bridge()?)?;
+        assert_param_roundtrip(&config, 81, config.polygon_jetton_bridge()?)?;
+
+        assert_eq!(config.prev_temp_validators()?, config.prev_validators()?);
+        assert_eq!(config.cur_temp_validators()?, config.cur_validators()?);
+        assert_eq!(config.next_validators()?, config.cur_validators()?);
+        assert_eq!(config.polygon_bridge()?, config.bsc_bridge()?);
+        assert_eq!(config.bnb_jetton_bridge()?, config.eth_jetton_bridge()?);
+        assert_eq!(config.polygon_jetton_bridge()?, config.eth_jetton_bridge()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_param_43_44() -> anyhow::Result<()> {
+        let size_limits = SizeLimitsConfig::V2(SizeLimitsConfigV2 {
+            max_msg_bits: 1 << 21,
+            max_msg_cells: 1 << 13,
+            max_library_cells: 1000,
+            max_vm_data_depth: 512,
+            max_ext_msg_size: 65535,
+            max_ext_msg_depth: 512,
+            max_acc_state_cells: 1 << 16,
+            max_acc_state_bits: (1 << 16) * 1023,
+            max_acc_public_libraries: 256,
+            defer_out_queue_size_limit: 256,
+            max_msg_extra_currencies: 2,
+            max_acc_fixed_prefix_length: 8,
+        });
+        assert_eq!(SizeLimitsConfig::from_cell(&size_limits.to_cell()?)?, size_limits);
+
+        let address = TonAddress::from_str("EQBSUY4UWGJFAps0KwHY4tpOGqzU41DZhyrT8OuyAWWtnezy")?;
+        let suspended = SuspendedAddressList {
+            addresses: HashMap::from([(address, ())]),
+            suspended_until: 1735689600,
+        };
+        assert_eq!(SuspendedAddressList::from_cell(&suspended.to_cell()?)?, suspended);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_params_merkle_proof_from_typed_read() -> anyhow::Result<()> {
+        let cell = TonCell::from_boc_hex(CONFIG_BOC_HEX)?;
+        let visited = VisitedCells::new();
+        let config = visited.read::<ConfigParams>(&cell)?;
+        // params are parsed lazily, so param 18 is tracked explicitly
+        let expected_prices = visited.read::<ConfigParam18>(&config.config[&18])?;
+
+        // dict and param 18 are read by tracked parsers, so they must survive pruning
+        let proof = MerkleProof::create_from_visited(&cell, &visited)?;
+        let config_from_proof = ConfigParams::from_cell(&proof.refs[0])?;
+        assert_eq!(*config_from_proof.storage_prices()?, expected_prices);
+        assert!(config_from_proof.cur_validators().is_err());
+        Ok(())
+    }
 }
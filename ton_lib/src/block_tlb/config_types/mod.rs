@@ -1,7 +1,29 @@
+mod config_param_0_2;
+mod config_param_12;
+mod config_param_15_17;
 mod config_param_18;
+mod config_param_20_21;
+mod config_param_22_23;
+mod config_param_24_25;
+mod config_param_32_36;
+mod config_param_43;
+mod config_param_44;
+mod config_param_45;
+mod config_param_71_81;
 mod config_param_8;
 mod config_params;
 
+pub use config_param_0_2::*;
+pub use config_param_12::*;
+pub use config_param_15_17::*;
 pub use config_param_18::*;
+pub use config_param_20_21::*;
+pub use config_param_22_23::*;
+pub use config_param_24_25::*;
+pub use config_param_32_36::*;
+pub use config_param_43::*;
+pub use config_param_44::*;
+pub use config_param_45::*;
+pub use config_param_71_81::*;
 pub use config_param_8::*;
 pub use config_params::*;
@@ -0,0 +1,87 @@
+use ton_lib_core::cell::{CellBuilder, CellParser, CellType, TonHash};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::{TLBPrefix, TLB};
+use ton_lib_core::types::TLBObject;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// !merkle_update#04 {X:Type} old_hash:bits256 new_hash:bits256 old_depth:uint16 new_depth:uint16
+//   old:^X new:^X = MERKLE_UPDATE X;
+/// Typed MerkleUpdate cell. Both sides are usually pruned, so they are parsed on access only
+/// Use `ton_lib_core::cell::MerkleUpdate` to create or apply the update
+#[derive(Debug, Clone, PartialEq)]
+pub struct TLBMerkleUpdate<T: TLB> {
+    pub old_hash: TonHash,
+    pub new_hash: TonHash,
+    pub old_depth: u16,
+    pub new_depth: u16,
+    pub old: TLBObject<T>,
+    pub new: TLBObject<T>,
+}
+
+// exotic cell type must be kept on writing, so implement TLB manually
+impl<T: TLB> TLB for TLBMerkleUpdate<T> {
+    const PREFIX: TLBPrefix = TLBPrefix::new(CellType::MerkleUpdate as usize, 8);
+
+    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+        Ok(Self {
+            old_hash: TLB::read(parser)?,
+            new_hash: TLB::read(parser)?,
+            old_depth: TLB::read(parser)?,
+            new_depth: TLB::read(parser)?,
+            old: TLB::read(parser)?,
+            new: TLB::read(parser)?,
+        })
+    }
+
+    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+        self.old_hash.write(builder)?;
+        self.new_hash.write(builder)?;
+        self.old_depth.write(builder)?;
+        self.new_depth.write(builder)?;
+        self.old.write(builder)?;
+        self.new.write(builder)
+    }
+
+    fn cell_type(&self) -> CellType { CellType::MerkleUpdate }
+}
+
+// pruned sides can't be serialized as values, so the whole cell is kept as base64 BOC
+#[cfg(feature = "serde")]
+impl<T: TLB> serde::Serialize for TLBMerkleUpdate<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let cell_ref = self.to_cell_ref().map_err(serde::ser::Error::custom)?;
+        serde::Serialize::serialize(&cell_ref, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: TLB> serde::Deserialize<'de> for TLBMerkleUpdate<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cell_ref = <ton_lib_core::cell::TonCellRef as serde::Deserialize>::deserialize(deserializer)?;
+        Self::from_cell(&cell_ref).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ton_lib_core::cell::{MerkleUpdate, TonCell};
+
+    #[test]
+    fn test_tlb_merkle_update() -> anyhow::Result<()> {
+        let old_root = 1u32.to_cell()?;
+        let new_root = 2u32.to_cell()?;
+        let update_cell = MerkleUpdate::create(&old_root, &new_root)?;
+
+        let parsed = TLBMerkleUpdate::<u32>::from_cell(&update_cell)?;
+        assert_eq!(&parsed.old_hash, old_root.hash()?);
+        assert_eq!(&parsed.new_hash, new_root.hash()?);
+        assert_eq!(parsed.new.get()?, &2);
+
+        let serialized = parsed.to_cell()?;
+        assert_eq!(serialized.cell_type, CellType::MerkleUpdate);
+        assert_eq!(serialized, update_cell);
+        assert!(TLBMerkleUpdate::<u32>::from_cell(&TonCell::EMPTY).is_err());
+        Ok(())
+    }
+}
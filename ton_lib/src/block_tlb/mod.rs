@@ -1,11 +1,12 @@
 #[cfg(test)]
-mod _test_block_data;
+pub(crate) mod _test_block_data;
 
 mod account;
 mod block_types;
 mod coins;
 mod config_types;
 mod hash_update;
+mod merkle_update;
 mod msg_types;
 mod out_action;
 mod shard_types;
@@ -18,6 +19,7 @@ pub use block_types::*;
 pub use coins::*;
 pub use config_types::*;
 pub use hash_update::*;
+pub use merkle_update::*;
 pub use msg_types::*;
 pub use out_action::*;
 pub use shard_types::*;
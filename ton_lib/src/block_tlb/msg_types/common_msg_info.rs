@@ -5,6 +5,7 @@ use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blob/050a984163a53df16fb03f66cc445c34bfed48ed/crypto/block/block.tlb#L155
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CommonMsgInfo {
     Int(CommonMsgInfoInt),
     ExtIn(CommonMsgInfoExtIn),
@@ -12,6 +13,7 @@ pub enum CommonMsgInfo {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b10, bits_len = 2)]
 pub struct CommonMsgInfoExtIn {
     pub src: MsgAddressExt,
@@ -20,6 +22,7 @@ pub struct CommonMsgInfoExtIn {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0, bits_len = 1)]
 pub struct CommonMsgInfoInt {
     pub ihr_disabled: bool,
@@ -35,6 +38,7 @@ pub struct CommonMsgInfoInt {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b11, bits_len = 2)]
 pub struct CommonMsgInfoExtOut {
     pub src: MsgAddressInt,
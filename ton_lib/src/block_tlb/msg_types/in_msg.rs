@@ -0,0 +1,119 @@
+use crate::block_tlb::*;
+use crate::tlb_adapters::{DictKeyAdapterTonHash, DictValAdapterTLB, TLBHashMapAugE};
+use std::collections::HashMap;
+use ton_lib_core::bail_tl_core;
+use ton_lib_core::cell::{TonCellRef, TonHash};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::types::TLBObject;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ (HashmapAugE 256 InMsg ImportFees) = InMsgDescr;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InMsgDescr {
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterTLB, _, _, _>::new(256, ImportFees::merge)"
+    )]
+    pub msgs: HashMap<TonHash, (InMsg, ImportFees)>, // msg_hash -> (in_msg, fees)
+}
+
+// import_fees$_ fees_collected:Grams value_imported:CurrencyCollection = ImportFees;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportFees {
+    pub fees_collected: Coins,
+    pub value_imported: CurrencyCollection,
+}
+
+impl ImportFees {
+    /// InMsgDescr augmentation
+    pub fn merge(&self, other: &Self) -> Result<Self, TLCoreError> {
+        let Some(fees_collected) = self.fees_collected.checked_add(*other.fees_collected) else {
+            bail_tl_core!("fees_collected overflow: {} + {}", *self.fees_collected, *other.fees_collected);
+        };
+        Ok(Self {
+            fees_collected: Coins::new(fees_collected),
+            value_imported: self.value_imported.checked_add(&other.value_imported)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InMsg {
+    // msg_import_ext$000 msg:^(Message Any) transaction:^Transaction = InMsg;
+    #[tlb_derive(prefix = 0b000, bits_len = 3)]
+    ImportExt { msg: TLBObject<Msg>, tx: TLBObject<Tx> },
+    // msg_import_ihr$010 msg:^(Message Any) transaction:^Transaction ihr_fee:Grams proof_created:^Cell = InMsg;
+    #[tlb_derive(prefix = 0b010, bits_len = 3)]
+    ImportIhr {
+        msg: TLBObject<Msg>,
+        tx: TLBObject<Tx>,
+        ihr_fee: Coins,
+        proof_created: TonCellRef,
+    },
+    // msg_import_imm$011 in_msg:^MsgEnvelope transaction:^Transaction fwd_fee:Grams = InMsg;
+    #[tlb_derive(prefix = 0b011, bits_len = 3)]
+    ImportImm {
+        in_msg: TLBObject<MsgEnvelope>,
+        tx: TLBObject<Tx>,
+        fwd_fee: Coins,
+    },
+    // msg_import_fin$100 in_msg:^MsgEnvelope transaction:^Transaction fwd_fee:Grams = InMsg;
+    #[tlb_derive(prefix = 0b100, bits_len = 3)]
+    ImportFin {
+        in_msg: TLBObject<MsgEnvelope>,
+        tx: TLBObject<Tx>,
+        fwd_fee: Coins,
+    },
+    // msg_import_tr$101 in_msg:^MsgEnvelope out_msg:^MsgEnvelope transit_fee:Grams = InMsg;
+    #[tlb_derive(prefix = 0b101, bits_len = 3)]
+    ImportTr {
+        in_msg: TLBObject<MsgEnvelope>,
+        out_msg: TLBObject<MsgEnvelope>,
+        transit_fee: Coins,
+    },
+    // msg_discard_fin$110 in_msg:^MsgEnvelope transaction_id:uint64 fwd_fee:Grams = InMsg;
+    #[tlb_derive(prefix = 0b110, bits_len = 3)]
+    DiscardFin {
+        in_msg: TLBObject<MsgEnvelope>,
+        tx_id: u64,
+        fwd_fee: Coins,
+    },
+    // msg_discard_tr$111 in_msg:^MsgEnvelope transaction_id:uint64 fwd_fee:Grams proof_delivered:^Cell = InMsg;
+    #[tlb_derive(prefix = 0b111, bits_len = 3)]
+    DiscardTr {
+        in_msg: TLBObject<MsgEnvelope>,
+        tx_id: u64,
+        fwd_fee: Coins,
+        proof_delivered: TonCellRef,
+    },
+    // msg_import_deferred_fin$00100 in_msg:^MsgEnvelope transaction:^Transaction fwd_fee:Grams = InMsg;
+    #[tlb_derive(prefix = 0b00100, bits_len = 5)]
+    ImportDeferredFin {
+        in_msg: TLBObject<MsgEnvelope>,
+        tx: TLBObject<Tx>,
+        fwd_fee: Coins,
+    },
+    // msg_import_deferred_tr$00101 in_msg:^MsgEnvelope out_msg:^MsgEnvelope = InMsg;
+    #[tlb_derive(prefix = 0b00101, bits_len = 5)]
+    ImportDeferredTr {
+        in_msg: TLBObject<MsgEnvelope>,
+        out_msg: TLBObject<MsgEnvelope>,
+    },
+}
+
+impl InMsg {
+    /// Transaction processed the message, if any
+    pub fn tx(&self) -> Option<&TLBObject<Tx>> {
+        match self {
+            InMsg::ImportExt { tx, .. }
+            | InMsg::ImportIhr { tx, .. }
+            | InMsg::ImportImm { tx, .. }
+            | InMsg::ImportFin { tx, .. }
+            | InMsg::ImportDeferredFin { tx, .. } => Some(tx),
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,11 @@
 mod common_msg_info;
+mod in_msg;
 mod msg;
+mod msg_envelope;
+mod out_msg;
 
 pub use common_msg_info::*;
+pub use in_msg::*;
 pub use msg::*;
+pub use msg_envelope::*;
+pub use out_msg::*;
@@ -1,6 +1,7 @@
 use crate::block_tlb::msg_types::common_msg_info::CommonMsgInfo;
 use crate::block_tlb::*;
-use ton_lib_core::cell::{TonCell, TonHash};
+use std::ops::Deref;
+use ton_lib_core::cell::{TonCell, TonCellUtils, TonHash};
 use ton_lib_core::error::TLCoreError;
 use ton_lib_core::traits::tlb::TLB;
 use ton_lib_core::types::tlb_core::{EitherRefLayout, MsgAddress, MsgAddressExt, MsgAddressInt, TLBEitherRef};
@@ -8,6 +9,7 @@ use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blob/050a984163a53df16fb03f66cc445c34bfed48ed/crypto/block/block.tlb#L157
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Msg {
     pub info: CommonMsgInfo,
     pub init: Option<TLBEitherRef<StateInit>>,
@@ -57,6 +59,18 @@ impl Msg {
 
     pub fn state_init(&self) -> Option<&StateInit> { self.init.as_ref().map(|init| &init.value) }
 
+    /// Unique cells & bits of state_init and body, the way the node computes msg size for fees and size limits.
+    /// Root cell is not counted, extra currencies dict is counted until extra_currency_v2 (see GlobalVersion)
+    pub fn size(&self, extra_currency_v2: bool) -> Result<StorageUsedShort, TLCoreError> {
+        let cell = self.to_cell()?;
+        let skip_refs = match &self.info {
+            CommonMsgInfo::Int(info) if extra_currency_v2 && !info.value.other.is_empty() => 1,
+            _ => 0,
+        };
+        let stats = TonCellUtils::storage_stats(cell.refs.iter().skip(skip_refs).map(Deref::deref))?;
+        Ok(stats.into())
+    }
+
     pub fn cell_hash_normalized(&self) -> Result<TonHash, TLCoreError> {
         match &self.info {
             CommonMsgInfo::ExtIn(_) => {
@@ -82,9 +96,10 @@ impl Msg {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use std::str::FromStr;
     use tokio_test::assert_ok;
-    use ton_lib_core::types::tlb_core::{Anycast, MsgAddressExtern, MsgAddressIntStd, VarLenBits};
+    use ton_lib_core::types::tlb_core::{Anycast, MsgAddressExtern, MsgAddressIntStd, VarLenBits, VarLenBytes};
     use ton_lib_core::types::TonAddress;
 
     #[test]
@@ -168,4 +183,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_msg_size() -> anyhow::Result<()> {
+        let body = TonCell::from_boc_hex("b5ee9c7201010201000900010412340100045678")?;
+        let mut msg = Msg::new(CommonMsgInfoInt::default(), body.clone());
+        // body is stored in ref, so it's fully counted
+        assert_eq!(msg.size(true)?, StorageUsedShort::new(2, 32));
+
+        // extra currencies are not counted since extra_currency_v2
+        let CommonMsgInfo::Int(info) = &mut msg.info else {
+            unreachable!()
+        };
+        info.value.other = HashMap::from([(1, VarLenBytes::new(100u32, 8))]);
+        assert_eq!(msg.size(true)?, StorageUsedShort::new(2, 32));
+        let currency_cell = msg.to_cell()?.refs[0].clone();
+        assert_eq!(msg.size(false)?, StorageUsedShort::new(3, 32 + currency_cell.data_bits_len as u64));
+
+        // inlined body root is not counted
+        msg.body.layout = EitherRefLayout::ToCell;
+        assert_eq!(msg.size(true)?, StorageUsedShort::new(1, 16));
+        Ok(())
+    }
 }
@@ -0,0 +1,80 @@
+use crate::block_tlb::*;
+use crate::tlb_adapters::ConstLen;
+use ton_lib_core::types::tlb_core::MsgAddressInt;
+use ton_lib_core::types::TLBObject;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// msg_envelope#4 cur_addr:IntermediateAddress next_addr:IntermediateAddress fwd_fee_remaining:Grams msg:^(Message Any) = MsgEnvelope;
+// msg_envelope_v2#5 ... emitted_lt:(Maybe uint64) metadata:(Maybe MsgMetadata) = MsgEnvelope;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MsgEnvelope {
+    V1(MsgEnvelopeV1),
+    V2(MsgEnvelopeV2),
+}
+
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x4, bits_len = 4)]
+pub struct MsgEnvelopeV1 {
+    pub cur_addr: IntermediateAddress,
+    pub next_addr: IntermediateAddress,
+    pub fwd_fee_remaining: Coins,
+    pub msg: TLBObject<Msg>,
+}
+
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x5, bits_len = 4)]
+pub struct MsgEnvelopeV2 {
+    pub cur_addr: IntermediateAddress,
+    pub next_addr: IntermediateAddress,
+    pub fwd_fee_remaining: Coins,
+    pub msg: TLBObject<Msg>,
+    pub emitted_lt: Option<u64>,
+    pub metadata: Option<MsgMetadata>,
+}
+
+// msg_metadata#0 depth:uint32 initiator_addr:MsgAddressInt initiator_lt:uint64 = MsgMetadata;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x0, bits_len = 4)]
+pub struct MsgMetadata {
+    pub depth: u32,
+    pub initiator_addr: MsgAddressInt,
+    pub initiator_lt: u64,
+}
+
+// interm_addr_regular$0 use_dest_bits:(#<= 96) = IntermediateAddress;
+// interm_addr_simple$10 workchain_id:int8 addr_pfx:uint64 = IntermediateAddress;
+// interm_addr_ext$11 workchain_id:int32 addr_pfx:uint64 = IntermediateAddress;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntermediateAddress {
+    #[tlb_derive(prefix = 0b0, bits_len = 1)]
+    Regular {
+        #[tlb_derive(bits_len = 7)]
+        use_dest_bits: u8,
+    },
+    #[tlb_derive(prefix = 0b10, bits_len = 2)]
+    Simple { workchain: i8, addr_pfx: u64 },
+    #[tlb_derive(prefix = 0b11, bits_len = 2)]
+    Ext { workchain: i32, addr_pfx: u64 },
+}
+
+impl MsgEnvelope {
+    pub fn msg(&self) -> &TLBObject<Msg> {
+        match self {
+            MsgEnvelope::V1(envelope) => &envelope.msg,
+            MsgEnvelope::V2(envelope) => &envelope.msg,
+        }
+    }
+
+    pub fn fwd_fee_remaining(&self) -> &Coins {
+        match self {
+            MsgEnvelope::V1(envelope) => &envelope.fwd_fee_remaining,
+            MsgEnvelope::V2(envelope) => &envelope.fwd_fee_remaining,
+        }
+    }
+}
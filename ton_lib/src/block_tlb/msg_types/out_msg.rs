@@ -0,0 +1,99 @@
+use crate::block_tlb::*;
+use crate::tlb_adapters::ConstLen;
+use crate::tlb_adapters::{DictKeyAdapterTonHash, DictValAdapterTLB, TLBHashMapAugE};
+use std::collections::HashMap;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::types::TLBObject;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ (HashmapAugE 256 OutMsg CurrencyCollection) = OutMsgDescr;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutMsgDescr {
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterTLB, _, _, _>::new(256, CurrencyCollection::checked_add)"
+    )]
+    pub msgs: HashMap<TonHash, (OutMsg, CurrencyCollection)>, // msg_hash -> (out_msg, exported value)
+}
+
+// variants differ only by inline TLBObject caches of referenced cells, which are empty until accessed
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutMsg {
+    // msg_export_ext$000 msg:^(Message Any) transaction:^Transaction = OutMsg;
+    #[tlb_derive(prefix = 0b000, bits_len = 3)]
+    ExportExt { msg: TLBObject<Msg>, tx: TLBObject<Tx> },
+    // msg_export_imm$010 out_msg:^MsgEnvelope transaction:^Transaction reimport:^InMsg = OutMsg;
+    #[tlb_derive(prefix = 0b010, bits_len = 3)]
+    ExportImm {
+        out_msg: TLBObject<MsgEnvelope>,
+        tx: TLBObject<Tx>,
+        reimport: TLBObject<InMsg>,
+    },
+    // msg_export_new$001 out_msg:^MsgEnvelope transaction:^Transaction = OutMsg;
+    #[tlb_derive(prefix = 0b001, bits_len = 3)]
+    ExportNew {
+        out_msg: TLBObject<MsgEnvelope>,
+        tx: TLBObject<Tx>,
+    },
+    // msg_export_tr$011 out_msg:^MsgEnvelope imported:^InMsg = OutMsg;
+    #[tlb_derive(prefix = 0b011, bits_len = 3)]
+    ExportTr {
+        out_msg: TLBObject<MsgEnvelope>,
+        imported: TLBObject<InMsg>,
+    },
+    // msg_export_deq$1100 out_msg:^MsgEnvelope import_block_lt:uint63 = OutMsg;
+    #[tlb_derive(prefix = 0b1100, bits_len = 4)]
+    ExportDeq {
+        out_msg: TLBObject<MsgEnvelope>,
+        #[tlb_derive(bits_len = 63)]
+        import_block_lt: u64,
+    },
+    // msg_export_deq_short$1101 msg_env_hash:bits256 next_workchain:int32 next_addr_pfx:uint64 import_block_lt:uint64 = OutMsg;
+    #[tlb_derive(prefix = 0b1101, bits_len = 4)]
+    ExportDeqShort {
+        msg_env_hash: TonHash,
+        next_workchain: i32,
+        next_addr_pfx: u64,
+        import_block_lt: u64,
+    },
+    // msg_export_tr_req$111 out_msg:^MsgEnvelope imported:^InMsg = OutMsg;
+    #[tlb_derive(prefix = 0b111, bits_len = 3)]
+    ExportTrReq {
+        out_msg: TLBObject<MsgEnvelope>,
+        imported: TLBObject<InMsg>,
+    },
+    // msg_export_deq_imm$100 out_msg:^MsgEnvelope reimport:^InMsg = OutMsg;
+    #[tlb_derive(prefix = 0b100, bits_len = 3)]
+    ExportDeqImm {
+        out_msg: TLBObject<MsgEnvelope>,
+        reimport: TLBObject<InMsg>,
+    },
+    // msg_export_new_defer$10100 out_msg:^MsgEnvelope transaction:^Transaction = OutMsg;
+    #[tlb_derive(prefix = 0b10100, bits_len = 5)]
+    ExportNewDefer {
+        out_msg: TLBObject<MsgEnvelope>,
+        tx: TLBObject<Tx>,
+    },
+    // msg_export_deferred_tr$10101 out_msg:^MsgEnvelope imported:^InMsg = OutMsg;
+    #[tlb_derive(prefix = 0b10101, bits_len = 5)]
+    ExportDeferredTr {
+        out_msg: TLBObject<MsgEnvelope>,
+        imported: TLBObject<InMsg>,
+    },
+}
+
+impl OutMsg {
+    /// Transaction produced the message, if any
+    pub fn tx(&self) -> Option<&TLBObject<Tx>> {
+        match self {
+            OutMsg::ExportExt { tx, .. }
+            | OutMsg::ExportImm { tx, .. }
+            | OutMsg::ExportNew { tx, .. }
+            | OutMsg::ExportNewDefer { tx, .. } => Some(tx),
+            _ => None,
+        }
+    }
+}
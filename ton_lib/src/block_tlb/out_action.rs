@@ -8,12 +8,14 @@ use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blob/2a68c8610bf28b43b2019a479a70d0606c2a0aa1/crypto/block/block.tlb#L399
 #[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutList {
     pub actions: Vec<OutAction>,
 }
 
 // https://github.com/ton-blockchain/ton/blob/2a68c8610bf28b43b2019a479a70d0606c2a0aa1/crypto/block/block.tlb#L408
 #[derive(Debug, PartialEq, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OutAction {
     SendMsg(OutActionSendMsg),
     SetCode(OutActionSetCode),
@@ -22,6 +24,7 @@ pub enum OutAction {
 }
 
 #[derive(Debug, PartialEq, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x0ec3c86d, bits_len = 32)]
 pub struct OutActionSendMsg {
     pub mode: u8,
@@ -29,12 +32,14 @@ pub struct OutActionSendMsg {
 }
 
 #[derive(Debug, PartialEq, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0xad4de08e, bits_len = 32)]
 pub struct OutActionSetCode {
     pub new_code: TonCellRef,
 }
 
 #[derive(Debug, PartialEq, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x36e6b809, bits_len = 32)]
 pub struct OutActionReserveCurrency {
     pub mode: u8,
@@ -42,6 +47,7 @@ pub struct OutActionReserveCurrency {
 }
 
 #[derive(Debug, PartialEq, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x26fa1dd4, bits_len = 32)]
 pub struct OutActionChangeLibrary {
     #[tlb_derive(bits_len = 7)]
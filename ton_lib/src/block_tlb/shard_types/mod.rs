@@ -1,7 +1,13 @@
+mod out_msg_queue;
+mod shard_accounts;
 mod shard_accounts_blocks;
 mod shard_descr;
 mod shard_ident;
+mod shard_state;
 
+pub use out_msg_queue::*;
+pub use shard_accounts::*;
 pub use shard_accounts_blocks::*;
 pub use shard_descr::*;
 pub use shard_ident::*;
+pub use shard_state::*;
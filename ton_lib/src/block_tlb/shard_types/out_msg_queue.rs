@@ -0,0 +1,208 @@
+use crate::block_tlb::MsgEnvelope;
+use crate::tlb_adapters::{ConstLen, TLBHashMapAugE, TLBHashMapE};
+use crate::tlb_adapters::{DictKeyAdapterInto, DictKeyAdapterTonHash, DictValAdapterNum, DictValAdapterTLB};
+use num_bigint::BigUint;
+use std::collections::HashMap;
+use ton_lib_core::bail_tl_core;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::types::TLBObject;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ out_queue:OutMsgQueue proc_info:ProcessedInfo extra:(Maybe OutMsgQueueExtra) = OutMsgQueueInfo;
+// _ (HashmapAugE 352 EnqueuedMsg uint64) = OutMsgQueue;
+// _ (HashmapE 96 ProcessedUpto) = ProcessedInfo;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutMsgQueueInfo {
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterInto, DictValAdapterTLB, DictValAdapterNum<64>, _, _, _>::new(352, min_lt_merge)"
+    )]
+    pub out_queue: HashMap<OutMsgQueueKey, (EnqueuedMsg, u64)>, // -> (msg, enqueued_lt)
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(96)")]
+    pub proc_info: HashMap<u128, ProcessedUpto>, // [shard:uint64 mc_seqno:uint32] -> ProcessedUpto
+    pub extra: Option<OutMsgQueueExtra>,
+}
+
+// key is [ next_workchain:int32 next_addr_pfx:uint64 msg_hash:bits256 ]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OutMsgQueueKey {
+    pub next_workchain: i32,
+    pub next_addr_pfx: u64,
+    pub msg_hash: TonHash,
+}
+
+// _ enqueued_lt:uint64 out_msg:^MsgEnvelope = EnqueuedMsg;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnqueuedMsg {
+    pub enqueued_lt: u64,
+    pub out_msg: TLBObject<MsgEnvelope>,
+}
+
+// processed_upto$_ last_msg_lt:uint64 last_msg_hash:bits256 = ProcessedUpto;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessedUpto {
+    pub last_msg_lt: u64,
+    pub last_msg_hash: TonHash,
+}
+
+// out_msg_queue_extra#0 dispatch_queue:DispatchQueue out_queue_size:(Maybe uint48) = OutMsgQueueExtra;
+// _ (HashmapAugE 256 AccountDispatchQueue uint64) = DispatchQueue;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x0, bits_len = 4)]
+pub struct OutMsgQueueExtra {
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterNum<64>, _, _, _>::new(256, min_lt_merge)"
+    )]
+    pub dispatch_queue: HashMap<TonHash, (AccountDispatchQueue, u64)>, // sender_addr -> (queue, min created_lt)
+    #[tlb_derive(bits_len = 48)]
+    pub out_queue_size: Option<u64>,
+}
+
+// _ messages:(HashmapE 64 EnqueuedMsg) count:uint48 = AccountDispatchQueue;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountDispatchQueue {
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(64)")]
+    pub messages: HashMap<u64, EnqueuedMsg>, // created_lt -> msg
+    #[tlb_derive(bits_len = 48)]
+    pub count: u64,
+}
+
+impl OutMsgQueueKey {
+    const BYTES_LEN: usize = 44;
+}
+
+impl From<OutMsgQueueKey> for BigUint {
+    fn from(key: OutMsgQueueKey) -> Self {
+        let mut bytes = Vec::with_capacity(OutMsgQueueKey::BYTES_LEN);
+        bytes.extend_from_slice(&key.next_workchain.to_be_bytes());
+        bytes.extend_from_slice(&key.next_addr_pfx.to_be_bytes());
+        bytes.extend_from_slice(key.msg_hash.as_slice());
+        BigUint::from_bytes_be(&bytes)
+    }
+}
+
+impl TryFrom<BigUint> for OutMsgQueueKey {
+    type Error = TLCoreError;
+
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        let value_bytes = value.to_bytes_be();
+        if value_bytes.len() > Self::BYTES_LEN {
+            bail_tl_core!("OutMsgQueue key {value} doesn't fit into {} bytes", Self::BYTES_LEN);
+        }
+        let mut bytes = [0u8; Self::BYTES_LEN];
+        bytes[Self::BYTES_LEN - value_bytes.len()..].copy_from_slice(&value_bytes);
+        Ok(Self {
+            next_workchain: i32::from_be_bytes(bytes[..4].try_into().unwrap()),
+            next_addr_pfx: u64::from_be_bytes(bytes[4..12].try_into().unwrap()),
+            msg_hash: TonHash::from_slice(&bytes[12..])?,
+        })
+    }
+}
+
+// used as map key, so it's encoded as "next_workchain:next_addr_pfx:msg_hash" string
+#[cfg(feature = "serde")]
+impl serde::Serialize for OutMsgQueueKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let key_str = format!("{}:{:016x}:{}", self.next_workchain, self.next_addr_pfx, self.msg_hash);
+        serializer.serialize_str(&key_str)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OutMsgQueueKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use std::str::FromStr;
+
+        let key_str = <String as serde::Deserialize>::deserialize(deserializer)?;
+        let parse = || -> Option<Self> {
+            let mut parts = key_str.split(':');
+            let key = Self {
+                next_workchain: parts.next()?.parse().ok()?,
+                next_addr_pfx: u64::from_str_radix(parts.next()?, 16).ok()?,
+                msg_hash: TonHash::from_str(parts.next()?).ok()?,
+            };
+            parts.next().is_none().then_some(key)
+        };
+        parse().ok_or_else(|| serde::de::Error::custom(format!("invalid OutMsgQueue key: {key_str}")))
+    }
+}
+
+// both OutMsgQueue and DispatchQueue are augmented with the minimal lt in the subtree
+fn min_lt_merge(left: &u64, right: &u64) -> Result<u64, TLCoreError> { Ok(*left.min(right)) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_tlb::{Coins, IntermediateAddress, MsgEnvelopeV1};
+    use ton_lib_core::cell::TonCell;
+    use ton_lib_core::traits::tlb::TLB;
+
+    #[test]
+    fn test_out_msg_queue_info() -> anyhow::Result<()> {
+        let envelope = MsgEnvelope::V1(MsgEnvelopeV1 {
+            cur_addr: IntermediateAddress::Regular { use_dest_bits: 0 },
+            next_addr: IntermediateAddress::Regular { use_dest_bits: 96 },
+            fwd_fee_remaining: Coins::new(1u32),
+            msg: TLBObject::from_cell_ref(TonCell::EMPTY.into_ref()), // is not parsed
+        });
+        let enqueued = |enqueued_lt| EnqueuedMsg {
+            enqueued_lt,
+            out_msg: TLBObject::new(envelope.clone()),
+        };
+        let first_key = OutMsgQueueKey {
+            next_workchain: -1,
+            next_addr_pfx: 0x8000000000000000,
+            msg_hash: TonHash::from([1u8; 32]),
+        };
+        let second_key = OutMsgQueueKey {
+            next_workchain: 0,
+            next_addr_pfx: 1,
+            msg_hash: TonHash::ZERO,
+        };
+        let info = OutMsgQueueInfo {
+            out_queue: HashMap::from([
+                (first_key.clone(), (enqueued(20), 20)),
+                (second_key.clone(), (enqueued(10), 10)),
+            ]),
+            proc_info: HashMap::from([(
+                (0x8000000000000000u128 << 32) | 5,
+                ProcessedUpto {
+                    last_msg_lt: 1,
+                    last_msg_hash: TonHash::from([1u8; 32]),
+                },
+            )]),
+            extra: Some(OutMsgQueueExtra {
+                dispatch_queue: HashMap::from([(
+                    TonHash::ZERO,
+                    (
+                        AccountDispatchQueue {
+                            messages: HashMap::from([(30, enqueued(30)), (40, enqueued(40))]),
+                            count: 2,
+                        },
+                        30,
+                    ),
+                )]),
+                out_queue_size: Some(2),
+            }),
+        };
+        let cell = info.to_cell()?;
+        assert_eq!(OutMsgQueueInfo::from_cell(&cell)?, info);
+
+        // out_queue fork extra must be the minimal enqueued_lt
+        let mut parser = cell.parser();
+        assert!(parser.read_bit()?);
+        assert_eq!(parser.read_num::<u64>(64)?, 10);
+
+        for key in [first_key, second_key] {
+            assert_eq!(OutMsgQueueKey::try_from(BigUint::from(key.clone()))?, key);
+        }
+        assert!(OutMsgQueueKey::try_from(BigUint::from(1u32) << 352).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::block_tlb::{CurrencyCollection, ShardAccount};
+use crate::tlb_adapters::ConstLen;
+use crate::tlb_adapters::{DictKeyAdapterTonHash, DictValAdapterTLB, TLBHashMapAugE};
+use std::collections::HashMap;
+use ton_lib_core::cell::TonHash;
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ (HashmapAugE 256 ShardAccount DepthBalanceInfo) = ShardAccounts;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardAccounts {
+    #[tlb_derive(
+        adapter = "TLBHashMapAugE::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterTLB, _, _, _>::new(256, DepthBalanceInfo::merge)"
+    )]
+    pub accounts: HashMap<TonHash, (ShardAccount, DepthBalanceInfo)>, // account_addr -> (account, balance)
+}
+
+// depth_balance$_ split_depth:(#<= 30) balance:CurrencyCollection = DepthBalanceInfo;
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepthBalanceInfo {
+    #[tlb_derive(bits_len = 5)]
+    pub split_depth: u8,
+    pub balance: CurrencyCollection,
+}
+
+impl DepthBalanceInfo {
+    /// ShardAccounts augmentation
+    pub fn merge(&self, other: &Self) -> Result<Self, TLCoreError> {
+        Ok(Self {
+            split_depth: self.split_depth.max(other.split_depth),
+            balance: self.balance.checked_add(&other.balance)?,
+        })
+    }
+}
@@ -5,6 +5,7 @@ use ton_lib_core::traits::tlb::TLB;
 use ton_lib_core::TLBDerive;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShardDescrTag {
     Old,
     New,
@@ -12,6 +13,7 @@ pub enum ShardDescrTag {
 
 // https://github.com/ton-blockchain/ton/blame/26761a1d139402ef343081810677d2582c3eff51/crypto/block/block.tlb#L509
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShardDescr {
     pub prefix: ShardDescrTag, // in fact it's TLBPrefix
     pub seqno: u32,
@@ -64,7 +66,7 @@ impl TLB for ShardDescr {
         let (fees, funds) = match prefix {
             ShardDescrTag::Old => (TLB::read(parser)?, TLB::read(parser)?),
             ShardDescrTag::New => {
-                let mut ref_parser = parser.read_next_ref()?.parser();
+                let mut ref_parser = parser.read_next_ref_parser()?;
                 (TLB::read(&mut ref_parser)?, TLB::read(&mut ref_parser)?)
             }
         };
@@ -136,6 +138,7 @@ impl TLB for ShardDescr {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FutureSplitMerge {
     None(FutureSplitMergeNone),
     Split(FutureSplitMergeSplit),
@@ -143,10 +146,12 @@ pub enum FutureSplitMerge {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0, bits_len = 1)]
 pub struct FutureSplitMergeNone;
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b10, bits_len = 2)]
 pub struct FutureSplitMergeSplit {
     pub split_utime: u32,
@@ -154,6 +159,7 @@ pub struct FutureSplitMergeSplit {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b11, bits_len = 2)]
 pub struct FutureSplitMergeMerge {
     pub merge_utime: u32,
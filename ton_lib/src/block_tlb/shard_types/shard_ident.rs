@@ -15,6 +15,7 @@ pub struct ShardPfx {
 
 // TLBType implementation is quite tricky, it doesn't keep shard as is
 #[derive(Clone, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShardIdent {
     pub workchain: i32,
     pub shard: u64,
@@ -25,6 +26,33 @@ impl ShardPfx {
         let tag = 1u64 << (63 - self.bits_len);
         (self.value & (!tag).wrapping_add(1)) | tag
     }
+
+    pub fn from_shard(shard: u64) -> Self {
+        let tag = shard & (!shard).wrapping_add(1);
+        Self {
+            value: shard ^ tag,
+            bits_len: 63 - shard.trailing_zeros(),
+        }
+    }
+}
+
+// used as map key, so it's encoded as hex string of shard (with tag bit)
+#[cfg(feature = "serde")]
+impl serde::Serialize for ShardPfx {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:016x}", self.to_shard()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ShardPfx {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let shard_str = <String as serde::Deserialize>::deserialize(deserializer)?;
+        match u64::from_str_radix(&shard_str, 16) {
+            Ok(shard) if shard != 0 => Ok(Self::from_shard(shard)),
+            _ => Err(serde::de::Error::custom(format!("invalid shard: {shard_str}"))),
+        }
+    }
 }
 
 impl Debug for ShardPfx {
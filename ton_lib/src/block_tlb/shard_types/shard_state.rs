@@ -0,0 +1,260 @@
+use crate::block_tlb::{CurrencyCollection, ExtBlockRef, MCStateExtra, OutMsgQueueInfo, ShardAccounts, ShardIdent};
+use crate::tlb_adapters::{DictKeyAdapterTonHash, DictValAdapterTLB, TLBHashMap, TLBHashMapE};
+use std::collections::HashMap;
+use ton_lib_core::cell::{TonCellRef, TonHash};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::types::TLBObject;
+use ton_lib_core::TLBDerive;
+
+// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/block.tlb
+// _ ShardStateUnsplit = ShardState;
+// split_state#5f327da5 left:^ShardStateUnsplit right:^ShardStateUnsplit = ShardState;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShardState {
+    Unsplit(Box<ShardStateUnsplit>),
+    Split(Box<ShardStateSplit>),
+}
+
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x5f327da5, bits_len = 32)]
+pub struct ShardStateSplit {
+    pub left: TLBObject<ShardStateUnsplit>,
+    pub right: TLBObject<ShardStateUnsplit>,
+}
+
+// shard_state#9023afe2 global_id:int32 shard_id:ShardIdent seq_no:uint32 vert_seq_no:#
+//   gen_utime:uint32 gen_lt:uint64 min_ref_mc_seqno:uint32 out_msg_queue_info:^OutMsgQueueInfo
+//   before_split:(## 1) accounts:^ShardAccounts
+//   ^[ overload_history:uint64 underload_history:uint64 total_balance:CurrencyCollection
+//     total_validator_fees:CurrencyCollection libraries:(HashmapE 256 LibDescr) master_ref:(Maybe BlkMasterInfo) ]
+//   custom:(Maybe ^McStateExtra) = ShardStateUnsplit;
+/// All cells are parsed on access only: states from MerkleUpdate have unchanged subtrees pruned
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0x9023afe2, bits_len = 32)]
+pub struct ShardStateUnsplit {
+    pub global_id: i32,
+    pub shard_ident: ShardIdent,
+    pub seqno: u32,
+    pub vert_seqno: u32,
+    pub gen_utime: u32,
+    pub gen_lt: u64,
+    pub min_ref_mc_seqno: u32,
+    pub out_msg_queue_info: TLBObject<OutMsgQueueInfo>,
+    pub before_split: bool,
+    pub accounts: TLBObject<ShardAccounts>,
+    pub info: TLBObject<ShardStateUnsplitInfo>,
+    pub mc_state_extra: Option<TLBObject<MCStateExtra>>,
+}
+
+// anonymous ^[...] cell of ShardStateUnsplit
+#[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardStateUnsplitInfo {
+    pub overload_history: u64,
+    pub underload_history: u64,
+    pub total_balance: CurrencyCollection,
+    pub total_validator_fees: CurrencyCollection,
+    #[tlb_derive(adapter = "TLBHashMapE::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub libraries: HashMap<TonHash, LibDescr>,
+    pub master_ref: Option<ExtBlockRef>,
+}
+
+// shared_lib_descr$00 lib:^Cell publishers:(Hashmap 256 True) = LibDescr;
+#[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[tlb_derive(prefix = 0b00, bits_len = 2)]
+pub struct LibDescr {
+    pub lib: TonCellRef,
+    #[tlb_derive(adapter = "TLBHashMap::<DictKeyAdapterTonHash, DictValAdapterTLB, _, _>::new(256)")]
+    pub publishers: HashMap<TonHash, ()>,
+}
+
+impl ShardState {
+    /// Returns both halves for split state
+    pub fn unsplit_states(&self) -> Result<Vec<&ShardStateUnsplit>, TLCoreError> {
+        match self {
+            ShardState::Unsplit(state) => Ok(vec![state]),
+            ShardState::Split(split) => Ok(vec![split.left.get()?, split.right.get()?]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_tlb::_test_block_data::{CONFIG_BOC_HEX, MASTER_BLOCK_BOC_HEX, SHARD_BLOCK_BOC_HEX};
+    use crate::block_tlb::*;
+    use ton_lib_core::cell::{LevelMask, TonCell};
+    use ton_lib_core::traits::tlb::TLB;
+    use ton_lib_core::types::tlb_core::MsgAddressInt;
+
+    #[test]
+    fn test_shard_state_from_block_state_update() -> anyhow::Result<()> {
+        let block = Block::from_boc_hex(SHARD_BLOCK_BOC_HEX)?;
+        assert_eq!(block.value_flow.burned(), None); // value_flow v1
+        let new_state_cell = block.state_update.new.cell_ref()?;
+        assert_eq!(new_state_cell.hash_for_level(LevelMask::MIN_LEVEL)?, &block.state_update.new_hash);
+        let ShardState::Unsplit(state) = block.state_update.new.get()? else {
+            panic!("unexpected split state");
+        };
+        assert_eq!(state.shard_ident, block.info.shard);
+        assert_eq!(state.seqno, block.info.seqno);
+        assert_eq!(state.gen_utime, block.info.gen_utime);
+        assert_eq!(state.info.get()?.total_balance, block.value_flow.transfers().to_next_blk);
+        assert_eq!(state.info.get()?.master_ref, block.info.master_ref);
+        assert!(state.mc_state_extra.is_none());
+        // accounts are pruned in merkle update, but they are not parsed until requested
+        assert!(state.accounts.get().is_err());
+        assert_eq!(&state.to_cell()?, &**new_state_cell);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shard_state_from_mc_block_state_update() -> anyhow::Result<()> {
+        let block = Block::from_boc_hex(MASTER_BLOCK_BOC_HEX)?;
+        let ShardState::Unsplit(state) = block.state_update.new.get()? else {
+            panic!("unexpected split state");
+        };
+        assert_eq!(state.shard_ident, block.info.shard);
+        assert_eq!(state.seqno, block.info.seqno);
+        assert_eq!(state.gen_utime, block.info.gen_utime);
+
+        let queue_info = state.out_msg_queue_info.get()?;
+        assert!(queue_info.out_queue.is_empty());
+        assert_eq!(queue_info.proc_info.len(), 1);
+        assert_eq!(queue_info.extra.as_ref().unwrap().out_queue_size, Some(0));
+
+        // unchanged libraries, extra currencies & config are pruned
+        assert!(state.info.get().is_err());
+        assert!(state.mc_state_extra.as_ref().unwrap().get().is_err());
+        assert_eq!(state.to_cell()?.hash_for_level(LevelMask::MIN_LEVEL)?, &block.state_update.new_hash);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shard_state_full() -> anyhow::Result<()> {
+        let shard_account = ShardAccount::from_boc_hex("b5ee9c7201020d0100017500015099602ce40fd84286bddb06f8bcc9fceb7e3027f9826c8985017f16cba12363cc000016e2cc89c18101036fcff34517c7bdf5187c55af4f8b61fdc321588c7ab768dee24b006df29106458d7cf21881f4800000000000005b8b322706090311d3e017f009080202016206030142bf412429205ea66d6f2004edfa570f6f56b3e85e59baa1befbc73b7da5d55bdc61040104123405000456780142bf5a2eef5056775f5b9572ff3ad63dd2a71d1fb281ca177a5e1c74730eccb2e51307000fabacabadabacaba8004811fd096c00000000000000000000000000000000000000000000000000000000000000000114ff00f4a413f4a0f2c80b0a0201200c0b00dfa5ffff76a268698fe9ffe8e42c5267858f90e785ffe4f6aa6467c444ffb365ffc10802faf0807d014035e7a064b87d804077e7857fc10803dfd2407d014035e7a064b86467cd8903a32b9ba4410803ade68afd014035e7a045ea432b6363796103bb7b9363210c678b64b87d807d80400002d2")?;
+        let MaybeAccount::Account(account) = &shard_account.account else {
+            panic!("account expected");
+        };
+        let MsgAddressInt::Std(addr) = &account.addr else {
+            panic!("std address expected");
+        };
+        let balance = account.storage.balance.clone();
+        let accounts = ShardAccounts {
+            accounts: HashMap::from([
+                (
+                    addr.address.clone(),
+                    (
+                        shard_account.clone(),
+                        DepthBalanceInfo {
+                            split_depth: 0,
+                            balance,
+                        },
+                    ),
+                ),
+                (TonHash::ZERO, (ShardAccount::NON_EXIST, DepthBalanceInfo::default())),
+            ]),
+        };
+
+        let block_ref = ExtBlockRef {
+            end_lt: 2,
+            seqno: 1,
+            root_hash: TonHash::from([1u8; 32]),
+            file_hash: TonHash::from([2u8; 32]),
+        };
+        let mc_state_extra = MCStateExtra {
+            shard_hashes: HashMap::new(),
+            config: ConfigParams::from_boc_hex(CONFIG_BOC_HEX)?,
+            info: MCStateExtraInfo {
+                flags: 1,
+                validator_info: ValidatorInfo {
+                    validator_list_hash_short: 1,
+                    catchain_seqno: 2,
+                    nx_cc_updated: true,
+                },
+                prev_blocks: HashMap::from([
+                    (
+                        0,
+                        (
+                            KeyExtBlockRef {
+                                key: true,
+                                block_ref: block_ref.clone(),
+                            },
+                            KeyMaxLt {
+                                key: true,
+                                max_end_lt: 1,
+                            },
+                        ),
+                    ),
+                    (
+                        1,
+                        (
+                            KeyExtBlockRef {
+                                key: false,
+                                block_ref: block_ref.clone(),
+                            },
+                            KeyMaxLt {
+                                key: false,
+                                max_end_lt: 2,
+                            },
+                        ),
+                    ),
+                ]),
+                after_key_block: false,
+                last_key_block: Some(block_ref),
+                block_create_stats: Some(BlockCreateStats::Ext(BlockCreateStatsExt {
+                    counters: HashMap::from([
+                        (TonHash::ZERO, (CreatorStats::default(), 1)),
+                        (TonHash::from([1u8; 32]), (CreatorStats::default(), 1)),
+                    ]),
+                })),
+            },
+            global_balance: CurrencyCollection::new(100u32),
+        };
+
+        let state = ShardStateUnsplit {
+            global_id: -239,
+            shard_ident: ShardIdent::new_mc(),
+            seqno: 3,
+            vert_seqno: 1,
+            gen_utime: 4,
+            gen_lt: 5,
+            min_ref_mc_seqno: 6,
+            out_msg_queue_info: TLBObject::new(OutMsgQueueInfo::default()),
+            before_split: false,
+            accounts: TLBObject::new(accounts.clone()),
+            info: TLBObject::new(ShardStateUnsplitInfo {
+                overload_history: 7,
+                underload_history: 8,
+                total_balance: CurrencyCollection::new(9u32),
+                total_validator_fees: CurrencyCollection::new(10u32),
+                libraries: HashMap::from([(
+                    TonHash::ZERO,
+                    LibDescr {
+                        lib: TonCell::EMPTY.into_ref(),
+                        publishers: HashMap::from([(TonHash::ZERO, ())]),
+                    },
+                )]),
+                master_ref: None,
+            }),
+            mc_state_extra: Some(TLBObject::new(mc_state_extra.clone())),
+        };
+        let state_boc = ShardState::Unsplit(Box::new(state)).to_boc()?;
+
+        let ShardState::Unsplit(parsed) = ShardState::from_boc(&state_boc)? else {
+            panic!("unexpected split state");
+        };
+        assert_eq!(parsed.info.get()?.libraries[&TonHash::ZERO].publishers.len(), 1);
+        assert_eq!(parsed.accounts.get()?, &accounts);
+        let (parsed_account, _) = &parsed.accounts.get()?.accounts[&addr.address];
+        assert_eq!(parsed_account, &shard_account);
+        let parsed_mc_extra = parsed.mc_state_extra.as_ref().unwrap().get()?;
+        assert_eq!(parsed_mc_extra, &mc_state_extra);
+        assert_eq!(parsed_mc_extra.config.global_version()?.version, 9);
+        Ok(())
+    }
+}
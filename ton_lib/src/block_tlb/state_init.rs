@@ -11,6 +11,7 @@ use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blob/59a8cf0ae5c3062d14ec4c89a04fee80b5fd05c1/crypto/block/block.tlb#L281
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StateInit {
     #[tlb_derive(bits_len = 5)]
     pub split_depth: Option<u8>,
@@ -22,12 +23,14 @@ pub struct StateInit {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimpleLib {
     pub public: bool,
     pub root: TonCellRef,
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TickTock {
     pub tick: bool,
     pub tock: bool,
@@ -5,6 +5,7 @@ use ton_lib_core::traits::tlb::{TLBPrefix, TLB};
 // https://github.com/ton-blockchain/ton/blob/ed4682066978f69ffa38dd98912ca77d4f660f66/crypto/block/block.tlb#L873
 // really tricky to implement with current design,
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TVMCellSlice {
     pub value: TonCellRef, // is not part of TLB
     pub cell_original: TonCellRef,
@@ -13,6 +13,7 @@ use ton_lib_core::cell::TonCellRef;
 use ton_lib_core::TLBDerive;
 
 #[derive(Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TVMStackValue {
     Null(TVMNull),
     TinyInt(TVMTinyInt),
@@ -26,10 +27,12 @@ pub enum TVMStackValue {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x00, bits_len = 8)]
 pub struct TVMNull;
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x01, bits_len = 8)]
 pub struct TVMTinyInt {
     pub value: i64,
@@ -37,29 +40,35 @@ pub struct TVMTinyInt {
 
 // vm_stk_int#0201_ value:int257 = VmStackValue; means 0x0201 without latest bit ==> 0000001000000000
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x0100, bits_len = 15)]
 pub struct TVMInt {
     #[tlb_derive(bits_len = 257)]
+    #[cfg_attr(feature = "serde", serde(with = "ton_lib_core::serde_utils::as_string"))]
     pub value: BigInt,
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x02ff, bits_len = 16)]
 pub struct TVMNan;
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x03, bits_len = 8)]
 pub struct TVMCell {
     pub value: TonCellRef,
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x05, bits_len = 8)]
 pub struct TVMBuilder {
     pub cell: TonCellRef,
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TVMCont {
     Std(VMContStd),
     Envelope(TVMContEnvelope),
@@ -74,6 +83,7 @@ pub enum TVMCont {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VMControlData {
     #[tlb_derive(bits_len = 13)]
     pub nargs: Option<u16>,
@@ -83,12 +93,14 @@ pub struct VMControlData {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VMSaveList {
     #[tlb_derive(adapter = "TLBHashMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(4)")]
     pub cregs: HashMap<u8, TVMStackValue>,
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x00, bits_len = 8)]
 pub struct VMContStd {
     pub data: Arc<VMControlData>,
@@ -96,6 +108,7 @@ pub struct VMContStd {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x01, bits_len = 8)]
 pub struct TVMContEnvelope {
     pub data: VMControlData,
@@ -104,16 +117,19 @@ pub struct TVMContEnvelope {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x1000, bits_len = 16)]
 pub struct VMContQuit {
     pub exit_code: i32,
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x1001, bits_len = 16)]
 pub struct TVMContQuitExc {}
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x10100, bits_len = 20)]
 pub struct VMContRepeat {
     #[tlb_derive(bits_len = 63)]
@@ -125,6 +141,7 @@ pub struct VMContRepeat {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x110000, bits_len = 24)]
 pub struct VMContUntil {
     #[tlb_derive(adapter = "TLBRef")]
@@ -134,6 +151,7 @@ pub struct VMContUntil {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x110001, bits_len = 24)]
 pub struct VMContAgain {
     #[tlb_derive(adapter = "TLBRef")]
@@ -141,6 +159,7 @@ pub struct VMContAgain {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x110010, bits_len = 24)]
 pub struct VMContWhileCond {
     #[tlb_derive(adapter = "TLBRef")]
@@ -152,6 +171,7 @@ pub struct VMContWhileCond {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x110011, bits_len = 24)]
 pub struct VMContWhileBody {
     #[tlb_derive(adapter = "TLBRef")]
@@ -163,6 +183,7 @@ pub struct VMContWhileBody {
 }
 
 #[derive(Debug, Clone, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0x1111, bits_len = 16)]
 pub struct VMContPushInt {
     pub value: i32,
@@ -20,6 +20,7 @@ macro_rules! extract_tuple_val {
 // Doesn't implement tlb schema directly for convenience purposes
 // Very similar with VMStackValue, but random access to underlying values
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TVMTuple(Vec<TVMStackValue>);
 
 impl Deref for TVMTuple {
@@ -71,16 +72,16 @@ impl TLB for TVMTuple {
 
 fn read_tuple(parser: &mut CellParser, data: &mut Vec<TVMStackValue>, rest_len: usize) -> Result<(), TLCoreError> {
     read_tuple_ref(parser, data, rest_len - 1)?;
-    data.push(TVMStackValue::from_cell(parser.read_next_ref()?)?);
+    data.push(TVMStackValue::read(&mut parser.read_next_ref_parser()?)?);
     Ok(())
 }
 
 fn read_tuple_ref(parser: &mut CellParser, data: &mut Vec<TVMStackValue>, rest_len: usize) -> Result<(), TLCoreError> {
     match rest_len {
         0 => {}
-        1 => data.push(TVMStackValue::from_cell(parser.read_next_ref()?)?),
+        1 => data.push(TVMStackValue::read(&mut parser.read_next_ref_parser()?)?),
         _ => {
-            let mut ref_parser = parser.read_next_ref()?.parser();
+            let mut ref_parser = parser.read_next_ref_parser()?;
             read_tuple(&mut ref_parser, data, rest_len)?
         }
     }
@@ -8,6 +8,7 @@ use ton_lib_core::TLBDerive;
 
 // https://github.com/ton-blockchain/ton/blob/ed4682066978f69ffa38dd98912ca77d4f660f66/crypto/block/block.tlb#L353
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TxDescr {
     Ord(TxDescrOrd),
     Storage(TxDescrStorage),
@@ -19,6 +20,7 @@ pub enum TxDescr {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0000, bits_len = 4)]
 pub struct TxDescrOrd {
     pub credit_first: bool,
@@ -33,12 +35,14 @@ pub struct TxDescrOrd {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0001, bits_len = 4)]
 pub struct TxDescrStorage {
     pub storage_phase: TrStoragePhase,
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b001, bits_len = 3)]
 pub struct TxDescrTickTock {
     pub is_tock: bool,
@@ -51,6 +55,7 @@ pub struct TxDescrTickTock {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0110, bits_len = 4)]
 pub struct TxDescrSplitPrepare {
     pub split_info: SplitMergeInfo,
@@ -63,6 +68,7 @@ pub struct TxDescrSplitPrepare {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0101, bits_len = 4)]
 pub struct TxDescrSplitInstall {
     pub split_info: SplitMergeInfo,
@@ -72,6 +78,7 @@ pub struct TxDescrSplitInstall {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0110, bits_len = 4)]
 pub struct TxDescrMergePrepare {
     pub split_info: SplitMergeInfo,
@@ -80,6 +87,7 @@ pub struct TxDescrMergePrepare {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0111, bits_len = 4)]
 pub struct TxDescrMergeInstall {
     pub split_info: SplitMergeInfo,
@@ -97,6 +105,7 @@ pub struct TxDescrMergeInstall {
 }
 
 #[derive(Debug, Clone, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SplitMergeInfo {
     #[tlb_derive(bits_len = 6)]
     pub cur_shard_pfx_len: u8,
@@ -1,10 +1,11 @@
 use crate::block_tlb::*;
 use crate::tlb_adapters::TLBRef;
-use ton_lib_core::cell::TonHash;
+use ton_lib_core::cell::{CellStorageStats, TonHash};
 use ton_lib_core::types::tlb_core::VarLenBytes;
 use ton_lib_core::TLBDerive;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrStoragePhase {
     pub storage_fees_collected: Coins,
     pub storage_fees_due: Option<Coins>,
@@ -12,6 +13,7 @@ pub struct TrStoragePhase {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TrComputePhase {
     Skipped(TrComputePhaseSkipped),
     #[rustfmt::skip]
@@ -27,6 +29,7 @@ impl Default for TrComputePhase {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrActionPhase {
     pub success: bool,
     pub valid: bool,
@@ -45,6 +48,7 @@ pub struct TrActionPhase {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TrBouncePhase {
     NegFunds(TrBouncePhaseNegFunds),
     NoFunds(TrBouncePhaseNoFunds),
@@ -52,18 +56,34 @@ pub enum TrBouncePhase {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StorageUsedShort {
     pub cells: VarLenBytes<u64, 3>,
     pub bits: VarLenBytes<u64, 3>,
 }
 
+impl StorageUsedShort {
+    pub fn new(cells: u64, bits: u64) -> Self {
+        Self {
+            cells: VarLenBytes::new_min_len(cells),
+            bits: VarLenBytes::new_min_len(bits),
+        }
+    }
+}
+
+impl From<CellStorageStats> for StorageUsedShort {
+    fn from(stats: CellStorageStats) -> Self { Self::new(stats.cells, stats.bits) }
+}
+
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0, bits_len = 1)]
 pub struct TrComputePhaseSkipped {
     pub reason: TxComputeSkipReason,
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b1, bits_len = 1)]
 pub struct TrComputePhaseVM {
     pub success: bool,
@@ -75,6 +95,7 @@ pub struct TrComputePhaseVM {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComputePhaseVMInfo {
     pub gas_used: VarLenBytes<u64, 3>,
     pub gas_limit: VarLenBytes<u64, 3>,
@@ -88,6 +109,7 @@ pub struct ComputePhaseVMInfo {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccStatusChange {
     Unchanged(AccStatusChangeUnchanged), // x -> x
     Frozen(AccStatusChangeFrozen),       // init -> frozen
@@ -95,28 +117,34 @@ pub enum AccStatusChange {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b0, bits_len = 1)]
 pub struct AccStatusChangeUnchanged;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b10, bits_len = 2)]
 pub struct AccStatusChangeFrozen;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b11, bits_len = 2)]
 pub struct AccStatusChangeDeleted;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrCreditPhase {
     pub due_fees_collected: Option<Coins>,
     pub credit: CurrencyCollection,
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b00, bits_len = 2)]
 pub struct TrBouncePhaseNegFunds;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b01, bits_len = 2)]
 pub struct TrBouncePhaseNoFunds {
     pub msg_size: StorageUsedShort,
@@ -124,6 +152,7 @@ pub struct TrBouncePhaseNoFunds {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b1, bits_len = 1)]
 pub struct TrBouncePhaseOk {
     pub msg_size: StorageUsedShort,
@@ -132,6 +161,7 @@ pub struct TrBouncePhaseOk {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TxComputeSkipReason {
     NoState(TxComputeSkipReasonNoState),
     BadState(TxComputeSkipReasonBadState),
@@ -140,17 +170,21 @@ pub enum TxComputeSkipReason {
 }
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b00, bits_len = 2)]
 pub struct TxComputeSkipReasonNoState;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b01, bits_len = 2)]
 pub struct TxComputeSkipReasonBadState;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b10, bits_len = 2)]
 pub struct TxComputeSkipReasonNoGas;
 
 #[derive(Clone, Debug, PartialEq, TLBDerive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[tlb_derive(prefix = 0b110, bits_len = 3)]
 pub struct TxComputeSkipReasonSuspended;
@@ -5,6 +5,7 @@ use thiserror::Error;
 use tokio::time::error::Elapsed;
 use ton_lib_core::error::TLCoreError;
 use ton_lib_core::types::{TonAddress, TxIdLTHash};
+use ton_lib_tlb_schema::TLBSchemaError;
 use ton_liteapi::tl::request::Request;
 use ton_liteapi::types::LiteError;
 
@@ -83,6 +84,17 @@ pub enum TLError {
     #[error("MnemonicFirstBytePassless: first byte can't be {0}")]
     MnemonicFirstBytePassless(u8),
 
+    // TLBSchema
+    #[error("TLBSchemaWrongFormat: line {line}: {msg}")]
+    TLBSchemaWrongFormat { line: usize, msg: String },
+    #[error("TLBSchemaDecodeError: {msg} at '{path}' (bit_pos={bit_pos}, ref_pos={ref_pos})")]
+    TLBSchemaDecodeError {
+        path: String,
+        bit_pos: usize,
+        ref_pos: usize,
+        msg: String,
+    },
+
     // General errors
     #[error("UnexpectedValue: expected: {expected}, actual: {actual}")]
     UnexpectedValue { expected: String, actual: String },
@@ -138,3 +150,12 @@ impl From<TLError> for TLCoreError {
 impl From<&TLError> for TLCoreError {
     fn from(err: &TLError) -> Self { TLCoreError::Custom(err.to_string()) }
 }
+
+impl From<TLBSchemaError> for TLError {
+    fn from(err: TLBSchemaError) -> Self {
+        TLError::TLBSchemaWrongFormat {
+            line: err.line,
+            msg: err.msg,
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::block_tlb::*;
+use crate::error::TLError;
+use std::sync::Arc;
+use ton_lib_core::types::tlb_core::MsgAddress;
+
+/// Offline fee calculator, follows the node implementation:
+/// https://github.com/ton-blockchain/ton/blob/6f745c04daf8861bb1791cffce6edb1beec62204/crypto/block/transaction.cpp
+///
+/// All prices in config are stored in 2^-16 nanotons units, so every result is rounded up to nanotons.
+#[derive(Debug, Clone)]
+pub struct FeeCalculator {
+    storage_prices: Vec<StoragePrices>, // sorted by utime_since
+    mc_gas_prices: Arc<GasLimitsPrices>,
+    gas_prices: Arc<GasLimitsPrices>,
+    mc_fwd_prices: Arc<MsgForwardPrices>,
+    fwd_prices: Arc<MsgForwardPrices>,
+    extra_currency_v2: bool,
+}
+
+impl FeeCalculator {
+    pub fn new(config: &ConfigParams) -> Result<Self, TLError> {
+        let mut storage_prices: Vec<_> = config.storage_prices()?.storage_prices.values().cloned().collect();
+        storage_prices.sort_by_key(|prices| prices.utime_since);
+        Ok(Self {
+            storage_prices,
+            mc_gas_prices: config.mc_gas_prices()?,
+            gas_prices: config.gas_prices()?,
+            mc_fwd_prices: config.mc_fwd_prices()?,
+            fwd_prices: config.fwd_prices()?,
+            extra_currency_v2: config.global_version()?.extra_currency_v2(),
+        })
+    }
+
+    pub fn gas_prices(&self, is_mc: bool) -> &GasLimitsPrices {
+        if is_mc {
+            &self.mc_gas_prices
+        } else {
+            &self.gas_prices
+        }
+    }
+    pub fn fwd_prices(&self, is_mc: bool) -> &MsgForwardPrices {
+        if is_mc {
+            &self.mc_fwd_prices
+        } else {
+            &self.fwd_prices
+        }
+    }
+
+    /// Storage fee for the period [last_paid, now)
+    pub fn storage_fee(&self, used: &StorageUsed, last_paid: u32, now: u32, is_mc: bool, is_special: bool) -> Coins {
+        let prices = &self.storage_prices;
+        if now <= last_paid || last_paid == 0 || is_special || prices.is_empty() || now <= prices[0].utime_since {
+            return Coins::ZERO;
+        }
+        let (cells, bits) = (*used.cells as u128, *used.bits as u128);
+        // start from the last prices which were valid at last_paid
+        let mut pos = prices.iter().rposition(|p| p.utime_since <= last_paid).unwrap_or(0);
+        let mut upto = last_paid.max(prices[0].utime_since);
+        let mut total = 0u128;
+        while pos < prices.len() && upto < now {
+            let valid_until = prices.get(pos + 1).map(|next| next.utime_since.min(now)).unwrap_or(now);
+            if upto < valid_until {
+                let (cell_price, bit_price) = match is_mc {
+                    true => (prices[pos].mc_cell_price_ps, prices[pos].mc_bit_price_ps),
+                    false => (prices[pos].cell_price_ps, prices[pos].bit_price_ps),
+                };
+                total += (cells * cell_price as u128 + bits * bit_price as u128) * (valid_until - upto) as u128;
+            }
+            upto = valid_until;
+            pos += 1;
+        }
+        Coins::new(ceil_shr16(total))
+    }
+
+    /// Gas fee for gas_used, including flat part. Special accounts don't pay for gas
+    pub fn gas_fee(&self, gas_used: u64, is_mc: bool, is_special: bool) -> Coins {
+        if is_special {
+            return Coins::ZERO;
+        }
+        let prices = self.gas_prices(is_mc);
+        let (flat_gas_limit, flat_gas_price) = prices.flat_gas();
+        if gas_used <= flat_gas_limit {
+            return Coins::new(flat_gas_price);
+        }
+        let variable = ceil_shr16((gas_used - flat_gas_limit) as u128 * prices.gas_price() as u128);
+        Coins::new(flat_gas_price as u128 + variable)
+    }
+
+    /// Amount of gas which can be bought for value, capped by gas_limit
+    pub fn gas_bought_for(&self, value: &Coins, is_mc: bool) -> u64 {
+        let prices = self.gas_prices(is_mc);
+        let (flat_gas_limit, flat_gas_price) = prices.flat_gas();
+        let max_gas_threshold =
+            ceil_shr16(prices.gas_limit().saturating_sub(flat_gas_limit) as u128 * prices.gas_price() as u128)
+                + flat_gas_price as u128;
+        if **value >= max_gas_threshold {
+            return prices.gas_limit();
+        }
+        if **value < flat_gas_price as u128 || prices.gas_price() == 0 {
+            return 0;
+        }
+        let bought = ((**value - flat_gas_price as u128) << 16) / prices.gas_price() as u128;
+        bought as u64 + flat_gas_limit
+    }
+
+    /// Max gas the account may spend in compute phase (gas_max in the node)
+    pub fn gas_max(&self, balance: &Coins, is_mc: bool, is_special: bool) -> u64 {
+        match is_special {
+            true => {
+                let prices = self.gas_prices(is_mc);
+                prices.special_gas_limit().unwrap_or(prices.gas_limit())
+            }
+            false => self.gas_bought_for(balance, is_mc),
+        }
+    }
+
+    /// Message size the fees are computed for, see Msg::size
+    pub fn msg_size(&self, msg: &Msg) -> Result<StorageUsedShort, TLError> { Ok(msg.size(self.extra_currency_v2)?) }
+
+    /// Forward fee for cells & bits of message, excluding root cell
+    pub fn fwd_fee_for_size(&self, cells: u64, bits: u64, is_mc: bool) -> Coins {
+        let prices = self.fwd_prices(is_mc);
+        let variable = ceil_shr16(prices.bit_price as u128 * bits as u128 + prices.cell_price as u128 * cells as u128);
+        Coins::new(prices.lump_price as u128 + variable)
+    }
+
+    /// Total forward fee for outbound internal message.
+    ///
+    /// Masterchain prices are used if src or dst is in masterchain, so set src explicitly when sending from masterchain
+    pub fn fwd_fee(&self, msg: &Msg) -> Result<Coins, TLError> {
+        let is_mc = is_mc_addr(&msg.src()) || is_mc_addr(&msg.dst());
+        let size = self.msg_size(msg)?;
+        Ok(self.fwd_fee_for_size(*size.cells, *size.bits, is_mc))
+    }
+
+    /// Part of the total forward fee which is collected immediately as action fee.
+    /// The rest is stored in msg.fwd_fee
+    pub fn fwd_fee_first_part(&self, fwd_fee: &Coins, is_mc: bool) -> Coins {
+        Coins::new((**fwd_fee * self.fwd_prices(is_mc).first_frac as u128) >> 16)
+    }
+
+    /// Import fee for inbound external message
+    pub fn import_fee(&self, msg: &Msg) -> Result<Coins, TLError> {
+        let size = self.msg_size(msg)?;
+        Ok(self.fwd_fee_for_size(*size.cells, *size.bits, is_mc_addr(&msg.dst())))
+    }
+}
+
+fn ceil_shr16(value: u128) -> u128 { (value >> 16) + u128::from(value & 0xffff != 0) }
+
+fn is_mc_addr(addr: &MsgAddress) -> bool { matches!(addr, MsgAddress::Int(addr) if addr.wc() == -1) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_tlb::_test_block_data::{CONFIG_BOC_HEX, MASTER_BLOCK_BOC_HEX, SHARD_BLOCK_BOC_HEX};
+    use std::collections::HashMap;
+    use ton_lib_core::cell::TonCell;
+    use ton_lib_core::traits::tlb::TLB;
+    use ton_lib_core::types::tlb_core::VarLenBytes;
+
+    fn calculator() -> anyhow::Result<FeeCalculator> {
+        Ok(FeeCalculator::new(&ConfigParams::from_boc_hex(CONFIG_BOC_HEX)?)?)
+    }
+
+    #[test]
+    fn test_fees_match_shard_block() -> anyhow::Result<()> {
+        let (gas_checked, fwd_checked, import_checked) = check_block_fees(SHARD_BLOCK_BOC_HEX, false)?;
+        assert!(gas_checked > 0);
+        assert!(fwd_checked > 0);
+        assert!(import_checked > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fees_match_master_block() -> anyhow::Result<()> {
+        // master block has only tick-tock txs & elector tx, so just zero gas fees of special accounts are checked
+        let (gas_checked, fwd_checked, import_checked) = check_block_fees(MASTER_BLOCK_BOC_HEX, true)?;
+        assert_eq!(gas_checked, 3);
+        assert_eq!(fwd_checked, 0);
+        assert_eq!(import_checked, 0);
+        Ok(())
+    }
+
+    // checks fees of ordinary & tick-tock txs in block, returns counts of checked (gas, fwd, import) fees
+    fn check_block_fees(block_hex: &str, is_mc: bool) -> anyhow::Result<(usize, usize, usize)> {
+        let config = ConfigParams::from_boc_hex(CONFIG_BOC_HEX)?;
+        let calc = FeeCalculator::new(&config)?;
+        // special accounts are listed in param 31, but only config & elector have txs in test blocks
+        let special_addrs = [
+            config.config_contract()?.config_addr.clone(),
+            config.elector_contract()?.elector_addr.clone(),
+        ];
+        let block = Block::from_boc_hex(block_hex)?;
+        let extra = block.extra.get()?;
+
+        let (mut gas_checked, mut fwd_checked, mut import_checked) = (0, 0, 0);
+        for (account_block, _) in extra.account_blocks.get()?.accounts.values() {
+            let is_special = is_mc && special_addrs.contains(&account_block.account_addr);
+            for (tx, _) in account_block.txs.values() {
+                let tx = tx.get()?;
+                let (compute_phase, descr) = match tx.descr.get()? {
+                    TxDescr::Ord(descr) => (&descr.compute_phase, Some(descr)),
+                    TxDescr::TickTock(descr) => (&descr.compute_phase, None),
+                    _ => continue,
+                };
+                if let TrComputePhase::VM(compute) = compute_phase {
+                    let gas_used = *compute.compute_phase_vm_info.gas_used;
+                    assert_eq!(calc.gas_fee(gas_used, is_mc, is_special), compute.gas_fees);
+                    gas_checked += 1;
+                }
+                let Some(descr) = descr else {
+                    continue;
+                };
+                // total_fees of ext_in tx: import_fee + storage_fee + gas_fee + action_fee
+                if let Some(in_msg) =
+                    tx.msgs.get()?.in_msg.as_ref().filter(|msg| matches!(msg.info, CommonMsgInfo::ExtIn(_)))
+                {
+                    let storage_fee = descr.storage_phase.as_ref().map(|x| *x.storage_fees_collected).unwrap_or(0);
+                    let gas_fee = match &descr.compute_phase {
+                        TrComputePhase::VM(compute) => *compute.gas_fees,
+                        _ => 0,
+                    };
+                    let action_fee = descr.action.as_ref().and_then(|x| x.total_action_fees).map(|x| *x).unwrap_or(0);
+                    let import_fee = *calc.import_fee(in_msg)?;
+                    assert_eq!(import_fee + storage_fee + gas_fee + action_fee, *tx.total_fees.grams);
+                    import_checked += 1;
+                }
+                let Some(action) = &descr.action else {
+                    continue;
+                };
+                let (mut total_fwd_fees, mut total_action_fees) = (0, 0);
+                for msg in &tx.msgs.get()?.out_msgs {
+                    let CommonMsgInfo::Int(info) = &msg.info else {
+                        continue;
+                    };
+                    let fwd_fee = calc.fwd_fee(msg)?;
+                    let first_part = calc.fwd_fee_first_part(&fwd_fee, is_mc);
+                    assert_eq!(*fwd_fee - *first_part, *info.fwd_fee);
+                    total_fwd_fees += *fwd_fee;
+                    total_action_fees += *first_part;
+                    fwd_checked += 1;
+                }
+                if tx.msgs.get()?.out_msgs.iter().all(|msg| matches!(msg.info, CommonMsgInfo::Int(_))) {
+                    assert_eq!(total_fwd_fees, *action.total_fwd_fees.unwrap_or(Coins::ZERO));
+                    assert_eq!(total_action_fees, *action.total_action_fees.unwrap_or(Coins::ZERO));
+                }
+            }
+        }
+        Ok((gas_checked, fwd_checked, import_checked))
+    }
+
+    #[test]
+    fn test_fees_gas() -> anyhow::Result<()> {
+        let calc = calculator()?;
+        let prices = calc.gas_prices(false).clone();
+        let (flat_gas_limit, flat_gas_price) = prices.flat_gas();
+        assert_eq!(*calc.gas_fee(0, false, false), flat_gas_price as u128);
+        assert_eq!(*calc.gas_fee(flat_gas_limit, false, false), flat_gas_price as u128);
+        let fee = calc.gas_fee(flat_gas_limit + 1000, false, false);
+        assert_eq!(*fee, flat_gas_price as u128 + ceil_shr16(1000 * prices.gas_price() as u128));
+        assert_eq!(calc.gas_bought_for(&fee, false), flat_gas_limit + 1000);
+        assert_eq!(calc.gas_bought_for(&Coins::ZERO, false), 0);
+        assert_eq!(calc.gas_bought_for(&Coins::new(u64::MAX), false), prices.gas_limit());
+        assert_eq!(calc.gas_max(&Coins::ZERO, true, true), calc.gas_prices(true).special_gas_limit().unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fees_storage() -> anyhow::Result<()> {
+        let mut calc = calculator()?;
+        let used = StorageUsed {
+            cells: VarLenBytes::new(10u64, 8),
+            bits: VarLenBytes::new(5000u64, 16),
+        };
+        let prices = calc.storage_prices[0].clone();
+        let expected = ceil_shr16((10 * prices.cell_price_ps as u128 + 5000 * prices.bit_price_ps as u128) * 86400);
+        assert_eq!(*calc.storage_fee(&used, 1_000_000, 1_086_400, false, false), expected);
+        assert_eq!(calc.storage_fee(&used, 1_000_000, 1_086_400, false, true), Coins::ZERO);
+        assert_eq!(calc.storage_fee(&used, 1_086_400, 1_000_000, false, false), Coins::ZERO);
+
+        // prices changed in the middle of the period
+        let new_prices = StoragePrices {
+            utime_since: 1_043_200,
+            bit_price_ps: prices.bit_price_ps * 2,
+            cell_price_ps: prices.cell_price_ps * 2,
+            mc_bit_price_ps: prices.mc_bit_price_ps * 2,
+            mc_cell_price_ps: prices.mc_cell_price_ps * 2,
+        };
+        calc.storage_prices.push(new_prices);
+        let mc_per_sec = 10 * prices.mc_cell_price_ps as u128 + 5000 * prices.mc_bit_price_ps as u128;
+        let expected = ceil_shr16(mc_per_sec * 43200 + mc_per_sec * 2 * 43200);
+        assert_eq!(*calc.storage_fee(&used, 1_000_000, 1_086_400, true, false), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fees_fwd_msg_size() -> anyhow::Result<()> {
+        let mut calc = calculator()?;
+        calc.extra_currency_v2 = true;
+        let body = TonCell::builder().build()?;
+        let mut msg = Msg::new(CommonMsgInfoInt::default(), body);
+        // empty body in ref is 1 cell with 0 bits, default addresses are in masterchain
+        assert_eq!(calc.msg_size(&msg)?, StorageUsedShort::new(1, 0));
+        let prices = calc.fwd_prices(true);
+        assert_eq!(*calc.fwd_fee(&msg)?, prices.lump_price as u128 + ceil_shr16(prices.cell_price as u128));
+
+        // extra currencies are not counted
+        let CommonMsgInfo::Int(info) = &mut msg.info else {
+            unreachable!()
+        };
+        info.value.other = HashMap::from([(1, VarLenBytes::new(100u32, 8))]);
+        assert_eq!(calc.msg_size(&msg)?, StorageUsedShort::new(1, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fees_fwd_extra_currencies() -> anyhow::Result<()> {
+        let calc = calculator()?;
+        let mut info = CommonMsgInfoInt::default();
+        info.value.other = HashMap::from([(1, VarLenBytes::new(100u32, 8))]);
+        let msg = Msg::new(info, TonCell::builder().build()?);
+        let currencies_bits = msg.to_cell()?.refs[0].data_bits_len as u128;
+
+        // extra currencies dict is counted until global version 10
+        assert!(!calc.extra_currency_v2);
+        let prices = calc.fwd_prices(true).clone();
+        let variable = ceil_shr16(2 * prices.cell_price as u128 + currencies_bits * prices.bit_price as u128);
+        assert_eq!(calc.msg_size(&msg)?, StorageUsedShort::new(2, currencies_bits as u64));
+        assert_eq!(*calc.fwd_fee(&msg)?, prices.lump_price as u128 + variable);
+        Ok(())
+    }
+}
@@ -2,9 +2,11 @@ pub use ton_lib_core; // re-export
 pub mod block_tlb;
 pub mod clients;
 pub mod error;
+pub mod fees;
 pub mod libs_dict;
 pub mod tep;
 pub mod tlb_adapters;
+pub mod tlb_dynamic;
 pub mod wallet;
 
 #[cfg(feature = "tonlibjson")]
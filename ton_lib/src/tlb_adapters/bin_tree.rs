@@ -44,13 +44,13 @@ impl<VA: DictValAdapter<T>, T: TLB> BinTree<VA, T> {
             value: cur_key.value,
             bits_len: new_bits_len,
         };
-        Self::read_impl(&mut parser.read_next_ref()?.parser(), left_key, cur_val)?;
+        Self::read_impl(&mut parser.read_next_ref_parser()?, left_key, cur_val)?;
 
         let right_key = ShardPfx {
             value: cur_key.value | (1 << (64 - new_bits_len)),
             bits_len: new_bits_len,
         };
-        Self::read_impl(&mut parser.read_next_ref()?.parser(), right_key, cur_val)?;
+        Self::read_impl(&mut parser.read_next_ref_parser()?, right_key, cur_val)?;
         Ok(())
     }
 
@@ -1,20 +1,22 @@
 use super::label_type::DictLabelType;
-use super::leading_bit_utils::{add_leading_bit, all_bits_same, common_prefix_len, remove_leading_bit};
+use super::leading_bit_utils::{add_leading_bit, common_prefix_len};
 use crate::tlb_adapters::DictValAdapter;
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
+use num_traits::One;
 use std::marker::PhantomData;
 use std::mem::swap;
+use std::ops::Range;
 use ton_lib_core::cell::CellBuilder;
 use ton_lib_core::cell::TonCell;
 use ton_lib_core::error::TLCoreError;
-use ton_lib_core::traits::tlb::TLB;
-use ton_lib_core::types::tlb_core::UnaryLen;
+
+type ForkExtraWriter<'a> = dyn FnMut(&mut CellBuilder, Range<usize>, Range<usize>) -> Result<(), TLCoreError> + 'a;
 
 pub struct DictDataBuilder<'a, T, VA: DictValAdapter<T>> {
     keys_sorted: Vec<BigUint>, // contains 1 extra leading bit set to 1
     values_sorted: &'a [&'a T],
     key_bits_len_left: usize,
+    fork_extra_writer: Option<Box<ForkExtraWriter<'a>>>,
     _phantom: PhantomData<VA>,
 }
 
@@ -30,11 +32,22 @@ impl<'a, T, VA: DictValAdapter<T>> DictDataBuilder<'a, T, VA> {
             keys_sorted,
             values_sorted,
             key_bits_len_left: key_bits_len,
+            fork_extra_writer: None,
             _phantom: PhantomData,
         };
         Ok(builder)
     }
 
+    /// HashmapAug support: writer is called for each fork with positions of left & right subtrees values.
+    /// Subtrees are always written before their fork
+    pub fn with_fork_extra_writer(
+        mut self,
+        writer: impl FnMut(&mut CellBuilder, Range<usize>, Range<usize>) -> Result<(), TLCoreError> + 'a,
+    ) -> Self {
+        self.fork_extra_writer = Some(Box::new(writer));
+        self
+    }
+
     pub fn build(mut self) -> Result<TonCell, TLCoreError> {
         let mut builder = TonCell::builder();
         if self.keys_sorted.is_empty() {
@@ -66,7 +79,7 @@ impl<'a, T, VA: DictValAdapter<T>> DictDataBuilder<'a, T, VA> {
             let ignored_suffix_len = key_len - common_prefix_len - 1;
             key >> ignored_suffix_len
         };
-        self.store_label(builder, &label)?;
+        DictLabelType::write(builder, &label, self.key_bits_len_left)?;
 
         let mut left_keys = Vec::with_capacity(keys.len() / 2);
         let mut right_keys = Vec::with_capacity(keys.len() / 2);
@@ -84,6 +97,10 @@ impl<'a, T, VA: DictValAdapter<T>> DictDataBuilder<'a, T, VA> {
             }
         }
 
+        // keys are sorted, so each subtree holds continuous range of values
+        let left_range = left_keys[0].0..left_keys[left_keys.len() - 1].0 + 1;
+        let right_range = right_keys[0].0..right_keys[right_keys.len() - 1].0 + 1;
+
         self.key_bits_len_left -= common_prefix_len + 1; // branch consumes 1 more bit
         let mut left_builder = TonCell::builder();
         self.fill_cell(&mut left_builder, left_keys)?;
@@ -93,6 +110,9 @@ impl<'a, T, VA: DictValAdapter<T>> DictDataBuilder<'a, T, VA> {
         self.fill_cell(&mut right_builder, right_keys)?;
         builder.write_ref(right_builder.build()?.into_ref())?;
 
+        if let Some(writer) = &mut self.fork_extra_writer {
+            writer(builder, left_range, right_range)?;
+        }
         self.key_bits_len_left = key_len_bits_left_original;
         Ok(())
     }
@@ -103,57 +123,10 @@ impl<'a, T, VA: DictValAdapter<T>> DictDataBuilder<'a, T, VA> {
         orig_key_pos: usize,
         label: &BigUint,
     ) -> Result<(), TLCoreError> {
-        self.store_label(builder, label)?;
+        DictLabelType::write(builder, label, self.key_bits_len_left)?;
         VA::write(builder, self.values_sorted[orig_key_pos])?;
         Ok(())
     }
-
-    // expect label with leading one
-    fn store_label(&self, builder: &mut CellBuilder, label: &BigUint) -> Result<(), TLCoreError> {
-        assert!(label.bits() > 0);
-        if label.is_one() {
-            // it's leading bit => label_type == short, len == 0 => store [false, false]
-            builder.write_num(&0, 2)?;
-            return Ok(());
-        }
-        let all_bits_same = all_bits_same(label);
-
-        let label_len = label.bits() as usize - 1;
-        let label_len_len = (self.key_bits_len_left as f32 + 1.0).log2().ceil() as usize;
-        let fair_label = remove_leading_bit(label);
-        let same_label_len = if all_bits_same { 3 + label_len_len } else { usize::MAX };
-        let short_label_len = 2 + label_len * 2;
-        let long_label_len = 2 + label_len_len + label_len;
-
-        let mut label_type = DictLabelType::Short;
-        if long_label_len < short_label_len {
-            label_type = DictLabelType::Long;
-        }
-        if same_label_len < short_label_len {
-            label_type = DictLabelType::Same;
-        }
-        match label_type {
-            DictLabelType::Same => {
-                builder.write_bit(true)?;
-                builder.write_bit(true)?;
-                builder.write_bit(!fair_label.is_zero())?;
-                builder.write_num(&label_len, label_len_len)?;
-            }
-            DictLabelType::Short => {
-                builder.write_bit(false)?;
-                let unary_len = UnaryLen(label_len);
-                unary_len.write(builder)?;
-                builder.write_num(&fair_label, label_len)?;
-            }
-            DictLabelType::Long => {
-                builder.write_bit(true)?;
-                builder.write_bit(false)?;
-                builder.write_num(&label_len, label_len_len)?;
-                builder.write_num(&fair_label, label_len)?;
-            }
-        }
-        Ok(())
-    }
 }
 
 fn prepare_keys(keys: &mut [BigUint], key_bits_len: usize) -> Result<(), TLCoreError> {
@@ -1,13 +1,12 @@
 use std::collections::HashMap;
 
 use super::label_type::DictLabelType;
+use super::leading_bit_utils::remove_leading_bit;
 use crate::tlb_adapters::DictValAdapter;
 use num_bigint::BigUint;
 use num_traits::One;
 use ton_lib_core::cell::CellParser;
 use ton_lib_core::error::TLCoreError;
-use ton_lib_core::traits::tlb::TLB;
-use ton_lib_core::types::tlb_core::UnaryLen;
 
 pub struct DictDataParser {
     key_bits_len: usize,
@@ -26,87 +25,62 @@ impl DictDataParser {
         &mut self,
         parser: &mut CellParser,
     ) -> Result<HashMap<BigUint, T>, TLCoreError> {
+        self.read_with(parser, &mut |val_parser: &mut CellParser| VA::read(val_parser))
+    }
+
+    /// Same as `read`, but values are read by `read_val` instead of DictValAdapter
+    pub fn read_with<T, E: From<TLCoreError>>(
+        &mut self,
+        parser: &mut CellParser,
+        read_val: &mut dyn FnMut(&mut CellParser) -> Result<T, E>,
+    ) -> Result<HashMap<BigUint, T>, E> {
+        self.read_aug_with(parser, read_val, &mut |_| Ok(()))
+    }
+
+    /// Same as `read_with`, but `read_fork_extra` is called for each fork node (HashmapAug)
+    pub fn read_aug_with<T, E: From<TLCoreError>>(
+        &mut self,
+        parser: &mut CellParser,
+        read_val: &mut dyn FnMut(&mut CellParser) -> Result<T, E>,
+        read_fork_extra: &mut dyn FnMut(&mut CellParser) -> Result<(), E>,
+    ) -> Result<HashMap<BigUint, T>, E> {
         // reset state in case of reusing
         self.cur_key_prefix = BigUint::one();
 
         let mut result = HashMap::new();
-        self.parse_impl::<T, VA>(parser, &mut result)?;
+        self.parse_impl(parser, &mut result, read_val, read_fork_extra)?;
         Ok(result)
     }
 
-    fn parse_impl<T, VA: DictValAdapter<T>>(
+    fn parse_impl<T, E: From<TLCoreError>>(
         &mut self,
         parser: &mut CellParser,
         dst: &mut HashMap<BigUint, T>,
-    ) -> Result<(), TLCoreError> {
+        read_val: &mut dyn FnMut(&mut CellParser) -> Result<T, E>,
+        read_fork_extra: &mut dyn FnMut(&mut CellParser) -> Result<(), E>,
+    ) -> Result<(), E> {
         // will rollback prefix to original value at the end of the function
         let origin_key_prefix_len = self.cur_key_prefix.bits();
 
-        let label_type = self.detect_label_type(parser)?;
-        match label_type {
-            DictLabelType::Same => {
-                let prefix_val = parser.read_bit()?;
-                let prefix_len_len = self.remain_suffix_bit_len();
-                let prefix_len = parser.read_num::<usize>(prefix_len_len)?;
-                if prefix_val {
-                    self.cur_key_prefix += 1u32;
-                    self.cur_key_prefix <<= prefix_len;
-                    self.cur_key_prefix -= 1u32;
-                } else {
-                    self.cur_key_prefix <<= prefix_len;
-                }
-            }
-            DictLabelType::Short => {
-                let prefix_len = UnaryLen::read(parser)?;
-                if *prefix_len != 0 {
-                    let val = parser.read_num::<BigUint>(*prefix_len)?;
-                    self.cur_key_prefix <<= *prefix_len;
-                    self.cur_key_prefix |= val;
-                }
-            }
-            DictLabelType::Long => {
-                let prefix_len_len = self.remain_suffix_bit_len();
-                let prefix_len: usize = parser.read_num(prefix_len_len)?;
-                if prefix_len_len != 0 {
-                    let val: BigUint = parser.read_num(prefix_len)?;
-                    self.cur_key_prefix <<= prefix_len;
-                    self.cur_key_prefix |= val;
-                }
-            }
-        }
+        let label_max_len = self.key_bits_len + 1 - self.cur_key_prefix.bits() as usize;
+        let label = DictLabelType::read(parser, label_max_len)?;
+        let label_len = label.bits() - 1;
+        self.cur_key_prefix <<= label_len;
+        self.cur_key_prefix |= remove_leading_bit(&label);
+
         if self.cur_key_prefix.bits() as usize == (self.key_bits_len + 1) {
             let mut key = BigUint::one() << self.key_bits_len;
             key ^= &self.cur_key_prefix;
-            dst.insert(key, VA::read(parser)?);
+            dst.insert(key, read_val(parser)?);
         } else {
-            let left_ref = parser.read_next_ref()?;
             self.cur_key_prefix <<= 1;
-            self.parse_impl::<T, VA>(&mut left_ref.parser(), dst)?;
+            self.parse_impl(&mut parser.read_next_ref_parser()?, dst, read_val, read_fork_extra)?;
 
-            let right_ref = parser.read_next_ref()?;
             self.cur_key_prefix += BigUint::one();
-            self.parse_impl::<T, VA>(&mut right_ref.parser(), dst)?;
+            self.parse_impl(&mut parser.read_next_ref_parser()?, dst, read_val, read_fork_extra)?;
+            read_fork_extra(parser)?;
         }
         self.cur_key_prefix >>= self.cur_key_prefix.bits() - origin_key_prefix_len;
         Ok(())
     }
-
-    fn detect_label_type(&self, parser: &mut CellParser) -> Result<DictLabelType, TLCoreError> {
-        let label = if parser.read_bit()? {
-            if parser.read_bit()? {
-                DictLabelType::Same
-            } else {
-                DictLabelType::Long
-            }
-        } else {
-            DictLabelType::Short
-        };
-        Ok(label)
-    }
-
-    fn remain_suffix_bit_len(&self) -> usize {
-        // add 2 because cur_prefix contains leading bit
-        let prefix_len_left = self.key_bits_len - self.cur_key_prefix.bits() as usize + 2;
-        (prefix_len_left as f32).log2().ceil() as usize
-    }
 }
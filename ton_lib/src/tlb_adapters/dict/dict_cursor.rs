@@ -0,0 +1,478 @@
+use crate::tlb_adapters::dict::label_type::DictLabelType;
+use crate::tlb_adapters::dict::leading_bit_utils::{
+    add_leading_bit, common_prefix_len_var, remove_leading_bit, split_at,
+};
+use crate::tlb_adapters::{DictKeyAdapter, DictValAdapter};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::marker::PhantomData;
+use ton_lib_core::cell::{CellBuilder, CellParser, TonCell, TonCellRef};
+use ton_lib_core::error::TLCoreError;
+
+/// Lookups in dict (Hashmap) without parsing it fully, like TVM DICTGET / DICTGETNEXT / DICTGETPREV / DICTMIN / DICTMAX do
+/// Only branches which may contain requested keys are visited
+///
+/// `set`, `replace` and `delete` rebuild only the path from root to the changed leaf, untouched subtrees are shared.
+/// The result is the same as full dict rebuild
+/// Usage: `DictCursor::<DictKeyAdapterInto, DictValAdapterTLB, u32, TonCellRef>::read_e(&mut parser, 32)?.get(&key)?`
+pub struct DictCursor<KA: DictKeyAdapter<K>, VA: DictValAdapter<V>, K, V> {
+    root: Option<TonCellRef>,
+    key_bits_len: usize,
+    _phantom: PhantomData<(KA, VA, K, V)>,
+}
+
+impl<KA, VA, K, V> DictCursor<KA, VA, K, V>
+where
+    KA: DictKeyAdapter<K>,
+    VA: DictValAdapter<V>,
+{
+    /// root: Hashmap root cell, None for empty dict
+    pub fn new(root: Option<TonCellRef>, key_bits_len: u32) -> Self {
+        Self {
+            root,
+            key_bits_len: key_bits_len as usize,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Reads HashmapE: present marker (0|1 bit) and root ref
+    pub fn read_e(parser: &mut CellParser, key_bits_len: u32) -> Result<Self, TLCoreError> {
+        let root = match parser.read_bit()? {
+            true => Some(parser.read_next_ref()?.clone()),
+            false => None,
+        };
+        Ok(Self::new(root, key_bits_len))
+    }
+
+    pub fn root(&self) -> Option<&TonCellRef> { self.root.as_ref() }
+
+    /// Writes HashmapE: present marker (0|1 bit) and root ref
+    pub fn write_e(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+        builder.write_bit(self.root.is_some())?;
+        match &self.root {
+            Some(root) => builder.write_ref(root.clone()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Result<Option<V>, TLCoreError> {
+        let dict_key = KA::make_key(key)?;
+        Ok(self.walk(&dict_key, &dict_key, false, 1)?.pop().map(|(_, val)| val))
+    }
+
+    /// Entry with the smallest key which is greater than `key`
+    pub fn next(&self, key: &K) -> Result<Option<(K, V)>, TLCoreError> {
+        let from = KA::make_key(key)? + 1u32;
+        Ok(self.walk(&from, &self.max_dict_key(), false, 1)?.pop())
+    }
+
+    /// Entry with the greatest key which is less than `key`
+    pub fn prev(&self, key: &K) -> Result<Option<(K, V)>, TLCoreError> {
+        let to = KA::make_key(key)?;
+        if to.is_zero() {
+            return Ok(None);
+        }
+        Ok(self.walk(&BigUint::zero(), &(to - 1u32), true, 1)?.pop())
+    }
+
+    pub fn min(&self) -> Result<Option<(K, V)>, TLCoreError> {
+        Ok(self.walk(&BigUint::zero(), &self.max_dict_key(), false, 1)?.pop())
+    }
+
+    pub fn max(&self) -> Result<Option<(K, V)>, TLCoreError> {
+        Ok(self.walk(&BigUint::zero(), &self.max_dict_key(), true, 1)?.pop())
+    }
+
+    /// Entries with keys in [from, to], ordered by dict keys
+    ///
+    /// Entries are found one by one (like repeated DICTGETNEXT), so dict is never materialized.
+    /// Iteration stops after the first error
+    pub fn iter_range(
+        &self,
+        from: &K,
+        to: &K,
+    ) -> Result<impl Iterator<Item = Result<(K, V), TLCoreError>> + '_, TLCoreError> {
+        let mut from = Some(KA::make_key(from)?);
+        let to = KA::make_key(to)?;
+        Ok(std::iter::from_fn(move || {
+            let (key, value) = match self.walk_raw(&from.take()?, &to, false, 1) {
+                Ok(mut found) => found.pop()?,
+                Err(err) => return Some(Err(err)),
+            };
+            from = Some(&key + 1u32);
+            Some(KA::extract_key(&key).map(|key| (key, value)).map_err(Into::into))
+        }))
+    }
+
+    /// Inserts or replaces the value, returns the previous one (like DICTSETGET)
+    pub fn set(&mut self, key: &K, value: &V) -> Result<Option<V>, TLCoreError> {
+        let dict_key = self.make_dict_key(key)?;
+        let Some(root) = &self.root else {
+            self.root = Some(build_leaf::<VA, V>(&dict_key, self.key_bits_len, value)?);
+            return Ok(None);
+        };
+        match set_impl::<VA, V>(root, &dict_key, self.key_bits_len, value, false)? {
+            Some((new_root, prev_value)) => {
+                self.root = Some(new_root);
+                Ok(prev_value)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Replaces the value only if the key exists, returns the previous one (like DICTREPLACEGET)
+    pub fn replace(&mut self, key: &K, value: &V) -> Result<Option<V>, TLCoreError> {
+        let dict_key = self.make_dict_key(key)?;
+        let Some(root) = &self.root else {
+            return Ok(None);
+        };
+        match set_impl::<VA, V>(root, &dict_key, self.key_bits_len, value, true)? {
+            Some((new_root, prev_value)) => {
+                self.root = Some(new_root);
+                Ok(prev_value)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the deleted value (like DICTDELGET)
+    pub fn delete(&mut self, key: &K) -> Result<Option<V>, TLCoreError> {
+        let dict_key = self.make_dict_key(key)?;
+        let Some(root) = &self.root else {
+            return Ok(None);
+        };
+        match delete_impl::<VA, V>(root, &dict_key, self.key_bits_len)? {
+            Some((new_root, value)) => {
+                self.root = new_root;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // with leading bit
+    fn make_dict_key(&self, key: &K) -> Result<BigUint, TLCoreError> {
+        let dict_key = KA::make_key(key)?;
+        if dict_key.bits() as usize > self.key_bits_len {
+            let err_str = format!("dict key too long: expected max {} bits, key={dict_key}", self.key_bits_len);
+            return Err(TLCoreError::TLBWrongData(err_str));
+        }
+        Ok(add_leading_bit(&dict_key, self.key_bits_len))
+    }
+
+    fn max_dict_key(&self) -> BigUint { (BigUint::one() << self.key_bits_len) - 1u32 }
+
+    // collects up to `limit` entries with keys in [from, to]
+    fn walk(&self, from: &BigUint, to: &BigUint, reverse: bool, limit: usize) -> Result<Vec<(K, V)>, TLCoreError> {
+        let found = self.walk_raw(from, to, reverse, limit)?;
+        found.into_iter().map(|(k, v)| Ok((KA::extract_key(&k)?, v))).collect()
+    }
+
+    // same as walk, but returns dict keys
+    fn walk_raw(
+        &self,
+        from: &BigUint,
+        to: &BigUint,
+        reverse: bool,
+        limit: usize,
+    ) -> Result<Vec<(BigUint, V)>, TLCoreError> {
+        let mut found = vec![];
+        if let Some(root) = &self.root {
+            let mut walker = DictWalker::<VA, V> {
+                from,
+                to,
+                reverse,
+                limit,
+                found: &mut found,
+                _phantom: PhantomData,
+            };
+            walker.walk(root, BigUint::zero(), self.key_bits_len)?;
+        }
+        Ok(found)
+    }
+}
+
+struct DictWalker<'a, VA: DictValAdapter<V>, V> {
+    from: &'a BigUint,
+    to: &'a BigUint,
+    reverse: bool,
+    limit: usize,
+    found: &'a mut Vec<(BigUint, V)>,
+    _phantom: PhantomData<VA>,
+}
+
+impl<VA: DictValAdapter<V>, V> DictWalker<'_, VA, V> {
+    fn walk(&mut self, cell: &TonCell, prefix: BigUint, bits_left: usize) -> Result<(), TLCoreError> {
+        let mut parser = cell.parser();
+        let label = DictLabelType::read(&mut parser, bits_left)?;
+        let label_len = label.bits() as usize - 1;
+        let prefix = (prefix << label_len) | remove_leading_bit(&label);
+        let bits_left = bits_left - label_len;
+
+        // all keys of the subtree are in [min_key, max_key]
+        let min_key = &prefix << bits_left;
+        let max_key = &min_key + ((BigUint::one() << bits_left) - 1u32);
+        if &max_key < self.from || &min_key > self.to {
+            return Ok(());
+        }
+        if bits_left == 0 {
+            self.found.push((prefix, VA::read(&mut parser)?));
+            return Ok(());
+        }
+        if cell.refs.len() < 2 {
+            let err_str = format!("dict fork must have 2 refs, got {}", cell.refs.len());
+            return Err(TLCoreError::TLBWrongData(err_str));
+        }
+        let branches = if self.reverse { [1, 0] } else { [0, 1] };
+        for branch in branches {
+            if self.found.len() >= self.limit {
+                break;
+            }
+            self.walk(&cell.refs[branch], (&prefix << 1) + branch as u32, bits_left - 1)?;
+        }
+        Ok(())
+    }
+}
+
+// key contains leading bit. Returns None if nothing is changed (replace_only & key is not found)
+fn set_impl<VA: DictValAdapter<V>, V>(
+    cell: &TonCell,
+    key: &BigUint,
+    bits_left: usize,
+    value: &V,
+    replace_only: bool,
+) -> Result<Option<(TonCellRef, Option<V>)>, TLCoreError> {
+    let mut parser = cell.parser();
+    let label = DictLabelType::read(&mut parser, bits_left)?;
+    let label_len = label.bits() as usize - 1;
+    let prefix_len = common_prefix_len_var(&label, key);
+    if prefix_len < label_len {
+        if replace_only {
+            return Ok(None);
+        }
+        // split the edge: new fork holds the current node and the new leaf
+        let (fork_label, cur_node_is_right, cur_node_label) = split_at(&label, prefix_len);
+        let (_, _, leaf_label) = split_at(key, prefix_len);
+        let child_bits_left = bits_left - prefix_len - 1;
+        let cur_node = rebuild_node(cell, &mut parser, &cur_node_label, child_bits_left, None)?;
+        let leaf = build_leaf::<VA, V>(&leaf_label, child_bits_left, value)?;
+        let (left, right) = if cur_node_is_right {
+            (leaf, cur_node)
+        } else {
+            (cur_node, leaf)
+        };
+        let mut builder = TonCell::builder();
+        DictLabelType::write(&mut builder, &fork_label, bits_left)?;
+        builder.write_ref(left)?;
+        builder.write_ref(right)?;
+        return Ok(Some((builder.build_ref()?, None)));
+    }
+    if label_len == bits_left {
+        let prev_value = VA::read(&mut parser)?;
+        return Ok(Some((build_leaf::<VA, V>(&label, bits_left, value)?, Some(prev_value))));
+    }
+    let (_, is_right, child_key) = split_at(key, label_len);
+    let child = get_fork_ref(cell, is_right)?;
+    let Some((new_child, prev_value)) =
+        set_impl::<VA, V>(child, &child_key, bits_left - label_len - 1, value, replace_only)?
+    else {
+        return Ok(None);
+    };
+    let new_cell = rebuild_node(cell, &mut parser, &label, bits_left, Some((is_right as usize, new_child)))?;
+    Ok(Some((new_cell, prev_value)))
+}
+
+// key contains leading bit. Returns None if key is not found, (None, value) if the whole subtree is deleted
+#[allow(clippy::type_complexity)]
+fn delete_impl<VA: DictValAdapter<V>, V>(
+    cell: &TonCell,
+    key: &BigUint,
+    bits_left: usize,
+) -> Result<Option<(Option<TonCellRef>, V)>, TLCoreError> {
+    let mut parser = cell.parser();
+    let label = DictLabelType::read(&mut parser, bits_left)?;
+    let label_len = label.bits() as usize - 1;
+    if common_prefix_len_var(&label, key) < label_len {
+        return Ok(None);
+    }
+    if label_len == bits_left {
+        return Ok(Some((None, VA::read(&mut parser)?)));
+    }
+    let (_, is_right, child_key) = split_at(key, label_len);
+    let child_bits_left = bits_left - label_len - 1;
+    let (new_node, value) = match delete_impl::<VA, V>(get_fork_ref(cell, is_right)?, &child_key, child_bits_left)? {
+        None => return Ok(None),
+        Some((Some(new_child), value)) => {
+            let new_cell = rebuild_node(cell, &mut parser, &label, bits_left, Some((is_right as usize, new_child)))?;
+            (new_cell, value)
+        }
+        Some((None, value)) => {
+            // fork has only one branch left, so it's merged with the sibling: label + sibling_bit + sibling_label
+            let sibling = get_fork_ref(cell, !is_right)?;
+            let mut sibling_parser = sibling.parser();
+            let sibling_label = DictLabelType::read(&mut sibling_parser, child_bits_left)?;
+            let sibling_label_len = sibling_label.bits() as usize - 1;
+            let merged_label =
+                (((label << 1) + !is_right as u32) << sibling_label_len) | remove_leading_bit(&sibling_label);
+            (rebuild_node(sibling, &mut sibling_parser, &merged_label, bits_left, None)?, value)
+        }
+    };
+    Ok(Some((Some(new_node), value)))
+}
+
+fn get_fork_ref(cell: &TonCell, is_right: bool) -> Result<&TonCellRef, TLCoreError> {
+    match cell.refs.get(is_right as usize) {
+        Some(child) => Ok(child),
+        None => Err(TLCoreError::TLBWrongData(format!("dict fork must have 2 refs, got {}", cell.refs.len()))),
+    }
+}
+
+fn build_leaf<VA: DictValAdapter<V>, V>(
+    label: &BigUint,
+    bits_left: usize,
+    value: &V,
+) -> Result<TonCellRef, TLCoreError> {
+    let mut builder = TonCell::builder();
+    DictLabelType::write(&mut builder, label, bits_left)?;
+    VA::write(&mut builder, value)?;
+    builder.build_ref()
+}
+
+// writes new label and copies the rest of node (parser must be positioned right after the old label)
+fn rebuild_node(
+    cell: &TonCell,
+    parser: &mut CellParser,
+    label: &BigUint,
+    bits_left: usize,
+    new_ref: Option<(usize, TonCellRef)>,
+) -> Result<TonCellRef, TLCoreError> {
+    let mut builder = TonCell::builder();
+    DictLabelType::write(&mut builder, label, bits_left)?;
+    let data_bits_left = parser.data_bits_remaining()?;
+    builder.write_bits(parser.read_bits(data_bits_left)?, data_bits_left)?;
+    for (pos, cell_ref) in cell.refs.iter().enumerate() {
+        match &new_ref {
+            Some((new_pos, new_cell_ref)) if *new_pos == pos => builder.write_ref(new_cell_ref.clone())?,
+            _ => builder.write_ref(cell_ref.clone())?,
+        }
+    }
+    builder.build_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb_adapters::{DictKeyAdapterInto, DictValAdapterNum, TLBBTreeMapE};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    type TestCursor = DictCursor<DictKeyAdapterInto, DictValAdapterNum<16>, u16, u16>;
+
+    #[test]
+    fn test_dict_cursor() -> anyhow::Result<()> {
+        let data = BTreeMap::from_iter((0..100u16).map(|i| (i * i % 1000, i)));
+        let mut builder = TonCell::builder();
+        TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterNum<16>, _, _>::new(16).write(&mut builder, &data)?;
+        let cell = builder.build()?;
+        let cursor = TestCursor::read_e(&mut cell.parser(), 16)?;
+
+        assert_eq!(cursor.min()?, data.first_key_value().map(|(k, v)| (*k, *v)));
+        assert_eq!(cursor.max()?, data.last_key_value().map(|(k, v)| (*k, *v)));
+        for key in 0..1010u16 {
+            assert_eq!(cursor.get(&key)?, data.get(&key).copied(), "key={key}");
+            let expected_next = data.range(key + 1..).next().map(|(k, v)| (*k, *v));
+            assert_eq!(cursor.next(&key)?, expected_next, "key={key}");
+            let expected_prev = data.range(..key).next_back().map(|(k, v)| (*k, *v));
+            assert_eq!(cursor.prev(&key)?, expected_prev, "key={key}");
+        }
+        let expected_range = data.range(100..=500).map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+        assert_eq!(cursor.iter_range(&100, &500)?.collect::<Result<Vec<_>, _>>()?, expected_range);
+        assert_eq!(cursor.iter_range(&0, &u16::MAX)?.count(), data.len());
+        assert_eq!(cursor.iter_range(&501, &500)?.count(), 0);
+        assert_eq!(cursor.next(&u16::MAX)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_cursor_empty() -> anyhow::Result<()> {
+        let cursor = TestCursor::new(None, 16);
+        assert_eq!(cursor.get(&1)?, None);
+        assert_eq!(cursor.min()?, None);
+        assert_eq!(cursor.prev(&0)?, None);
+        assert!(cursor.iter_range(&0, &10)?.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_cursor_skips_branches() -> anyhow::Result<()> {
+        // right branch is broken, but it's never visited for keys from the left one
+        let data = BTreeMap::from([(1u16, 10u16), (2, 20), (0x8000, 30)]);
+        let mut builder = TonCell::builder();
+        TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterNum<16>, _, _>::new(16).write(&mut builder, &data)?;
+        let root = builder.build()?.refs[0].clone();
+        let mut broken_root = TonCell::builder();
+        broken_root.write_bits(&root.data, root.data_bits_len)?;
+        broken_root.write_ref(root.refs[0].clone())?;
+        broken_root.write_ref(TonCell::EMPTY.into_ref())?;
+        let cursor = TestCursor::new(Some(broken_root.build_ref()?), 16);
+
+        assert_eq!(cursor.get(&2)?, Some(20));
+        assert_eq!(cursor.min()?, Some((1, 10)));
+        assert_eq!(cursor.next(&1)?, Some((2, 20)));
+        assert!(cursor.next(&2).is_err());
+
+        // entries are yielded before the broken branch is reached
+        let mut iter = cursor.iter_range(&0, &u16::MAX)?;
+        assert_eq!(iter.next().transpose()?, Some((1, 10)));
+        assert_eq!(iter.next().transpose()?, Some((2, 20)));
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_cursor_mutation() -> anyhow::Result<()> {
+        let mut data = BTreeMap::new();
+        let mut cursor = TestCursor::new(None, 16);
+        let mut rnd = 7u32;
+        for step in 0..600u32 {
+            rnd = rnd.wrapping_mul(1103515245).wrapping_add(12345);
+            let key = ((rnd >> 8) % 64) as u16 * 997;
+            let value = step as u16;
+            match rnd % 3 {
+                0 => assert_eq!(cursor.set(&key, &value)?, data.insert(key, value)),
+                1 => assert_eq!(cursor.delete(&key)?, data.remove(&key)),
+                _ => {
+                    let expected = data.get_mut(&key).map(|x| std::mem::replace(x, value));
+                    assert_eq!(cursor.replace(&key, &value)?, expected);
+                }
+            }
+            let mut builder = TonCell::builder();
+            TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterNum<16>, _, _>::new(16).write(&mut builder, &data)?;
+            let mut cursor_builder = TonCell::builder();
+            cursor.write_e(&mut cursor_builder)?;
+            assert_eq!(cursor_builder.build()?, builder.build()?, "step={step}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_cursor_mutation_shares_subtrees() -> anyhow::Result<()> {
+        let data = BTreeMap::from_iter((0..16u16).map(|i| (i, i)));
+        let mut builder = TonCell::builder();
+        TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterNum<16>, _, _>::new(16).write(&mut builder, &data)?;
+        let mut cursor = TestCursor::read_e(&mut builder.build()?.parser(), 16)?;
+        let root = cursor.root().unwrap().clone();
+
+        assert_eq!(cursor.set(&3, &100)?, Some(3));
+        let new_root = cursor.root().unwrap().clone();
+        assert_ne!(new_root, root);
+        assert!(Arc::ptr_eq(&new_root.refs[1].0, &root.refs[1].0));
+        assert!(Arc::ptr_eq(&new_root.refs[0].refs[1].0, &root.refs[0].refs[1].0));
+        assert_eq!(cursor.get(&3)?, Some(100));
+
+        assert_eq!(cursor.delete(&15)?, Some(15));
+        assert!(Arc::ptr_eq(&cursor.root().unwrap().refs[0].0, &new_root.refs[0].0));
+        Ok(())
+    }
+}
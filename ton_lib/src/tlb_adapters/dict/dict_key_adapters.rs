@@ -1,5 +1,6 @@
 use crate::bail_tl;
 use crate::error::TLError;
+use crate::tlb_adapters::dict::leading_bit_utils::{add_leading_bit, remove_leading_bit};
 use num_bigint::BigUint;
 use ton_lib_core::cell::TonCell;
 use ton_lib_core::cell::TonHash;
@@ -17,6 +18,34 @@ pub struct DictKeyAdapterInto;
 pub struct DictKeyAdapterAddress;
 pub struct DictKeyAdapterString; // TODO is not covered by tests
 
+/// Key of variable length, used by PfxHashMap & VarHashMap
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DictVarKey {
+    pub value: BigUint,
+    pub bits_len: usize,
+}
+
+impl DictVarKey {
+    pub fn new<T: Into<BigUint>>(value: T, bits_len: usize) -> Self {
+        Self {
+            value: value.into(),
+            bits_len,
+        }
+    }
+
+    // dict internals keep keys with 1 extra leading bit set to 1
+    pub(super) fn from_leading_bit(key: &BigUint) -> Self {
+        Self::new(remove_leading_bit(key), key.bits() as usize - 1)
+    }
+
+    pub(super) fn to_leading_bit(&self) -> Result<BigUint, TLError> {
+        if self.value.bits() as usize > self.bits_len {
+            bail_tl!("dict key value {} doesn't fit into {} bits", self.value, self.bits_len);
+        }
+        Ok(add_leading_bit(&self.value, self.bits_len))
+    }
+}
+
 impl DictKeyAdapter<TonHash> for DictKeyAdapterTonHash {
     fn make_key(src_key: &TonHash) -> Result<BigUint, TLError> { Ok(BigUint::from_bytes_be(src_key.as_slice())) }
 
@@ -1,6 +1,117 @@
+use super::leading_bit_utils::{add_leading_bit, all_bits_same, remove_leading_bit};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use ton_lib_core::cell::{CellBuilder, CellParser};
+use ton_lib_core::error::TLCoreError;
+use ton_lib_core::traits::tlb::TLB;
+use ton_lib_core::types::tlb_core::UnaryLen;
+
 #[derive(Debug)]
 pub enum DictLabelType {
     Short, // high bit is 0
     Long,  // high bits are 10
     Same,  // high bits are 11
 }
+
+// hml_short$0 {m:#} {n:#} len:(Unary ~n) {n <= m} s:(n * Bit) = HmLabel ~n m;
+// hml_long$10 {m:#} n:(#<= m) s:(n * Bit) = HmLabel ~n m;
+// hml_same$11 {m:#} v:Bit n:(#<= m) = HmLabel ~n m;
+/// Label is passed with 1 extra leading bit set to 1 to keep its length
+impl DictLabelType {
+    pub fn read(parser: &mut CellParser, max_len: usize) -> Result<BigUint, TLCoreError> {
+        let label = match Self::read_type(parser)? {
+            DictLabelType::Same => {
+                let bit_val = parser.read_bit()?;
+                let label_len = parser.read_num::<usize>(label_len_len(max_len))?;
+                match bit_val {
+                    true => (BigUint::one() << (label_len + 1)) - 1u32,
+                    false => BigUint::one() << label_len,
+                }
+            }
+            DictLabelType::Short => {
+                let label_len = *UnaryLen::read(parser)?;
+                read_label_bits(parser, label_len)?
+            }
+            DictLabelType::Long => {
+                let label_len = parser.read_num::<usize>(label_len_len(max_len))?;
+                read_label_bits(parser, label_len)?
+            }
+        };
+        let label_len = label.bits() as usize - 1;
+        if label_len > max_len {
+            let err_str = format!("dict label is too long: max_len={max_len}, got {label_len}");
+            return Err(TLCoreError::TLBWrongData(err_str));
+        }
+        Ok(label)
+    }
+
+    /// Chooses the shortest label representation
+    pub fn write(builder: &mut CellBuilder, label: &BigUint, max_len: usize) -> Result<(), TLCoreError> {
+        assert!(label.bits() > 0);
+        if label.is_one() {
+            // it's leading bit => label_type == short, len == 0 => store [false, false]
+            builder.write_num(&0, 2)?;
+            return Ok(());
+        }
+        let all_bits_same = all_bits_same(label);
+
+        let label_len = label.bits() as usize - 1;
+        let label_len_len = label_len_len(max_len);
+        let fair_label = remove_leading_bit(label);
+        let same_label_len = if all_bits_same { 3 + label_len_len } else { usize::MAX };
+        let short_label_len = 2 + label_len * 2;
+        let long_label_len = 2 + label_len_len + label_len;
+
+        let mut label_type = DictLabelType::Short;
+        if long_label_len < short_label_len {
+            label_type = DictLabelType::Long;
+        }
+        if same_label_len < short_label_len {
+            label_type = DictLabelType::Same;
+        }
+        match label_type {
+            DictLabelType::Same => {
+                builder.write_bit(true)?;
+                builder.write_bit(true)?;
+                builder.write_bit(!fair_label.is_zero())?;
+                builder.write_num(&label_len, label_len_len)?;
+            }
+            DictLabelType::Short => {
+                builder.write_bit(false)?;
+                let unary_len = UnaryLen(label_len);
+                unary_len.write(builder)?;
+                builder.write_num(&fair_label, label_len)?;
+            }
+            DictLabelType::Long => {
+                builder.write_bit(true)?;
+                builder.write_bit(false)?;
+                builder.write_num(&label_len, label_len_len)?;
+                builder.write_num(&fair_label, label_len)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_type(parser: &mut CellParser) -> Result<DictLabelType, TLCoreError> {
+        let label = if parser.read_bit()? {
+            if parser.read_bit()? {
+                DictLabelType::Same
+            } else {
+                DictLabelType::Long
+            }
+        } else {
+            DictLabelType::Short
+        };
+        Ok(label)
+    }
+}
+
+fn read_label_bits(parser: &mut CellParser, label_len: usize) -> Result<BigUint, TLCoreError> {
+    if label_len == 0 {
+        return Ok(BigUint::one());
+    }
+    Ok(add_leading_bit(&parser.read_num::<BigUint>(label_len)?, label_len))
+}
+
+// bits len of (#<= max_len)
+fn label_len_len(max_len: usize) -> usize { (max_len as f32 + 1.0).log2().ceil() as usize }
@@ -17,6 +17,25 @@ pub fn common_prefix_len(a: &BigUint, b: &BigUint) -> usize {
     (a.bits() - xor.bits() - 1) as usize // don't forget leading zero
 }
 
+/// Same as `common_prefix_len`, but `a` and `b` may have different length
+pub fn common_prefix_len_var(a: &BigUint, b: &BigUint) -> usize {
+    let (a_bits, b_bits) = (a.bits(), b.bits());
+    if a_bits > b_bits {
+        common_prefix_len(&(a >> (a_bits - b_bits)), b)
+    } else {
+        common_prefix_len(a, &(b >> (b_bits - a_bits)))
+    }
+}
+
+/// Splits `val` after `prefix_len` bits: returns (label, next bit, suffix)
+pub fn split_at(val: &BigUint, prefix_len: usize) -> (BigUint, bool, BigUint) {
+    let suffix_len = val.bits() as usize - 1 - prefix_len - 1;
+    let label = val >> (suffix_len + 1);
+    let next_bit = val.bit(suffix_len as u64);
+    let suffix = add_leading_bit(&(val & ((BigUint::one() << suffix_len) - 1u32)), suffix_len);
+    (label, next_bit, suffix)
+}
+
 pub fn remove_leading_bit(val: &BigUint) -> BigUint {
     let bits = val.bits();
     let mask = BigUint::one() << (bits - 1);
@@ -53,6 +72,31 @@ mod tests {
         assert_eq!(common_prefix_len(&a, &b), 3);
     }
 
+    #[test]
+    fn test_common_prefix_len_var() {
+        let a = BigUint::from(0b1011u32);
+        let b = BigUint::from(0b101u32);
+        assert_eq!(common_prefix_len_var(&a, &b), 2);
+        assert_eq!(common_prefix_len_var(&b, &a), 2);
+
+        let b = BigUint::from(0b11u32);
+        assert_eq!(common_prefix_len_var(&a, &b), 0);
+    }
+
+    #[test]
+    fn test_split_at() {
+        let val = BigUint::from(0b101101u32);
+        let (label, next_bit, suffix) = split_at(&val, 2);
+        assert_eq!(label, BigUint::from(0b101u32));
+        assert!(next_bit);
+        assert_eq!(suffix, BigUint::from(0b101u32));
+
+        let (label, next_bit, suffix) = split_at(&val, 4);
+        assert_eq!(label, BigUint::from(0b10110u32));
+        assert!(next_bit);
+        assert_eq!(suffix, BigUint::one());
+    }
+
     #[test]
     fn test_remove_leading_bit() {
         let val = BigUint::from(0b1011u32);
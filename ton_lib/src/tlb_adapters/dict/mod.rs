@@ -1,13 +1,26 @@
 mod data_builder;
 mod data_parser;
+mod dict_cursor;
 mod dict_key_adapters;
 mod dict_val_adapters;
 mod label_type;
 mod leading_bit_utils;
+mod tlb_btree_map;
 mod tlb_hash_map;
+mod tlb_hash_map_aug;
+mod tlb_hash_map_aug_e;
 mod tlb_hash_map_e;
+mod tlb_pfx_hash_map;
+mod tlb_var_hash_map;
 
+pub(crate) use data_parser::DictDataParser;
+pub use dict_cursor::*;
 pub use dict_key_adapters::*;
 pub use dict_val_adapters::*;
+pub use tlb_btree_map::*;
 pub use tlb_hash_map::*;
+pub use tlb_hash_map_aug::*;
+pub use tlb_hash_map_aug_e::*;
 pub use tlb_hash_map_e::*;
+pub use tlb_pfx_hash_map::*;
+pub use tlb_var_hash_map::*;
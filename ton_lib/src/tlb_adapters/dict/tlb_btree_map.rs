@@ -0,0 +1,81 @@
+use crate::tlb_adapters::{DictKeyAdapter, DictValAdapter, TLBHashMap};
+use std::collections::BTreeMap;
+use ton_lib_core::cell::CellBuilder;
+use ton_lib_core::cell::CellParser;
+use ton_lib_core::cell::TonCell;
+use ton_lib_core::error::TLCoreError;
+
+/// Same as TLBHashMap, but keeps keys ordered in BTreeMap
+/// Usage example: `#[tlb_derive(adapter = "TLBBTreeMap::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32)")]`
+pub struct TLBBTreeMap<KA: DictKeyAdapter<K>, VA: DictValAdapter<V>, K, V>(TLBHashMap<KA, VA, K, V>);
+
+impl<KA, VA, K, V> TLBBTreeMap<KA, VA, K, V>
+where
+    KA: DictKeyAdapter<K>,
+    VA: DictValAdapter<V>,
+    K: Ord,
+{
+    pub fn new(key_bits_len: u32) -> Self { Self(TLBHashMap::new(key_bits_len)) }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<BTreeMap<K, V>, TLCoreError> { self.0.read_pairs(parser) }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &BTreeMap<K, V>) -> Result<(), TLCoreError> {
+        self.0.write_pairs(builder, data.iter())
+    }
+}
+
+/// Same as TLBHashMapE, but keeps keys ordered in BTreeMap
+/// Usage: `#[tlb_derive(adapter = "TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterTLB, _, _>::new(32)")]`
+pub struct TLBBTreeMapE<KA: DictKeyAdapter<K>, VA: DictValAdapter<V>, K, V>(TLBBTreeMap<KA, VA, K, V>);
+
+impl<KA, VA, K, V> TLBBTreeMapE<KA, VA, K, V>
+where
+    KA: DictKeyAdapter<K>,
+    VA: DictValAdapter<V>,
+    K: Ord,
+{
+    pub fn new(key_bits_len: u32) -> Self { Self(TLBBTreeMap::new(key_bits_len)) }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<BTreeMap<K, V>, TLCoreError> {
+        if !parser.read_bit()? {
+            return Ok(BTreeMap::new());
+        }
+        self.0.read(&mut parser.read_next_ref_parser()?)
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &BTreeMap<K, V>) -> Result<(), TLCoreError> {
+        if data.is_empty() {
+            return builder.write_bit(false);
+        }
+        builder.write_bit(true)?;
+        let mut dict_data_builder = TonCell::builder();
+        self.0.write(&mut dict_data_builder, data)?;
+        builder.write_ref(dict_data_builder.build()?.into_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb_adapters::{DictKeyAdapterInto, DictValAdapterNum, TLBHashMapE};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_btree_dict() -> anyhow::Result<()> {
+        let data = BTreeMap::from([(200u32, 1u8), (3, 2), (1000, 3), (0, 4)]);
+        let mut builder = TonCell::builder();
+        TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterNum<8>, _, _>::new(32).write(&mut builder, &data)?;
+        let cell = builder.build()?;
+        let parsed =
+            TLBBTreeMapE::<DictKeyAdapterInto, DictValAdapterNum<8>, _, _>::new(32).read(&mut cell.parser())?;
+        assert_eq!(parsed.keys().copied().collect::<Vec<_>>(), vec![0, 3, 200, 1000]);
+        assert_eq!(parsed, data);
+
+        // same cell as HashMap produces
+        let hash_map = data.clone().into_iter().collect::<HashMap<_, _>>();
+        let mut builder = TonCell::builder();
+        TLBHashMapE::<DictKeyAdapterInto, DictValAdapterNum<8>, _, _>::new(32).write(&mut builder, &hash_map)?;
+        assert_eq!(builder.build()?, cell);
+        Ok(())
+    }
+}
@@ -23,6 +23,19 @@ where
     KA: DictKeyAdapter<K>,
     VA: DictValAdapter<V>,
     K: Eq + Hash,
+{
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<K, V>, TLCoreError> { self.read_pairs(parser) }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<K, V>) -> Result<(), TLCoreError> {
+        self.write_pairs(builder, data.iter())
+    }
+}
+
+// shared with TLBBTreeMap
+impl<KA, VA, K, V> TLBHashMap<KA, VA, K, V>
+where
+    KA: DictKeyAdapter<K>,
+    VA: DictValAdapter<V>,
 {
     pub fn new(key_bits_len: u32) -> Self {
         Self {
@@ -31,22 +44,30 @@ where
         }
     }
 
-    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<K, V>, TLCoreError> {
+    pub(super) fn read_pairs<C: FromIterator<(K, V)>>(&self, parser: &mut CellParser) -> Result<C, TLCoreError> {
         let mut data_parser = DictDataParser::new(self.key_bits_len as usize);
         let data_raw = data_parser.read::<V, VA>(parser)?;
         let data = data_raw
             .into_iter()
             .map(|(k, v)| Ok::<_, TLCoreError>((KA::extract_key(&k)?, v)))
-            .collect::<Result<HashMap<K, V>, _>>()?;
+            .collect::<Result<C, _>>()?;
         Ok(data)
     }
 
-    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<K, V>) -> Result<(), TLCoreError> {
-        if data.is_empty() {
+    pub(super) fn write_pairs<'a>(
+        &self,
+        builder: &mut CellBuilder,
+        data: impl ExactSizeIterator<Item = (&'a K, &'a V)>,
+    ) -> Result<(), TLCoreError>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        if data.len() == 0 {
             return Err(TLCoreError::TLBWrongData("empty HashMap can't be written".to_string()));
         }
         let mut key_value_pairs =
-            data.iter().map(|(k, v)| Ok::<_, TLError>((KA::make_key(k)?, v))).collect::<Result<Vec<_>, _>>()?;
+            data.map(|(k, v)| Ok::<_, TLError>((KA::make_key(k)?, v))).collect::<Result<Vec<_>, _>>()?;
         key_value_pairs.sort_by_key(|(x, _)| x.clone());
 
         let mut keys_sorted = Vec::with_capacity(key_value_pairs.len());
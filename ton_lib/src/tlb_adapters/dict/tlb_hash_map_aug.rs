@@ -0,0 +1,164 @@
+use crate::error::TLError;
+use crate::tlb_adapters::dict::data_builder::DictDataBuilder;
+use crate::tlb_adapters::dict::data_parser::DictDataParser;
+use crate::tlb_adapters::{DictKeyAdapter, DictValAdapter};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use ton_lib_core::cell::CellBuilder;
+use ton_lib_core::cell::CellParser;
+use ton_lib_core::error::TLCoreError;
+
+// https://github.com/ton-blockchain/ton/blob/72056a2261cbb11f7cf0f20b389bcbffe018b1a8/crypto/block/block.tlb
+// ahm_edge#_ {n:#} {X:Type} {Y:Type} {l:#} {m:#} label:(HmLabel ~l n) {n = (~m) + l} node:(HashmapAugNode m X Y) = HashmapAug n X Y;
+/// Adapter to write augmented HashMap (HashmapAug) into a cell. Values are stored with their extra: `(value, extra)`
+/// Fork extras are not kept: on reading they are checked to be equal to `merge` of subtrees extras,
+/// on writing they are recomputed by `merge`
+/// Doesn't write 'present' marker to root cell. Generally, is not supposed to be used in TLB structs
+/// Usage example: `#[tlb_derive(adapter = "TLBHashMapAug::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterTLB, _, _, _>::new(256, Extra::merge)")]`
+pub struct TLBHashMapAug<KA: DictKeyAdapter<K>, VA: DictValAdapter<V>, EA: DictValAdapter<E>, K, V, E> {
+    key_bits_len: u32,
+    merge: fn(&E, &E) -> Result<E, TLCoreError>,
+    _phantom: PhantomData<(KA, VA, EA, K, V)>,
+}
+
+pub(super) type AugDictWithRootExtra<K, V, E> = (HashMap<K, (V, E)>, E);
+
+impl<KA, VA, EA, K, V, E> TLBHashMapAug<KA, VA, EA, K, V, E>
+where
+    KA: DictKeyAdapter<K>,
+    VA: DictValAdapter<V>,
+    EA: DictValAdapter<E>,
+    K: Eq + Hash,
+{
+    pub fn new(key_bits_len: u32, merge: fn(&E, &E) -> Result<E, TLCoreError>) -> Self {
+        Self {
+            key_bits_len,
+            merge,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<K, (V, E)>, TLCoreError>
+    where
+        E: Clone + PartialEq + Debug,
+    {
+        let (data, _) = self.read_root(parser)?;
+        Ok(data)
+    }
+
+    /// Returns extra of the root node along with data
+    pub(super) fn read_root(&self, parser: &mut CellParser) -> Result<AugDictWithRootExtra<K, V, E>, TLCoreError>
+    where
+        E: Clone + PartialEq + Debug,
+    {
+        // nodes are visited in post-order, so extras of both subtrees are on top of the stack when fork is read
+        let extras = RefCell::new(vec![]);
+        let mut data_parser = DictDataParser::new(self.key_bits_len as usize);
+        let data_raw = data_parser.read_aug_with(
+            parser,
+            &mut |val_parser: &mut CellParser| {
+                let (value, extra) = DictValAdapterAug::<VA, EA>::read(val_parser)?;
+                extras.borrow_mut().push(extra.clone());
+                Ok((value, extra))
+            },
+            &mut |fork_parser: &mut CellParser| {
+                let fork_extra = EA::read(fork_parser)?;
+                let mut extras = extras.borrow_mut();
+                let (Some(right), Some(left)) = (extras.pop(), extras.pop()) else {
+                    return Err(TLCoreError::TLBWrongData("HashMapAug fork without subtrees".to_string()));
+                };
+                let merged = (self.merge)(&left, &right)?;
+                if fork_extra != merged {
+                    let err_msg =
+                        format!("HashMapAug fork extra {fork_extra:?} != merged extra of subtrees {merged:?}");
+                    return Err(TLCoreError::TLBWrongData(err_msg));
+                }
+                extras.push(fork_extra);
+                Ok(())
+            },
+        )?;
+        let Some(root_extra) = extras.into_inner().pop() else {
+            return Err(TLCoreError::TLBWrongData("HashMapAug root extra is not found".to_string()));
+        };
+        let data = data_raw
+            .into_iter()
+            .map(|(k, v)| Ok::<_, TLCoreError>((KA::extract_key(&k)?, v)))
+            .collect::<Result<HashMap<K, (V, E)>, _>>()?;
+        Ok((data, root_extra))
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<K, (V, E)>) -> Result<(), TLCoreError>
+    where
+        E: Clone,
+    {
+        self.write_root(builder, data)?;
+        Ok(())
+    }
+
+    /// Returns extra of the root node (merged extras of all values)
+    pub(super) fn write_root(&self, builder: &mut CellBuilder, data: &HashMap<K, (V, E)>) -> Result<E, TLCoreError>
+    where
+        E: Clone,
+    {
+        if data.is_empty() {
+            return Err(TLCoreError::TLBWrongData("empty HashMapAug can't be written".to_string()));
+        }
+        let mut key_value_pairs =
+            data.iter().map(|(k, v)| Ok::<_, TLError>((KA::make_key(k)?, v))).collect::<Result<Vec<_>, _>>()?;
+        key_value_pairs.sort_by_key(|(x, _)| x.clone());
+
+        let mut keys_sorted = Vec::with_capacity(key_value_pairs.len());
+        let mut values_sorted = Vec::with_capacity(key_value_pairs.len());
+        for (key, value) in key_value_pairs {
+            keys_sorted.push(key);
+            values_sorted.push(value);
+        }
+
+        // subtrees are written before their forks, so merged extras of left & right are always available
+        let mut fork_extras = HashMap::new();
+        let data_builder = DictDataBuilder::<_, DictValAdapterAug<VA, EA>>::new(
+            self.key_bits_len as usize,
+            keys_sorted,
+            &values_sorted,
+        )?
+        .with_fork_extra_writer(|fork_builder, left, right| {
+            let left_extra = fork_extras.remove(&left);
+            let right_extra = fork_extras.remove(&right);
+            let extra = (self.merge)(
+                left_extra.as_ref().unwrap_or(&values_sorted[left.start].1),
+                right_extra.as_ref().unwrap_or(&values_sorted[right.start].1),
+            )?;
+            EA::write(fork_builder, &extra)?;
+            fork_extras.insert(left.start..right.end, extra);
+            Ok(())
+        });
+        let dict_data_cell = data_builder.build()?;
+        builder.write_cell(&dict_data_cell)?;
+
+        let root_extra = match fork_extras.remove(&(0..values_sorted.len())) {
+            Some(extra) => extra,
+            None => values_sorted[0].1.clone(),
+        };
+        Ok(root_extra)
+    }
+}
+
+// ahmn_fork#_ is handled by DictDataBuilder::with_fork_extra_writer
+// ahmn_leaf#_ {X:Type} {Y:Type} extra:Y value:X = HashmapAugNode 0 X Y;
+struct DictValAdapterAug<VA, EA>(PhantomData<(VA, EA)>);
+
+impl<V, E, VA: DictValAdapter<V>, EA: DictValAdapter<E>> DictValAdapter<(V, E)> for DictValAdapterAug<VA, EA> {
+    fn write(builder: &mut CellBuilder, val: &(V, E)) -> Result<(), TLCoreError> {
+        EA::write(builder, &val.1)?;
+        VA::write(builder, &val.0)
+    }
+
+    fn read(parser: &mut CellParser) -> Result<(V, E), TLCoreError> {
+        let extra = EA::read(parser)?;
+        let value = VA::read(parser)?;
+        Ok((value, extra))
+    }
+}
@@ -0,0 +1,144 @@
+use crate::tlb_adapters::{DictKeyAdapter, DictValAdapter, TLBHashMapAug};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use ton_lib_core::cell::CellBuilder;
+use ton_lib_core::cell::CellParser;
+use ton_lib_core::cell::TonCell;
+use ton_lib_core::error::TLCoreError;
+
+// https://github.com/ton-blockchain/ton/blob/72056a2261cbb11f7cf0f20b389bcbffe018b1a8/crypto/block/block.tlb
+// ahme_empty$0 {n:#} {X:Type} {Y:Type} extra:Y = HashmapAugE n X Y;
+// ahme_root$1 {n:#} {X:Type} {Y:Type} root:^(HashmapAug n X Y) extra:Y = HashmapAugE n X Y;
+/// Write present marker (0|1 bit) and root extra to root cell, and then Dict data to first ref cell.
+/// Root extra of empty dict is `E::default()`. On reading, root extra is checked against extra of dict root node
+/// Usage: `#[tlb_derive(adapter = "TLBHashMapAugE::<DictKeyAdapterTonHash, DictValAdapterTLB, DictValAdapterTLB, _, _, _>::new(256, Extra::merge)")]`
+pub struct TLBHashMapAugE<KA: DictKeyAdapter<K>, VA: DictValAdapter<V>, EA: DictValAdapter<E>, K, V, E>(
+    TLBHashMapAug<KA, VA, EA, K, V, E>,
+);
+
+impl<KA, VA, EA, K, V, E> TLBHashMapAugE<KA, VA, EA, K, V, E>
+where
+    KA: DictKeyAdapter<K>,
+    VA: DictValAdapter<V>,
+    EA: DictValAdapter<E>,
+    K: Eq + Hash,
+    E: Clone + Default + PartialEq + Debug,
+{
+    pub fn new(key_bits_len: u32, merge: fn(&E, &E) -> Result<E, TLCoreError>) -> Self {
+        Self(TLBHashMapAug::new(key_bits_len, merge))
+    }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<K, (V, E)>, TLCoreError> {
+        let (data, expected_extra) = match parser.read_bit()? {
+            true => self.0.read_root(&mut parser.read_next_ref_parser()?)?,
+            false => (HashMap::new(), E::default()),
+        };
+        let root_extra = EA::read(parser)?;
+        if root_extra != expected_extra {
+            let err_msg = format!("HashMapAugE root extra {root_extra:?} != extra of dict root {expected_extra:?}");
+            return Err(TLCoreError::TLBWrongData(err_msg));
+        }
+        Ok(data)
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<K, (V, E)>) -> Result<(), TLCoreError> {
+        if data.is_empty() {
+            builder.write_bit(false)?;
+            return EA::write(builder, &E::default());
+        }
+        builder.write_bit(true)?;
+        let mut dict_data_builder = TonCell::builder();
+        let root_extra = self.0.write_root(&mut dict_data_builder, data)?;
+        builder.write_ref(dict_data_builder.build()?.into_ref())?;
+        EA::write(builder, &root_extra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb_adapters::{DictKeyAdapterInto, DictValAdapterNum};
+
+    type TestHashMapAugE =
+        TLBHashMapAugE<DictKeyAdapterInto, DictValAdapterNum<16>, DictValAdapterNum<32>, u8, u16, u32>;
+
+    fn sum(a: &u32, b: &u32) -> Result<u32, TLCoreError> { Ok(a + b) }
+
+    #[test]
+    fn test_dict_aug_e() -> anyhow::Result<()> {
+        let data = HashMap::from([(0u8, (10u16, 1u32)), (1, (11, 2)), (7, (12, 4)), (200, (13, 8))]);
+        let mut builder = TonCell::builder();
+        TestHashMapAugE::new(8, sum).write(&mut builder, &data)?;
+        let cell = builder.build()?;
+
+        let mut parser = cell.parser();
+        assert!(parser.read_bit()?);
+        assert_eq!(parser.read_num::<u32>(32)?, 15); // root extra
+
+        // root fork: (0, 1, 7) | (200), empty label: hml_short$0 with len = 0
+        let mut root_parser = cell.refs[0].parser();
+        assert_eq!(root_parser.read_num::<u8>(2)?, 0);
+        assert_eq!(root_parser.read_num::<u32>(32)?, 15);
+        // right subtree is a leaf: label, extra, value
+        let leaf_cell = &cell.refs[0].refs[1];
+        let mut leaf_parser = leaf_cell.parser();
+        leaf_parser.seek_bits(leaf_cell.data_bits_len as i32 - 48)?;
+        assert_eq!(leaf_parser.read_num::<u32>(32)?, 8);
+        assert_eq!(leaf_parser.read_num::<u16>(16)?, 13);
+
+        let parsed = TestHashMapAugE::new(8, sum).read(&mut cell.parser())?;
+        assert_eq!(parsed, data);
+
+        let mut builder = TonCell::builder();
+        TestHashMapAugE::new(8, sum).write(&mut builder, &HashMap::new())?;
+        let empty_cell = builder.build()?;
+        assert_eq!(empty_cell.data_bits_len, 33);
+        assert!(TestHashMapAugE::new(8, sum).read(&mut empty_cell.parser())?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_aug_e_wrong_extra() -> anyhow::Result<()> {
+        let data = HashMap::from([(0u8, (10u16, 1u32)), (200, (13, 8))]);
+        let mut builder = TonCell::builder();
+        TestHashMapAugE::new(8, sum).write(&mut builder, &data)?;
+        let cell = builder.build()?;
+
+        // root extra doesn't match root fork extra
+        let mut builder = TonCell::builder();
+        builder.write_bit(true)?;
+        builder.write_ref(cell.refs[0].clone())?;
+        builder.write_num(&10u32, 32)?;
+        let wrong_root = builder.build()?;
+        assert!(TestHashMapAugE::new(8, sum).read(&mut wrong_root.parser()).is_err());
+
+        // root fork extra doesn't match merged extras of leaves
+        let mut builder = TonCell::builder();
+        builder.write_num(&0u8, 2)?;
+        builder.write_num(&10u32, 32)?;
+        builder.write_ref(cell.refs[0].refs[0].clone())?;
+        builder.write_ref(cell.refs[0].refs[1].clone())?;
+        let wrong_fork = builder.build()?.into_ref();
+        let mut builder = TonCell::builder();
+        builder.write_bit(true)?;
+        builder.write_ref(wrong_fork)?;
+        builder.write_num(&10u32, 32)?;
+        let wrong_fork_root = builder.build()?;
+        assert!(TestHashMapAugE::new(8, sum).read(&mut wrong_fork_root.parser()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_aug_e_single_value() -> anyhow::Result<()> {
+        let data = HashMap::from([(5u8, (10u16, 3u32))]);
+        let mut builder = TonCell::builder();
+        TestHashMapAugE::new(8, sum).write(&mut builder, &data)?;
+        let cell = builder.build()?;
+        let mut parser = cell.parser();
+        assert!(parser.read_bit()?);
+        assert_eq!(parser.read_num::<u32>(32)?, 3);
+        assert_eq!(TestHashMapAugE::new(8, sum).read(&mut cell.parser())?, data);
+        Ok(())
+    }
+}
@@ -15,7 +15,7 @@ impl<KA, VA, K, V> TLBHashMapE<KA, VA, K, V>
 where
     KA: DictKeyAdapter<K>,
     VA: DictValAdapter<V>,
-    K: Eq + Hash + Ord,
+    K: Eq + Hash,
 {
     pub fn new(key_bits_len: u32) -> Self { Self(TLBHashMap::new(key_bits_len)) }
 
@@ -23,7 +23,7 @@ where
         if !parser.read_bit()? {
             return Ok(HashMap::new());
         }
-        self.0.read(&mut parser.read_next_ref()?.parser())
+        self.0.read(&mut parser.read_next_ref_parser()?)
     }
 
     pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<K, V>) -> Result<(), TLCoreError> {
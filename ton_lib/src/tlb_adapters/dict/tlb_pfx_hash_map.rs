@@ -0,0 +1,187 @@
+use crate::tlb_adapters::dict::label_type::DictLabelType;
+use crate::tlb_adapters::dict::leading_bit_utils::{common_prefix_len_var, split_at};
+use crate::tlb_adapters::{DictValAdapter, DictVarKey};
+use num_bigint::BigUint;
+use num_traits::One;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use ton_lib_core::cell::CellBuilder;
+use ton_lib_core::cell::CellParser;
+use ton_lib_core::cell::TonCell;
+use ton_lib_core::error::TLCoreError;
+
+// https://github.com/ton-blockchain/ton/blob/72056a2261cbb11f7cf0f20b389bcbffe018b1a8/crypto/block/block.tlb
+// phm_edge#_ {n:#} {X:Type} {l:#} {m:#} label:(HmLabel ~l n) {n = (~m) + l} node:(PfxHashmapNode m X) = PfxHashmap n X;
+// phmn_leaf$0 {n:#} {X:Type} value:X = PfxHashmapNode n X;
+// phmn_fork$1 {n:#} {X:Type} left:^(PfxHashmap n X) right:^(PfxHashmap n X) = PfxHashmapNode (n + 1) X;
+/// Adapter to write prefix-code HashMap into a cell: keys have variable length up to `key_bits_len`,
+/// and none of them can be a prefix of another one
+/// Doesn't write 'present' marker to root cell. Generally, is not supposed to be used in TLB structs
+/// Usage example: `#[tlb_derive(adapter = "TLBPfxHashMap::<DictValAdapterTLB, _>::new(32)")]`
+pub struct TLBPfxHashMap<VA: DictValAdapter<V>, V> {
+    key_bits_len: u32,
+    _phantom: PhantomData<(VA, V)>,
+}
+
+impl<VA: DictValAdapter<V>, V> TLBPfxHashMap<VA, V> {
+    pub fn new(key_bits_len: u32) -> Self {
+        Self {
+            key_bits_len,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<DictVarKey, V>, TLCoreError> {
+        let mut data = HashMap::new();
+        self.read_edge(parser, BigUint::one(), self.key_bits_len as usize, &mut data)?;
+        Ok(data)
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<DictVarKey, V>) -> Result<(), TLCoreError> {
+        if data.is_empty() {
+            return Err(TLCoreError::TLBWrongData("empty PfxHashMap can't be written".to_string()));
+        }
+        let mut keys = Vec::with_capacity(data.len());
+        for (key, value) in data {
+            if key.bits_len > self.key_bits_len as usize {
+                let err_str = format!("dict key too long: expected max {} bits, got {key:?}", self.key_bits_len);
+                return Err(TLCoreError::TLBWrongData(err_str));
+            }
+            keys.push((key.to_leading_bit()?, value));
+        }
+        self.write_edge(builder, keys, self.key_bits_len as usize)
+    }
+
+    // prefix contains leading bit
+    fn read_edge(
+        &self,
+        parser: &mut CellParser,
+        prefix: BigUint,
+        max_len: usize,
+        dst: &mut HashMap<DictVarKey, V>,
+    ) -> Result<(), TLCoreError> {
+        let label = DictLabelType::read(parser, max_len)?;
+        let label_len = label.bits() as usize - 1;
+        let prefix = (prefix << label_len) | (label ^ (BigUint::one() << label_len));
+        if !parser.read_bit()? {
+            dst.insert(DictVarKey::from_leading_bit(&prefix), VA::read(parser)?);
+            return Ok(());
+        }
+        if label_len == max_len {
+            return Err(TLCoreError::TLBWrongData("PfxHashMap fork with no key bits left".to_string()));
+        }
+        self.read_edge(&mut parser.read_next_ref_parser()?, &prefix << 1, max_len - label_len - 1, dst)?;
+        self.read_edge(&mut parser.read_next_ref_parser()?, (prefix << 1) + 1u32, max_len - label_len - 1, dst)
+    }
+
+    // keys contain leading bit
+    fn write_edge(
+        &self,
+        builder: &mut CellBuilder,
+        keys: Vec<(BigUint, &V)>,
+        max_len: usize,
+    ) -> Result<(), TLCoreError> {
+        if keys.len() == 1 {
+            let (key, value) = &keys[0];
+            DictLabelType::write(builder, key, max_len)?;
+            builder.write_bit(false)?;
+            return VA::write(builder, value);
+        }
+
+        let first_key = &keys[0].0;
+        let prefix_len = keys.iter().map(|(key, _)| common_prefix_len_var(first_key, key)).min().unwrap_or(0);
+        let label = first_key >> (first_key.bits() as usize - 1 - prefix_len);
+        let mut left_keys = Vec::with_capacity(keys.len() / 2);
+        let mut right_keys = Vec::with_capacity(keys.len() / 2);
+        for (key, value) in keys {
+            if key.bits() as usize - 1 == prefix_len {
+                let err_str =
+                    format!("PfxHashMap key {:?} is a prefix of other key", DictVarKey::from_leading_bit(&key));
+                return Err(TLCoreError::TLBWrongData(err_str));
+            }
+            let (_, is_right, suffix) = split_at(&key, prefix_len);
+            match is_right {
+                true => right_keys.push((suffix, value)),
+                false => left_keys.push((suffix, value)),
+            }
+        }
+
+        DictLabelType::write(builder, &label, max_len)?;
+        builder.write_bit(true)?;
+        for keys in [left_keys, right_keys] {
+            let mut child_builder = TonCell::builder();
+            self.write_edge(&mut child_builder, keys, max_len - prefix_len - 1)?;
+            builder.write_ref(child_builder.build()?.into_ref())?;
+        }
+        Ok(())
+    }
+}
+
+// phme_empty$0 {n:#} {X:Type} = PfxHashmapE n X;
+// phme_root$1 {n:#} {X:Type} root:^(PfxHashmap n X) = PfxHashmapE n X;
+/// Write present marker (0|1 bit) to root cell, and then Dict data to first ref cell.
+/// Usage: `#[tlb_derive(adapter = "TLBPfxHashMapE::<DictValAdapterTLB, _>::new(32)")]`
+pub struct TLBPfxHashMapE<VA: DictValAdapter<V>, V>(TLBPfxHashMap<VA, V>);
+
+impl<VA: DictValAdapter<V>, V> TLBPfxHashMapE<VA, V> {
+    pub fn new(key_bits_len: u32) -> Self { Self(TLBPfxHashMap::new(key_bits_len)) }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<DictVarKey, V>, TLCoreError> {
+        if !parser.read_bit()? {
+            return Ok(HashMap::new());
+        }
+        self.0.read(&mut parser.read_next_ref_parser()?)
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<DictVarKey, V>) -> Result<(), TLCoreError> {
+        if data.is_empty() {
+            return builder.write_bit(false);
+        }
+        builder.write_bit(true)?;
+        let mut dict_data_builder = TonCell::builder();
+        self.0.write(&mut dict_data_builder, data)?;
+        builder.write_ref(dict_data_builder.build()?.into_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb_adapters::DictValAdapterNum;
+
+    #[test]
+    fn test_pfx_dict() -> anyhow::Result<()> {
+        let data = HashMap::from([
+            (DictVarKey::new(0b0u32, 1), 1u32),
+            (DictVarKey::new(0b10u32, 2), 2),
+            (DictVarKey::new(0b1100u32, 4), 3),
+            (DictVarKey::new(0b1101u32, 4), 4),
+            (DictVarKey::new(0b111u32, 3), 5),
+        ]);
+        let dict = TLBPfxHashMapE::<DictValAdapterNum<32>, _>::new(8);
+        let mut builder = TonCell::builder();
+        dict.write(&mut builder, &data)?;
+        let cell = builder.build()?;
+        assert_eq!(dict.read(&mut cell.parser())?, data);
+
+        // root: empty label, fork
+        let mut root_parser = cell.refs[0].parser();
+        assert_eq!(root_parser.read_num::<u8>(3)?, 0b001);
+        // left: empty label, leaf
+        let mut left_parser = cell.refs[0].refs[0].parser();
+        assert_eq!(left_parser.read_num::<u8>(3)?, 0b000);
+        assert_eq!(left_parser.read_num::<u32>(32)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pfx_dict_not_prefix_free() -> anyhow::Result<()> {
+        let data = HashMap::from([(DictVarKey::new(0b1u32, 1), 1u32), (DictVarKey::new(0b10u32, 2), 2)]);
+        let mut builder = TonCell::builder();
+        assert!(TLBPfxHashMap::<DictValAdapterNum<32>, _>::new(8).write(&mut builder, &data).is_err());
+
+        let data = HashMap::from([(DictVarKey::new(0b1u32, 9), 1u32)]);
+        assert!(TLBPfxHashMap::<DictValAdapterNum<32>, _>::new(8).write(&mut builder, &data).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::tlb_adapters::dict::label_type::DictLabelType;
+use crate::tlb_adapters::dict::leading_bit_utils::{common_prefix_len_var, split_at};
+use crate::tlb_adapters::{DictValAdapter, DictVarKey};
+use num_bigint::BigUint;
+use num_traits::One;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use ton_lib_core::cell::CellBuilder;
+use ton_lib_core::cell::CellParser;
+use ton_lib_core::cell::TonCell;
+use ton_lib_core::error::TLCoreError;
+
+// https://github.com/ton-blockchain/ton/blob/72056a2261cbb11f7cf0f20b389bcbffe018b1a8/crypto/block/block.tlb
+// vhm_edge#_ {n:#} {X:Type} {l:#} {m:#} label:(HmLabel ~l n) {n = (~m) + l} node:(VarHashmapNode m X) = VarHashmap n X;
+// vhmn_leaf$00 {n:#} {X:Type} value:X = VarHashmapNode n X;
+// vhmn_fork$01 {n:#} {X:Type} left:^(VarHashmap n X) right:^(VarHashmap n X) value:(Maybe X) = VarHashmapNode (n + 1) X;
+// vhmn_cont$1 {n:#} {X:Type} branch:Bit child:^(VarHashmap n X) value:X = VarHashmapNode (n + 1) X;
+/// Adapter to write HashMap with keys of variable length (up to `key_bits_len`) into a cell.
+/// Unlike PfxHashMap, a key may be a prefix of another one
+/// Doesn't write 'present' marker to root cell. Generally, is not supposed to be used in TLB structs
+/// Usage example: `#[tlb_derive(adapter = "TLBVarHashMap::<DictValAdapterTLB, _>::new(32)")]`
+pub struct TLBVarHashMap<VA: DictValAdapter<V>, V> {
+    key_bits_len: u32,
+    _phantom: PhantomData<(VA, V)>,
+}
+
+impl<VA: DictValAdapter<V>, V> TLBVarHashMap<VA, V> {
+    pub fn new(key_bits_len: u32) -> Self {
+        Self {
+            key_bits_len,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<DictVarKey, V>, TLCoreError> {
+        let mut data = HashMap::new();
+        self.read_edge(parser, BigUint::one(), self.key_bits_len as usize, &mut data)?;
+        Ok(data)
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<DictVarKey, V>) -> Result<(), TLCoreError> {
+        if data.is_empty() {
+            return Err(TLCoreError::TLBWrongData("empty VarHashMap can't be written".to_string()));
+        }
+        let mut keys = Vec::with_capacity(data.len());
+        for (key, value) in data {
+            if key.bits_len > self.key_bits_len as usize {
+                let err_str = format!("dict key too long: expected max {} bits, got {key:?}", self.key_bits_len);
+                return Err(TLCoreError::TLBWrongData(err_str));
+            }
+            keys.push((key.to_leading_bit()?, value));
+        }
+        self.write_edge(builder, keys, self.key_bits_len as usize)
+    }
+
+    // prefix contains leading bit
+    fn read_edge(
+        &self,
+        parser: &mut CellParser,
+        prefix: BigUint,
+        max_len: usize,
+        dst: &mut HashMap<DictVarKey, V>,
+    ) -> Result<(), TLCoreError> {
+        let label = DictLabelType::read(parser, max_len)?;
+        let label_len = label.bits() as usize - 1;
+        let prefix = (prefix << label_len) | (label ^ (BigUint::one() << label_len));
+        let is_cont = parser.read_bit()?;
+        let is_fork = !is_cont && parser.read_bit()?;
+        if (is_cont || is_fork) && label_len == max_len {
+            return Err(TLCoreError::TLBWrongData("VarHashMap fork with no key bits left".to_string()));
+        }
+        let child_max_len = max_len.saturating_sub(label_len + 1);
+        let has_value = match (is_cont, is_fork) {
+            (true, _) => {
+                let branch = parser.read_bit()?;
+                let child_prefix = (&prefix << 1) + branch as u32;
+                self.read_edge(&mut parser.read_next_ref_parser()?, child_prefix, child_max_len, dst)?;
+                true
+            }
+            (false, true) => {
+                self.read_edge(&mut parser.read_next_ref_parser()?, &prefix << 1, child_max_len, dst)?;
+                self.read_edge(&mut parser.read_next_ref_parser()?, (&prefix << 1) + 1u32, child_max_len, dst)?;
+                parser.read_bit()?
+            }
+            (false, false) => true,
+        };
+        if has_value {
+            dst.insert(DictVarKey::from_leading_bit(&prefix), VA::read(parser)?);
+        }
+        Ok(())
+    }
+
+    // keys contain leading bit
+    fn write_edge(
+        &self,
+        builder: &mut CellBuilder,
+        keys: Vec<(BigUint, &V)>,
+        max_len: usize,
+    ) -> Result<(), TLCoreError> {
+        let first_key = &keys[0].0;
+        let prefix_len = keys.iter().map(|(key, _)| common_prefix_len_var(first_key, key)).min().unwrap_or(0);
+        let label = first_key >> (first_key.bits() as usize - 1 - prefix_len);
+        let mut node_value = None;
+        let mut left_keys = vec![];
+        let mut right_keys = vec![];
+        for (key, value) in keys {
+            if key.bits() as usize - 1 == prefix_len {
+                node_value = Some(value);
+                continue;
+            }
+            let (_, is_right, suffix) = split_at(&key, prefix_len);
+            match is_right {
+                true => right_keys.push((suffix, value)),
+                false => left_keys.push((suffix, value)),
+            }
+        }
+
+        DictLabelType::write(builder, &label, max_len)?;
+        let child_max_len = max_len.saturating_sub(prefix_len + 1);
+        match (node_value, left_keys.is_empty(), right_keys.is_empty()) {
+            (Some(value), true, true) => {
+                builder.write_num(&0b00, 2)?;
+                VA::write(builder, value)
+            }
+            (value, false, false) => {
+                builder.write_num(&0b01, 2)?;
+                for keys in [left_keys, right_keys] {
+                    let mut child_builder = TonCell::builder();
+                    self.write_edge(&mut child_builder, keys, child_max_len)?;
+                    builder.write_ref(child_builder.build()?.into_ref())?;
+                }
+                builder.write_bit(value.is_some())?;
+                match value {
+                    Some(value) => VA::write(builder, value),
+                    None => Ok(()),
+                }
+            }
+            (Some(value), left_is_empty, _) => {
+                builder.write_bit(true)?;
+                builder.write_bit(left_is_empty)?;
+                let child_keys = if left_is_empty { right_keys } else { left_keys };
+                let mut child_builder = TonCell::builder();
+                self.write_edge(&mut child_builder, child_keys, child_max_len)?;
+                builder.write_ref(child_builder.build()?.into_ref())?;
+                VA::write(builder, value)
+            }
+            (None, _, _) => {
+                Err(TLCoreError::TLBWrongData("VarHashMap node has neither value nor both branches".to_string()))
+            }
+        }
+    }
+}
+
+// vhme_empty$0 {n:#} {X:Type} = VarHashmapE n X;
+// vhme_root$1 {n:#} {X:Type} root:^(VarHashmap n X) = VarHashmapE n X;
+/// Write present marker (0|1 bit) to root cell, and then Dict data to first ref cell.
+/// Usage: `#[tlb_derive(adapter = "TLBVarHashMapE::<DictValAdapterTLB, _>::new(32)")]`
+pub struct TLBVarHashMapE<VA: DictValAdapter<V>, V>(TLBVarHashMap<VA, V>);
+
+impl<VA: DictValAdapter<V>, V> TLBVarHashMapE<VA, V> {
+    pub fn new(key_bits_len: u32) -> Self { Self(TLBVarHashMap::new(key_bits_len)) }
+
+    pub fn read(&self, parser: &mut CellParser) -> Result<HashMap<DictVarKey, V>, TLCoreError> {
+        if !parser.read_bit()? {
+            return Ok(HashMap::new());
+        }
+        self.0.read(&mut parser.read_next_ref_parser()?)
+    }
+
+    pub fn write(&self, builder: &mut CellBuilder, data: &HashMap<DictVarKey, V>) -> Result<(), TLCoreError> {
+        if data.is_empty() {
+            return builder.write_bit(false);
+        }
+        builder.write_bit(true)?;
+        let mut dict_data_builder = TonCell::builder();
+        self.0.write(&mut dict_data_builder, data)?;
+        builder.write_ref(dict_data_builder.build()?.into_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tlb_adapters::DictValAdapterNum;
+
+    #[test]
+    fn test_var_dict() -> anyhow::Result<()> {
+        let data = HashMap::from([
+            (DictVarKey::new(0b1u32, 1), 1u32),      // fork with value
+            (DictVarKey::new(0b10u32, 2), 2),        // leaf
+            (DictVarKey::new(0b110u32, 3), 3),       // cont
+            (DictVarKey::new(0b11011u32, 5), 4),     // leaf
+            (DictVarKey::new(0b0u32, 0), 5),         // root value
+            (DictVarKey::new(0b0000_0000u32, 8), 6), // long label
+        ]);
+        let dict = TLBVarHashMapE::<DictValAdapterNum<32>, _>::new(8);
+        let mut builder = TonCell::builder();
+        dict.write(&mut builder, &data)?;
+        let cell = builder.build()?;
+        assert_eq!(dict.read(&mut cell.parser())?, data);
+
+        // root: empty label, fork with value
+        let mut root_parser = cell.refs[0].parser();
+        assert_eq!(root_parser.read_num::<u8>(4)?, 0b0001);
+        assert!(root_parser.read_bit()?);
+        assert_eq!(root_parser.read_num::<u32>(32)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_var_dict_single_key() -> anyhow::Result<()> {
+        for key in [
+            DictVarKey::new(0u32, 0),
+            DictVarKey::new(0b101u32, 3),
+            DictVarKey::new(0xFFu32, 8),
+        ] {
+            let data = HashMap::from([(key, 7u8)]);
+            let dict = TLBVarHashMap::<DictValAdapterNum<8>, _>::new(8);
+            let mut builder = TonCell::builder();
+            dict.write(&mut builder, &data)?;
+            assert_eq!(dict.read(&mut builder.build()?.parser())?, data);
+        }
+        Ok(())
+    }
+}
@@ -11,7 +11,9 @@ pub struct TLBRef<T: TLB>(PhantomData<T>);
 
 impl<T: TLB> TLBRef<T> {
     pub fn new() -> Self { TLBRef(PhantomData) }
-    pub fn read(&self, parser: &mut CellParser) -> Result<T, TLCoreError> { T::from_cell(parser.read_next_ref()?) }
+    pub fn read(&self, parser: &mut CellParser) -> Result<T, TLCoreError> {
+        T::read(&mut parser.read_next_ref_parser()?)
+    }
     pub fn write(&self, builder: &mut CellBuilder, val: &T) -> Result<(), TLCoreError> {
         builder.write_ref(val.to_cell_ref()?)
     }
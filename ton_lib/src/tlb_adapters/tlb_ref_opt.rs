@@ -14,7 +14,7 @@ impl<T: TLB> TLBRefOpt<Option<T>> {
 
     pub fn read(&self, parser: &mut CellParser) -> Result<Option<T>, TLCoreError> {
         match parser.read_bit()? {
-            true => Ok(Some(T::from_cell(parser.read_next_ref()?)?)),
+            true => Ok(Some(T::read(&mut parser.read_next_ref_parser()?)?)),
             false => Ok(None),
         }
     }
@@ -0,0 +1,349 @@
+use crate::error::TLError;
+use crate::tlb_adapters::DictDataParser;
+use crate::tlb_dynamic::TLBSchema;
+use num_bigint::{BigInt, BigUint};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use ton_lib_core::bits_utils::BitsUtils;
+use ton_lib_core::boc::BOC;
+use ton_lib_core::cell::CellParser;
+use ton_lib_core::error::TLCoreError;
+use ton_lib_tlb_schema::{Constructor, TypeExpr};
+
+pub(super) struct TLBDecoder<'a> {
+    schema: &'a TLBSchema,
+    path: Vec<String>, // for error reporting: type names, field names and ^ for refs
+}
+
+// values of implicit fields and named numeric fields of current constructor
+#[derive(Default)]
+struct Env {
+    types: HashMap<String, TypeExpr>,
+    nats: HashMap<String, u64>,
+}
+
+impl<'a> TLBDecoder<'a> {
+    pub(super) fn new(schema: &'a TLBSchema) -> Self { Self { schema, path: vec![] } }
+
+    /// `expr` must be concrete: all params are substituted
+    pub(super) fn decode(&mut self, expr: &TypeExpr, parser: &mut CellParser) -> Result<Value, TLError> {
+        match expr {
+            TypeExpr::Apply { name, args } => self.decode_apply(name, args, parser),
+            TypeExpr::Ref(inner) => {
+                let cell_ref = self.core(parser, |parser| parser.read_next_ref().cloned())?;
+                self.path.push("^".to_string());
+                let value = self.decode(inner, &mut cell_ref.parser())?;
+                self.path.pop();
+                Ok(value)
+            }
+            TypeExpr::Cond { .. } => Err(self.error(parser, "conditional type is allowed only for fields")),
+        }
+    }
+
+    fn decode_apply(&mut self, name: &str, args: &[TypeExpr], parser: &mut CellParser) -> Result<Value, TLError> {
+        let nat_arg = |decoder: &Self, parser: &mut CellParser, pos: usize| {
+            eval_nat(&args[pos]).map_err(|err| decoder.error(parser, err))
+        };
+        let value = match (name, args.len()) {
+            ("#", 0) => self.read_uint(parser, 32)?,
+            ("##", 1) | ("uint", 1) => {
+                let bits_len = nat_arg(self, parser, 0)?;
+                self.read_uint(parser, bits_len as usize)?
+            }
+            ("#<=", 1) => {
+                let max_value = nat_arg(self, parser, 0)?;
+                self.read_uint(parser, bits_to_store(max_value))?
+            }
+            ("#<", 1) => {
+                let upper_bound = nat_arg(self, parser, 0)?;
+                self.read_uint(parser, bits_to_store(upper_bound.saturating_sub(1)))?
+            }
+            ("int", 1) => {
+                let bits_len = nat_arg(self, parser, 0)?;
+                self.read_int(parser, bits_len as usize)?
+            }
+            ("bits", 1) => {
+                let bits_len = nat_arg(self, parser, 0)?;
+                self.read_bits(parser, bits_len as usize)?
+            }
+            ("Bool" | "Bit", 0) => Value::Bool(self.core(parser, |parser| parser.read_bit())?),
+            ("Cell" | "Any", 0) => {
+                let cell = self.core(parser, |parser| parser.read_cell())?;
+                Value::String(self.core(parser, |_| BOC::new(cell.into_ref()).to_hex(false))?)
+            }
+            ("Maybe", 1) => match self.core(parser, |parser| parser.read_bit())? {
+                true => self.decode(&args[0], parser)?,
+                false => Value::Null,
+            },
+            ("Either", 2) => {
+                let (constructor, value) = match self.core(parser, |parser| parser.read_bit())? {
+                    false => ("left", self.decode(&args[0], parser)?),
+                    true => ("right", self.decode(&args[1], parser)?),
+                };
+                let mut object = Map::new();
+                object.insert("@type".to_string(), Value::String(constructor.to_string()));
+                object.insert("value".to_string(), value);
+                Value::Object(object)
+            }
+            ("HashmapE", 2) => match self.core(parser, |parser| parser.read_bit())? {
+                true => {
+                    let dict_ref = self.core(parser, |parser| parser.read_next_ref().cloned())?;
+                    let key_bits_len = nat_arg(self, parser, 0)?;
+                    self.decode_hashmap(key_bits_len as usize, &args[1], &mut dict_ref.parser())?
+                }
+                false => Value::Object(Map::new()),
+            },
+            ("Hashmap", 2) => {
+                let key_bits_len = nat_arg(self, parser, 0)?;
+                self.decode_hashmap(key_bits_len as usize, &args[1], parser)?
+            }
+            (name, 0) if sized_builtin(name).is_some() => {
+                let (kind, bits_len) = sized_builtin(name).unwrap();
+                match kind {
+                    "uint" => self.read_uint(parser, bits_len)?,
+                    "int" => self.read_int(parser, bits_len)?,
+                    _ => self.read_bits(parser, bits_len)?,
+                }
+            }
+            (name, _) => match self.schema.types.get(name) {
+                Some(constructors) => self.decode_object(name, constructors, args, parser)?,
+                None => return Err(self.error(parser, format!("unknown type {name} with {} args", args.len()))),
+            },
+        };
+        Ok(value)
+    }
+
+    fn decode_object(
+        &mut self,
+        type_name: &str,
+        constructors: &[Constructor],
+        args: &[TypeExpr],
+        parser: &mut CellParser,
+    ) -> Result<Value, TLError> {
+        // constructor is selected by params and tag. The longest matching tag wins
+        let bits_remaining = self.core(parser, |parser| parser.data_bits_remaining())?;
+        let mut selected: Option<(&Constructor, Env)> = None;
+        for constructor in constructors {
+            if constructor.result_args.len() != args.len() {
+                let err_msg = format!("type {type_name} expects {} args", constructor.result_args.len());
+                return Err(self.error(parser, err_msg));
+            }
+            let mut env = Env::default();
+            if !constructor
+                .result_args
+                .iter()
+                .zip(args)
+                .all(|(pattern, arg)| unify(constructor, pattern, arg, &mut env))
+            {
+                continue;
+            }
+            let tag = &constructor.tag;
+            if tag.bits_len > bits_remaining {
+                continue;
+            }
+            if tag.bits_len > 0 && self.core(parser, |parser| parser.lookup_bits(tag.bits_len))? != tag.value {
+                continue;
+            }
+            match &selected {
+                Some((best, _)) if best.tag.bits_len >= tag.bits_len => {}
+                _ => selected = Some((constructor, env)),
+            }
+        }
+        let Some((constructor, mut env)) = selected else {
+            return Err(self.error(parser, format!("no matching constructor for {type_name}")));
+        };
+
+        self.path.push(type_name.to_string());
+        self.core(parser, |parser| parser.read_num::<u128>(constructor.tag.bits_len))?;
+        let mut object = Map::new();
+        let constructor_name = match constructor.name.as_str() {
+            "_" => type_name,
+            name => name,
+        };
+        object.insert("@type".to_string(), Value::String(constructor_name.to_string()));
+        for (position, field) in constructor.fields.iter().enumerate() {
+            let field_name = field.name.clone().unwrap_or_else(|| format!("field_{position}"));
+            self.path.push(field_name.clone());
+            let value = match &field.expr {
+                TypeExpr::Cond { cond, bit, inner } => {
+                    let cond_value = eval_nat(&subst(cond, &env)).map_err(|err| self.error(parser, err))?;
+                    let enabled = match bit {
+                        Some(bit) => cond_value >> bit & 1 == 1,
+                        None => cond_value != 0,
+                    };
+                    match enabled {
+                        true => self.decode(&subst(inner, &env), parser)?,
+                        false => Value::Null,
+                    }
+                }
+                expr => self.decode(&subst(expr, &env), parser)?,
+            };
+            if let (Some(name), Some(nat)) = (&field.name, value.as_u64()) {
+                env.nats.insert(name.clone(), nat);
+            }
+            object.insert(field_name, value);
+            self.path.pop();
+        }
+        self.path.pop();
+        Ok(Value::Object(object))
+    }
+
+    fn decode_hashmap(
+        &mut self,
+        key_bits_len: usize,
+        value_expr: &TypeExpr,
+        parser: &mut CellParser,
+    ) -> Result<Value, TLError> {
+        let mut dict_parser = DictDataParser::new(key_bits_len);
+        let items =
+            dict_parser.read_with(parser, &mut |value_parser: &mut CellParser| self.decode(value_expr, value_parser));
+        let items = match items {
+            Ok(items) => items,
+            Err(TLError::TLCoreError(err)) => return Err(self.error(parser, err)),
+            Err(err) => return Err(err),
+        };
+        let mut object = Map::new();
+        for (key, value) in items {
+            let key = match key_bits_len {
+                0..=64 => key.to_string(),
+                _ => format!("{key:0width$x}", width = key_bits_len.div_ceil(4)),
+            };
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn read_uint(&self, parser: &mut CellParser, bits_len: usize) -> Result<Value, TLError> {
+        match bits_len {
+            0..=64 => Ok(Value::from(self.core(parser, |parser| parser.read_num::<u64>(bits_len))?)),
+            _ => Ok(Value::String(self.core(parser, |parser| parser.read_num::<BigUint>(bits_len))?.to_string())),
+        }
+    }
+
+    fn read_int(&self, parser: &mut CellParser, bits_len: usize) -> Result<Value, TLError> {
+        match bits_len {
+            0..=64 => Ok(Value::from(self.core(parser, |parser| parser.read_num::<i64>(bits_len))?)),
+            _ => Ok(Value::String(self.core(parser, |parser| parser.read_num::<BigInt>(bits_len))?.to_string())),
+        }
+    }
+
+    fn read_bits(&self, parser: &mut CellParser, bits_len: usize) -> Result<Value, TLError> {
+        let data = self.core(parser, |parser| parser.read_bits(bits_len))?;
+        Ok(Value::String(BitsUtils::to_fift_hex(&data, bits_len)))
+    }
+
+    // runs parser operation and attaches current position to error
+    fn core<T>(
+        &self,
+        parser: &mut CellParser,
+        operation: impl FnOnce(&mut CellParser) -> Result<T, TLCoreError>,
+    ) -> Result<T, TLError> {
+        operation(parser).map_err(|err| self.error(parser, err))
+    }
+
+    fn error(&self, parser: &mut CellParser, msg: impl ToString) -> TLError {
+        let bits_remaining = parser.data_bits_remaining().unwrap_or(0);
+        TLError::TLBSchemaDecodeError {
+            path: self.path.join("."),
+            bit_pos: parser.data_bits_len() - bits_remaining,
+            ref_pos: parser.next_ref_pos,
+            msg: msg.to_string(),
+        }
+    }
+}
+
+// uintN, intN, bitsN
+fn sized_builtin(name: &str) -> Option<(&'static str, usize)> {
+    for kind in ["uint", "int", "bits"] {
+        if let Some(bits_len) = name.strip_prefix(kind).and_then(|x| x.parse().ok()) {
+            return Some((kind, bits_len));
+        }
+    }
+    None
+}
+
+fn eval_nat(expr: &TypeExpr) -> Result<u64, String> {
+    match expr {
+        TypeExpr::Apply { name, args } if args.is_empty() => {
+            name.parse().map_err(|_| format!("{name} is not a number or numeric field"))
+        }
+        TypeExpr::Apply { name, args } if args.len() == 2 && (name == "+" || name == "*") => {
+            let (left, right) = (eval_nat(&args[0])?, eval_nat(&args[1])?);
+            let result = match name.as_str() {
+                "+" => left.checked_add(right),
+                _ => left.checked_mul(right),
+            };
+            result.ok_or(format!("overflow in {left} {name} {right}"))
+        }
+        _ => Err(format!("{expr:?} is not a number")),
+    }
+}
+
+// replaces params and numeric fields by their values
+fn subst(expr: &TypeExpr, env: &Env) -> TypeExpr {
+    match expr {
+        TypeExpr::Apply { name, args } if args.is_empty() => {
+            if let Some(type_expr) = env.types.get(name) {
+                type_expr.clone()
+            } else if let Some(nat) = env.nats.get(name) {
+                TypeExpr::name(&nat.to_string())
+            } else {
+                expr.clone()
+            }
+        }
+        TypeExpr::Apply { name, args } => TypeExpr::Apply {
+            name: name.clone(),
+            args: args.iter().map(|arg| subst(arg, env)).collect(),
+        },
+        TypeExpr::Ref(inner) => TypeExpr::Ref(Box::new(subst(inner, env))),
+        TypeExpr::Cond { cond, bit, inner } => TypeExpr::Cond {
+            cond: Box::new(subst(cond, env)),
+            bit: *bit,
+            inner: Box::new(subst(inner, env)),
+        },
+    }
+}
+
+// matches constructor result arg (like `X`, `0`, `(n + 1)`) with actual type arg, binding params to env
+fn unify(constructor: &Constructor, pattern: &TypeExpr, arg: &TypeExpr, env: &mut Env) -> bool {
+    match pattern {
+        TypeExpr::Apply { name, args } if args.is_empty() => {
+            let Some(param) = constructor.params.iter().find(|param| &param.name == name) else {
+                return match name.parse::<u64>() {
+                    Ok(value) => eval_nat(arg) == Ok(value),
+                    Err(_) => pattern == arg,
+                };
+            };
+            if param.is_type {
+                return env.types.entry(name.clone()).or_insert_with(|| arg.clone()) == arg;
+            }
+            match eval_nat(arg) {
+                Ok(value) => *env.nats.entry(name.clone()).or_insert(value) == value,
+                Err(_) => false,
+            }
+        }
+        // n + c = value => n = value - c
+        TypeExpr::Apply { name, args } if name == "+" && args.len() == 2 => match (eval_nat(arg), eval_nat(&args[1])) {
+            (Ok(value), Ok(addend)) if value >= addend => {
+                unify(constructor, &args[0], &TypeExpr::name(&(value - addend).to_string()), env)
+            }
+            _ => false,
+        },
+        TypeExpr::Apply { name, args } => match arg {
+            TypeExpr::Apply {
+                name: arg_name,
+                args: arg_args,
+            } if arg_name == name && arg_args.len() == args.len() => {
+                args.iter().zip(arg_args).all(|(pattern, arg)| unify(constructor, pattern, arg, env))
+            }
+            _ => false,
+        },
+        TypeExpr::Ref(pattern) => match arg {
+            TypeExpr::Ref(arg) => unify(constructor, pattern, arg, env),
+            _ => false,
+        },
+        TypeExpr::Cond { .. } => false,
+    }
+}
+
+fn bits_to_store(value: u64) -> usize { (u64::BITS - value.leading_zeros()) as usize }
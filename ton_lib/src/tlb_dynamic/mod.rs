@@ -0,0 +1,190 @@
+mod decoder;
+
+use crate::error::TLError;
+use crate::tlb_dynamic::decoder::TLBDecoder;
+use serde_json::Value;
+use std::collections::HashMap;
+use ton_lib_core::cell::{CellParser, TonCell};
+use ton_lib_tlb_schema::{parse_schema, parse_type_expr, Constructor};
+
+/// TL-B schema loaded at runtime. Decodes cells to `serde_json::Value`, which can't be done with `TLBDerive`
+///
+/// Object is decoded as `{"@type": constructor_name, field_name: value, ...}`. Anonymous fields are named `field_{i}`
+/// - `#`, `## n`, `#<= n`, `#< n`, `uintN`, `intN`: number, or decimal string if it doesn't fit into 64 bits
+/// - `bitsN`: hex string, with completion tag and `_` suffix if N is not multiple of 4
+/// - `Bool`, `Bit`: bool
+/// - `Cell`, `Any`: hex-encoded BOC of the rest of the cell
+/// - `Maybe X`: null or value, `cond?X`: null if condition is false
+/// - `HashmapE n X`, `Hashmap n X`: object with decimal keys (hex for 128+ bits keys)
+///
+/// `Maybe`, `Either`, `Hashmap` and `HashmapE` are built-in and can't be redefined by schema
+/// Implicit fields are limited to `{X:Type}` and `{n:#}`
+/// Constructor without explicit tag has implicit CRC32 tag, only `#_`, `$_` and `_` constructor have empty tag
+pub struct TLBSchema {
+    types: HashMap<String, Vec<Constructor>>,
+}
+
+impl TLBSchema {
+    pub fn parse(source: &str) -> Result<Self, TLError> {
+        let mut types: HashMap<String, Vec<Constructor>> = HashMap::new();
+        for (type_name, constructor) in parse_schema(source)? {
+            types.entry(type_name).or_default().push(constructor);
+        }
+        Ok(Self { types })
+    }
+
+    pub fn has_type(&self, type_name: &str) -> bool { self.types.contains_key(type_name) }
+
+    /// `type_expr` is a TL-B type expression, like `Block` or `HashmapE 32 ^Cell`
+    pub fn decode(&self, type_expr: &str, cell: &TonCell) -> Result<Value, TLError> {
+        self.decode_parser(type_expr, &mut cell.parser())
+    }
+
+    pub fn decode_parser(&self, type_expr: &str, parser: &mut CellParser) -> Result<Value, TLError> {
+        let type_expr = parse_type_expr(type_expr)?;
+        TLBDecoder::new(self).decode(&type_expr, parser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_tlb::{Coins, SimpleLib, StateInit, TickTock};
+    use serde_json::json;
+    use ton_lib_core::cell::TonHash;
+    use ton_lib_core::traits::tlb::TLB;
+
+    // https://github.com/ton-blockchain/ton/blob/59a8cf0ae5c3062d14ec4c89a04fee80b5fd05c1/crypto/block/block.tlb
+    const BLOCK_TLB_PART: &str = r#"
+        nothing$0 {X:Type} = Maybe X;
+        just$1 {X:Type} value:X = Maybe X;
+        var_uint$_ {n:#} len:(#< n) value:(uint (len * 8)) = VarUInteger n;
+        nanograms$_ amount:(VarUInteger 16) = Grams;
+        tick_tock$_ tick:Bool tock:Bool = TickTock;
+        _ split_depth:(Maybe (## 5)) special:(Maybe TickTock)
+          code:(Maybe ^Cell) data:(Maybe ^Cell)
+          library:(HashmapE 256 SimpleLib) = StateInit;
+        simple_lib$_ public:Bool root:^Cell = SimpleLib;
+    "#;
+
+    #[test]
+    fn test_tlb_schema_decode_block_types() -> anyhow::Result<()> {
+        let schema = TLBSchema::parse(BLOCK_TLB_PART)?;
+        assert!(schema.has_type("StateInit"));
+
+        let grams = schema.decode("Grams", &Coins::new(1_000_000_000u64).to_cell()?)?;
+        let expected = json!({"@type": "nanograms", "amount": {"@type": "var_uint", "len": 4, "value": 1_000_000_000}});
+        assert_eq!(grams, expected);
+
+        let lib_root = TonCell::EMPTY.into_ref();
+        let state_init = StateInit {
+            split_depth: Some(3),
+            tick_tock: Some(TickTock {
+                tick: true,
+                tock: false,
+            }),
+            code: None,
+            data: Some(lib_root.clone()),
+            library: HashMap::from([(
+                TonHash::from([0xAB; 32]),
+                SimpleLib {
+                    public: true,
+                    root: lib_root,
+                },
+            )]),
+        };
+        let decoded = schema.decode("StateInit", &state_init.to_cell()?)?;
+        let empty_cell_boc = "b5ee9c72010101010002000000";
+        let expected = json!({
+            "@type": "StateInit",
+            "split_depth": 3,
+            "special": {"@type": "tick_tock", "tick": true, "tock": false},
+            "code": null,
+            "data": empty_cell_boc,
+            "library": {
+                "ab".repeat(32): {"@type": "simple_lib", "public": true, "root": empty_cell_boc},
+            },
+        });
+        assert_eq!(decoded, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_schema_decode_params() -> anyhow::Result<()> {
+        let schema = TLBSchema::parse(
+            r#"
+            list_nil$_ = List 0;
+            list_next$_ {n:#} head:uint8 tail:^(List n) = List (n + 1);
+            flags#AB flags:(## 8) ext:flags.0?^Cell count:flags.1?uint16 rest:(bits 3) = Flags;
+            "#,
+        )?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&2u8, 8)?;
+        builder.write_ref(TonCell::EMPTY.into_ref())?;
+        let tail = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&1u8, 8)?;
+        builder.write_ref(tail)?;
+        let list = builder.build()?;
+        let expected = json!({
+            "@type": "list_next",
+            "head": 1,
+            "tail": {"@type": "list_next", "head": 2, "tail": {"@type": "list_nil"}},
+        });
+        assert_eq!(schema.decode("List 2", &list)?, expected);
+        assert!(schema.decode("List 3", &list).is_err());
+
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xABu8, 8)?;
+        builder.write_num(&0b10u8, 8)?;
+        builder.write_num(&0xFFFFu16, 16)?;
+        builder.write_bits([0b1010_0000], 3)?;
+        let expected = json!({"@type": "flags", "flags": 2, "ext": null, "count": 0xFFFF, "rest": "B_"});
+        assert_eq!(schema.decode("Flags", &builder.build()?)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_schema_decode_tags() -> anyhow::Result<()> {
+        let schema = TLBSchema::parse(
+            r#"
+            ping#AB = Msg;
+            pong value:uint8 = Msg;
+            "#,
+        )?;
+        // tag-only constructor: tag ends at the end of data
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xABu8, 8)?;
+        assert_eq!(schema.decode("Msg", &builder.build()?)?, json!({"@type": "ping"}));
+
+        // crc32("pong value:uint8 = Msg")
+        let mut builder = TonCell::builder();
+        builder.write_num(&0x224eb5f5u32, 32)?;
+        builder.write_num(&7u8, 8)?;
+        assert_eq!(schema.decode("Msg", &builder.build()?)?, json!({"@type": "pong", "value": 7}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_schema_decode_error() -> anyhow::Result<()> {
+        let schema = TLBSchema::parse(BLOCK_TLB_PART)?;
+        let mut builder = TonCell::builder();
+        builder.write_bits([0b1001_0000], 4)?; // split_depth: just, then only 3 bits of 5
+        let result = schema.decode("StateInit", &builder.build()?);
+        match result {
+            Err(TLError::TLBSchemaDecodeError { path, bit_pos, .. }) => {
+                assert_eq!(path, "StateInit.split_depth");
+                assert_eq!(bit_pos, 1);
+            }
+            _ => panic!("unexpected result: {result:?}"),
+        }
+
+        let mut builder = TonCell::builder();
+        builder.write_bits([0b1000_0000], 1)?; // tick_tock without data
+        let result = schema.decode("Maybe TickTock", &builder.build()?);
+        assert!(matches!(result, Err(TLError::TLBSchemaDecodeError { path, .. }) if path == "TickTock.tick"));
+        assert!(schema.decode("Unknown", &TonCell::EMPTY).is_err());
+        assert!(TLBSchema::parse("a$0 = a;").is_err());
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ pub(super) fn write_up_to_4_msgs(
 }
 
 pub(super) fn read_up_to_4_msgs(parser: &mut CellParser) -> Result<(Vec<u8>, Vec<TonCellRef>), TLCoreError> {
-    let msgs_cnt = parser.cell.refs.len();
+    let msgs_cnt = parser.refs_count();
     let mut msgs_modes = Vec::with_capacity(msgs_cnt);
     let mut msgs = Vec::with_capacity(msgs_cnt);
     for _ in 0..msgs_cnt {
@@ -47,7 +47,7 @@ impl TLB for InnerRequest {
         if !parser.read_bit()? {
             return Ok(Self { out_actions: None });
         }
-        let out_actions = TLB::from_cell(parser.read_next_ref()?)?;
+        let out_actions = TLB::read(&mut parser.read_next_ref_parser()?)?;
         if parser.read_bit()? {
             bail_tl_core!("other_actions parsing is unsupported");
         }
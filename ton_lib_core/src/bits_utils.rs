@@ -53,6 +53,25 @@ impl BitsUtils {
 
         true
     }
+
+    /// Fift-like hex: if bits_len is not multiple of 4, completion tag is added and `_` is appended
+    pub fn to_fift_hex(data: &[u8], bits_len: usize) -> String {
+        let mut result = String::with_capacity(bits_len.div_ceil(4) + 1);
+        for nibble_pos in 0..bits_len.div_ceil(4) {
+            let byte = data[nibble_pos / 2];
+            let mut nibble = if nibble_pos % 2 == 0 { byte >> 4 } else { byte & 0x0F };
+            let rest_bits = bits_len - nibble_pos * 4;
+            if rest_bits < 4 {
+                nibble &= (0xF0u8 >> rest_bits) & 0x0F;
+                nibble |= 1 << (3 - rest_bits); // completion tag
+            }
+            result.push(char::from_digit(nibble as u32, 16).unwrap().to_ascii_uppercase());
+        }
+        if bits_len % 4 != 0 {
+            result.push('_');
+        }
+        result
+    }
 }
 
 #[cfg(test)]
@@ -75,6 +94,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_to_fift_hex() {
+        assert_eq!(BitsUtils::to_fift_hex(&[0xAB, 0xC0], 12), "ABC");
+        assert_eq!(BitsUtils::to_fift_hex(&[0b1010_0000], 3), "B_");
+        assert_eq!(BitsUtils::to_fift_hex(&[], 0), "");
+    }
+
     #[test]
     fn test_rewrite_bits() {
         let src = vec![0b11001100, 0b10101010];
@@ -0,0 +1,138 @@
+use crate::boc::boc_lazy::{read_var_size, BOCLazy};
+use crate::cell::{CellMeta, CellParser, CellType, LevelMask, TonCell, TonCellRef, TonHash};
+use crate::error::TLCoreError;
+use std::fmt::{Debug, Formatter};
+
+/// Cell of [`BOCLazy`]. Data is borrowed from BOC bytes, refs are decoded on access.
+/// Bits after `data_bits_len` are not cleared (completion tag is kept in the last byte)
+#[derive(Clone, Copy)]
+pub struct LazyCell<'a> {
+    pub cell_type: CellType,
+    pub data: &'a [u8],
+    pub data_bits_len: usize,
+    boc: &'a BOCLazy<'a>,
+    pos: usize,
+    level_mask: LevelMask,
+    refs_cnt: usize,
+    refs_data: &'a [u8],
+}
+
+impl<'a> LazyCell<'a> {
+    pub fn pos(&self) -> usize { self.pos }
+    pub fn level_mask(&self) -> LevelMask { self.level_mask }
+    pub fn refs_count(&self) -> usize { self.refs_cnt }
+    /// Parser decodes refs only when it walks into them, so `TLB::read` touches only the cells it needs
+    pub fn parser(&self) -> CellParser<'a> { CellParser::new_lazy(*self) }
+
+    pub fn reference(&self, ref_pos: usize) -> Result<LazyCell<'a>, TLCoreError> {
+        if ref_pos >= self.refs_count() {
+            return Err(TLCoreError::ParserRefsUnderflow { req: ref_pos });
+        }
+        let ref_size = self.boc.ref_size();
+        let cell_pos = read_var_size(&self.refs_data[ref_pos * ref_size..(ref_pos + 1) * ref_size]);
+        if cell_pos <= self.pos {
+            return Err(TLCoreError::Custom("ref to parent cell detected".to_string()));
+        }
+        self.boc.cell(cell_pos)
+    }
+
+    /// Decodes the whole subtree into TonCell. Decoded cells are cached in [`BOCLazy`]
+    pub fn to_cell(&self) -> Result<TonCellRef, TLCoreError> { self.cell_ref().cloned() }
+
+    pub(crate) fn cell_ref(&self) -> Result<&'a TonCellRef, TLCoreError> {
+        let loaded = self.boc.loaded_cell(self.pos);
+        if let Some(cell) = loaded.get() {
+            return Ok(cell);
+        }
+        let mut data = self.data.to_vec();
+        if let Some(last_byte) = data.last_mut().filter(|_| self.data_bits_len < self.data.len() * 8) {
+            *last_byte &= *last_byte - 1; // drop completion tag
+        }
+        let mut refs = Vec::with_capacity(self.refs_count());
+        for ref_pos in 0..self.refs_count() {
+            refs.push(self.reference(ref_pos)?.cell_ref()?.clone());
+        }
+        let cell = TonCell {
+            cell_type: self.cell_type,
+            data,
+            data_bits_len: self.data_bits_len,
+            refs,
+            meta: CellMeta::default(),
+        };
+        cell.meta.validate(&cell)?;
+        Ok(loaded.get_or_init(|| cell.into_ref()))
+    }
+
+    pub(super) fn parse(boc: &'a BOCLazy<'a>, pos: usize, serial: &'a [u8]) -> Result<Self, TLCoreError> {
+        let cell_len = match Self::serialized_len(serial, boc.ref_size()) {
+            Some(cell_len) => cell_len,
+            None => return Err(TLCoreError::BOCWrongData(format!("cell {pos} is out of bounds"))),
+        };
+        let (d1, d2) = (serial[0], serial[1]);
+        let refs_cnt = (d1 & 0b111) as usize;
+        let is_exotic = (d1 & 0b1000) != 0;
+        let level_mask = LevelMask::new(d1 >> 5);
+        let data_start = cell_len - refs_cnt * boc.ref_size() - data_size(d2);
+        let data = &serial[data_start..data_start + data_size(d2)];
+        let refs_data = &serial[data_start + data.len()..cell_len];
+
+        let full_bytes = (d2 & 1) == 0;
+        let data_bits_len = match data.last() {
+            Some(&last_byte) if !full_bytes => {
+                if last_byte == 0 {
+                    return Err(TLCoreError::Custom(
+                        "Last byte of binary must not be zero if full_byte flag is not set".to_string(),
+                    ));
+                }
+                data.len() * 8 - last_byte.trailing_zeros() as usize - 1
+            }
+            _ => data.len() * 8,
+        };
+        let cell_type = match is_exotic {
+            true => match data.first() {
+                Some(&type_byte) => CellType::new_exotic(type_byte)?,
+                None => return Err(TLCoreError::BOCWrongData("Exotic cell must have at least 1 byte".to_string())),
+            },
+            false => CellType::Ordinary,
+        };
+        Ok(Self {
+            cell_type,
+            data,
+            data_bits_len,
+            boc,
+            pos,
+            level_mask,
+            refs_cnt,
+            refs_data,
+        })
+    }
+
+    // None if cell doesn't fit into serial
+    pub(super) fn serialized_len(serial: &[u8], ref_size: usize) -> Option<usize> {
+        let (&d1, &d2) = (serial.first()?, serial.get(1)?);
+        let refs_cnt = (d1 & 0b111) as usize;
+        if refs_cnt > 4 {
+            return None;
+        }
+        let has_hashes = (d1 & 0b10000) != 0;
+        let hashes_len = match has_hashes {
+            true => LevelMask::new(d1 >> 5).hash_count() * (TonHash::BYTES_LEN + 2),
+            false => 0,
+        };
+        let cell_len = 2 + hashes_len + data_size(d2) + refs_cnt * ref_size;
+        (cell_len <= serial.len()).then_some(cell_len)
+    }
+}
+
+impl Debug for LazyCell<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyCell")
+            .field("pos", &self.pos)
+            .field("cell_type", &self.cell_type)
+            .field("data_bits_len", &self.data_bits_len)
+            .field("refs_cnt", &self.refs_cnt)
+            .finish()
+    }
+}
+
+fn data_size(d2: u8) -> usize { ((d2 >> 1) + (d2 & 1)) as usize }
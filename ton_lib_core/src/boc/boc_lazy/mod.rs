@@ -0,0 +1,232 @@
+mod lazy_cell;
+
+pub use lazy_cell::*;
+
+use crate::boc::boc_raw::BOCHeader;
+use crate::cell::TonCellRef;
+use crate::error::TLCoreError;
+use std::sync::OnceLock;
+
+/// Reads BOC directly from borrowed bytes (e.g. mmapped file) without parsing all cells upfront.
+/// Cells are decoded only when requested. If BOC has index, it's used to locate cells,
+/// otherwise cells offsets are collected by a single pass over cells headers.
+pub struct BOCLazy<'a> {
+    serial: &'a [u8],
+    header: BOCHeader,
+    cells_offsets: Option<Vec<usize>>,
+    loaded: Vec<OnceLock<TonCellRef>>,
+}
+
+impl<'a> BOCLazy<'a> {
+    pub fn new(serial: &'a [u8]) -> Result<Self, TLCoreError> {
+        if serial.is_empty() {
+            return Err(TLCoreError::BOCEmpty);
+        }
+        let header = BOCHeader::parse(serial)?;
+        let cells_end = header.cells_offset + header.tot_cells_size;
+        if cells_end > serial.len() {
+            let err_msg = format!("cells data end at {cells_end}, but BOC has only {} bytes", serial.len());
+            return Err(TLCoreError::BOCWrongData(err_msg));
+        }
+        let mut boc = Self {
+            serial,
+            cells_offsets: None,
+            loaded: (0..header.cells_cnt).map(|_| OnceLock::new()).collect(),
+            header,
+        };
+        if !boc.header.has_idx {
+            boc.cells_offsets = Some(boc.scan_cells_offsets()?);
+        }
+        Ok(boc)
+    }
+
+    pub fn cells_count(&self) -> usize { self.header.cells_cnt }
+    pub fn roots_count(&self) -> usize { self.header.roots_position.len() }
+
+    pub fn root(&self, root_pos: usize) -> Result<LazyCell<'_>, TLCoreError> {
+        match self.header.roots_position.get(root_pos) {
+            Some(&cell_pos) => self.cell(cell_pos),
+            None => Err(TLCoreError::BOCWrongData(format!("root {root_pos} is out of bounds"))),
+        }
+    }
+
+    pub fn single_root(&self) -> Result<LazyCell<'_>, TLCoreError> {
+        if self.roots_count() != 1 {
+            return Err(TLCoreError::BOCSingleRoot(self.roots_count()));
+        }
+        self.root(0)
+    }
+
+    /// Random access to cell by its position in BOC
+    pub fn cell(&self, cell_pos: usize) -> Result<LazyCell<'_>, TLCoreError> {
+        if cell_pos >= self.cells_count() {
+            return Err(TLCoreError::BOCWrongData(format!("cell {cell_pos} is out of bounds")));
+        }
+        let cells_data = &self.serial[self.header.cells_offset..self.header.cells_offset + self.header.tot_cells_size];
+        let cell_offset = self.cell_offset(cell_pos)?;
+        match cells_data.get(cell_offset..) {
+            Some(cell_data) => LazyCell::parse(self, cell_pos, cell_data),
+            None => Err(TLCoreError::BOCWrongData(format!("cell {cell_pos} offset {cell_offset} is out of bounds"))),
+        }
+    }
+
+    pub(crate) fn ref_size(&self) -> usize { self.header.size as usize }
+
+    pub(crate) fn loaded_cell(&self, cell_pos: usize) -> &OnceLock<TonCellRef> { &self.loaded[cell_pos] }
+
+    // offset of cell inside cell_data
+    fn cell_offset(&self, cell_pos: usize) -> Result<usize, TLCoreError> {
+        if let Some(offsets) = &self.cells_offsets {
+            return Ok(offsets[cell_pos]);
+        }
+        if cell_pos == 0 {
+            return Ok(0);
+        }
+        // index stores end offsets, with cache bit in the lowest bit if has_cache_bits
+        let off_bytes = self.header.off_bytes as usize;
+        let entry_offset = self.header.idx_offset + (cell_pos - 1) * off_bytes;
+        let entry = read_var_size(&self.serial[entry_offset..entry_offset + off_bytes]);
+        match self.header.has_cache_bits {
+            true => Ok(entry >> 1),
+            false => Ok(entry),
+        }
+    }
+
+    fn scan_cells_offsets(&self) -> Result<Vec<usize>, TLCoreError> {
+        let cells_data = &self.serial[self.header.cells_offset..self.header.cells_offset + self.header.tot_cells_size];
+        let mut offsets = Vec::with_capacity(self.cells_count());
+        let mut cell_offset = 0;
+        for cell_pos in 0..self.cells_count() {
+            offsets.push(cell_offset);
+            cell_offset += LazyCell::serialized_len(cells_data.get(cell_offset..).unwrap_or_default(), self.ref_size())
+                .ok_or_else(|| TLCoreError::BOCWrongData(format!("cell {cell_pos} is out of bounds")))?;
+        }
+        Ok(offsets)
+    }
+}
+
+pub(crate) fn read_var_size(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |result, &byte| (result << 8) | usize::from(byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boc::{BOCSerializeOptions, BOC};
+    use crate::cell::{CellBuilder, CellParser, TonCell};
+    use crate::traits::tlb::TLB;
+
+    fn make_boc() -> anyhow::Result<BOC> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&0x0Au8, 8)?;
+        let leaf = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&0x0Bu8, 8)?;
+        builder.write_bits([0b1010_0000], 3)?;
+        builder.write_ref(leaf.clone())?;
+        builder.write_ref(leaf.clone())?;
+        let parent = builder.build_ref()?;
+        Ok(BOC::from_roots(vec![parent, leaf]))
+    }
+
+    #[test]
+    fn test_boc_lazy_read() -> anyhow::Result<()> {
+        let boc = make_boc()?;
+        let all_options = [
+            BOCSerializeOptions::default(),
+            BOCSerializeOptions {
+                has_idx: true,
+                ..Default::default()
+            },
+            BOCSerializeOptions {
+                has_idx: true,
+                has_cache_bits: true,
+                has_crc32c: true,
+                with_top_hash: true,
+                ..Default::default()
+            },
+        ];
+        for options in all_options {
+            let bytes = boc.to_bytes_with(&options)?;
+            let lazy = BOCLazy::new(&bytes)?;
+            assert_eq!(lazy.cells_count(), 2);
+            assert_eq!(lazy.roots_count(), 2);
+
+            let root = lazy.root(0)?;
+            assert_eq!(root.data_bits_len, 11);
+            assert_eq!(root.refs_count(), 2);
+            let mut parser = root.parser();
+            assert_eq!(parser.read_num::<u8>(8)?, 0x0B);
+            assert_eq!(parser.read_bits(3)?, vec![0b1010_0000]);
+            assert_eq!(parser.read_next_ref_parser()?.read_num::<u8>(8)?, 0x0A);
+            assert_eq!(parser.read_next_ref()?, &boc.roots[1]);
+            parser.ensure_empty()?;
+            assert!(parser.read_next_ref_parser().is_err());
+
+            assert_eq!(*lazy.root(0)?.to_cell()?, *boc.roots[0]);
+            assert_eq!(*lazy.root(1)?.to_cell()?, *boc.roots[1]);
+        }
+        Ok(())
+    }
+
+    // reads the first ref only
+    #[derive(Debug, PartialEq)]
+    struct FirstRefNum(u8);
+
+    impl TLB for FirstRefNum {
+        fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+            Ok(Self(u8::read(&mut parser.read_next_ref_parser()?)?))
+        }
+
+        fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+            let mut ref_builder = TonCell::builder();
+            self.0.write(&mut ref_builder)?;
+            builder.write_ref(ref_builder.build_ref()?)?;
+            builder.write_ref(TonCell::EMPTY.into_ref())
+        }
+    }
+
+    #[test]
+    fn test_boc_lazy_tlb_read() -> anyhow::Result<()> {
+        let cell = FirstRefNum(42).to_cell()?;
+        let bytes = BOC::new(cell.into_ref()).to_bytes(false)?;
+        let lazy = BOCLazy::new(&bytes)?;
+        assert_eq!(FirstRefNum::read(&mut lazy.single_root()?.parser())?, FirstRefNum(42));
+        assert!((0..lazy.cells_count()).all(|pos| lazy.loaded_cell(pos).get().is_none()));
+
+        // TonCell is decoded completely
+        let root = TonCell::read(&mut lazy.single_root()?.parser())?;
+        assert_eq!(FirstRefNum::from_cell(&root)?, FirstRefNum(42));
+        assert!((0..lazy.cells_count()).all(|pos| lazy.loaded_cell(pos).get().is_some()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_boc_lazy_exotic() -> anyhow::Result<()> {
+        let boc_hex = "b5ee9c720101040100b900010d000000000000050102c9801459f7c0a12bb4ac4b78a788c425ee4d52f8b6041dda17b77b09fc5a03e894d6900287cd9fbe2ea663415da0aa6bbdf0cb136abe9c4f45214dd259354b80da8c265a006aebb27f5d0f1daf43e200f52408f3eb9ff5610f5b43284224644e7c6a590d14400203084202c00836440d084e44fb94316132ac5a21417ef4f429ee09b5560b5678b334c3e8084202c95a2ed22ab516f77f9d4898dc4578e72f18a2448e8f6832334b0b4bf501bc79";
+        let bytes = hex::decode(boc_hex)?;
+        let lazy = BOCLazy::new(&bytes)?;
+        let library_ref = lazy.single_root()?.reference(0)?.reference(0)?;
+        assert_eq!(library_ref.cell_type, crate::cell::CellType::LibraryRef);
+        assert_eq!(lazy.single_root()?.to_cell()?, BOC::from_bytes(&bytes)?.single_root()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_boc_lazy_wrong_data() -> anyhow::Result<()> {
+        let bytes = make_boc()?.to_bytes(false)?;
+        let lazy = BOCLazy::new(&bytes)?;
+        assert!(matches!(lazy.root(2), Err(TLCoreError::BOCWrongData(_))));
+        assert!(matches!(lazy.cell(2), Err(TLCoreError::BOCWrongData(_))));
+        assert!(matches!(lazy.single_root(), Err(TLCoreError::BOCSingleRoot(2))));
+
+        assert!(matches!(BOCLazy::new(&[]), Err(TLCoreError::BOCEmpty)));
+        assert!(matches!(BOCLazy::new(&bytes[..bytes.len() - 1]), Err(TLCoreError::BOCWrongData(_))));
+
+        // cells count doesn't fit into data: b5ee9c72, flags & size = 1, off_bytes = 1, cells = 255, roots = 1
+        let bytes = hex::decode("b5ee9c720101ff010002000000")?;
+        assert!(matches!(BOCLazy::new(&bytes), Err(TLCoreError::BOCWrongData(_))));
+        assert!(matches!(BOC::from_bytes(&bytes), Err(TLCoreError::BOCWrongData(_))));
+        Ok(())
+    }
+}
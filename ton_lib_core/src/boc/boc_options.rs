@@ -0,0 +1,17 @@
+/// Controls BOC layout produced by [`BOC::to_bytes_with`](crate::boc::BOC::to_bytes_with)
+///
+/// `size` and `off_bytes` are calculated automatically if not set.
+/// If set, they must be big enough to fit cells count and total cells size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BOCSerializeOptions {
+    pub has_idx: bool,
+    pub has_crc32c: bool,
+    /// Requires `has_idx`. Marks cells referenced more than once in index
+    pub has_cache_bits: bool,
+    /// Store hashes & depths of root cells
+    pub with_top_hash: bool,
+    /// Store hashes & depths of cells with heavy subtrees (node uses it for blocks & states)
+    pub with_int_hashes: bool,
+    pub size: Option<u8>,
+    pub off_bytes: Option<u8>,
+}
@@ -1,14 +1,67 @@
 use crate::cell::CellType;
 use crate::cell::LevelMask;
+use crate::cell::TonHash;
 use crate::error::TLCoreError;
 use bitstream_io::{BigEndian, ByteRead, ByteReader};
 use std::io::Cursor;
 
-use super::{BOCRaw, CellRaw, GENERIC_BOC_MAGIC};
+use super::{BOCRaw, CellRaw, CRC_32_ISCSI, GENERIC_BOC_MAGIC};
 
 impl BOCRaw {
+    pub fn from_bytes(serial: &[u8]) -> Result<BOCRaw, TLCoreError> { Self::from_bytes_impl(serial, false) }
+
+    /// Checks crc32c (if present) and keeps stored hashes & depths to verify them in `into_ton_cells`
+    pub fn from_bytes_strict(serial: &[u8]) -> Result<BOCRaw, TLCoreError> { Self::from_bytes_impl(serial, true) }
+
+    fn from_bytes_impl(serial: &[u8], strict: bool) -> Result<BOCRaw, TLCoreError> {
+        let header = BOCHeader::parse(serial)?;
+        let cursor = Cursor::new(serial);
+        let mut reader = ByteReader::endian(cursor, BigEndian);
+        reader.skip(header.cells_offset as u32)?;
+
+        //   cell_data:(tot_cells_size * [ uint8 ])
+        let mut cells = Vec::with_capacity(header.cells_cnt);
+
+        for _ in 0..header.cells_cnt {
+            let cell = read_cell(&mut reader, header.size, strict)?;
+            cells.push(cell);
+        }
+        //   crc32c:has_crc32c?uint32
+        if header.has_crc32c {
+            let crc32c_pos = reader.reader().position() as usize;
+            let stored = u32::from_le_bytes(reader.read::<[u8; 4]>()?);
+            if strict {
+                let actual = CRC_32_ISCSI.checksum(&serial[..crc32c_pos]);
+                if stored != actual {
+                    return Err(TLCoreError::BOCWrongCrc32c { stored, actual });
+                }
+            }
+        }
+
+        Ok(BOCRaw {
+            cells,
+            roots_position: header.roots_position,
+        })
+    }
+}
+
+/// Everything before cell_data. Offsets are positions in serialized BOC
+pub(crate) struct BOCHeader {
+    pub(crate) has_idx: bool,
+    pub(crate) has_crc32c: bool,
+    pub(crate) has_cache_bits: bool,
+    pub(crate) size: u8,
+    pub(crate) off_bytes: u8,
+    pub(crate) cells_cnt: usize,
+    pub(crate) tot_cells_size: usize,
+    pub(crate) roots_position: Vec<usize>,
+    pub(crate) idx_offset: usize,
+    pub(crate) cells_offset: usize,
+}
+
+impl BOCHeader {
     // https://github.com/ton-blockchain/ton/blob/24dc184a2ea67f9c47042b4104bbb4d82289fac1/crypto/tl/boc.tlb#L25
-    pub fn from_bytes(serial: &[u8]) -> Result<BOCRaw, TLCoreError> {
+    pub(crate) fn parse(serial: &[u8]) -> Result<Self, TLCoreError> {
         let cursor = Cursor::new(serial);
         let mut reader = ByteReader::endian(cursor, BigEndian);
         let magic = reader.read::<u32>()?;
@@ -17,7 +70,7 @@ impl BOCRaw {
             return Err(TLCoreError::BOCWrongData(format!("Unexpected magic: {magic}")));
         };
 
-        let (has_idx, has_crc32c, _has_cache_bits, size) = {
+        let (has_idx, has_crc32c, has_cache_bits, size) = {
             // has_idx:(## 1) has_crc32c:(## 1) has_cache_bits:(## 1) flags:(## 2) { flags = 0 }
             let header = reader.read::<u8>()?;
             let has_idx = (header & 0b1000_0000) != 0;
@@ -53,31 +106,46 @@ impl BOCRaw {
             )));
         }
         //   tot_cells_size:(##(off_bytes * 8))
-        let _tot_cells_size = read_var_size(&mut reader, off_bytes)?;
+        let tot_cells_size = read_var_size(&mut reader, off_bytes)?;
+        // counts are used to allocate memory, so check them against the real data size
+        // each cell takes at least 2 bytes (d1 & d2 descriptors)
+        if tot_cells_size > serial.len() || cells_cnt > tot_cells_size / 2 {
+            return Err(TLCoreError::BOCWrongData(format!(
+                "Invalid header: cells({cells_cnt}) with size {tot_cells_size} don't fit into {} bytes",
+                serial.len()
+            )));
+        }
         //   root_list:(roots * ##(size * 8))
         let mut roots_position = vec![];
         for _ in 0..roots_cnt {
             roots_position.push(read_var_size(&mut reader, size)?)
         }
         //   index:has_idx?(cells * ##(off_bytes * 8))
-        if has_idx {
-            let _idx = reader.read_to_vec(cells_cnt * off_bytes as usize)?;
-        }
-        //   cell_data:(tot_cells_size * [ uint8 ])
-        let mut cells = Vec::with_capacity(cells_cnt);
-
-        for _ in 0..cells_cnt {
-            let cell = read_cell(&mut reader, size)?;
-            cells.push(cell);
-        }
-        //   crc32c:has_crc32c?uint32
-        let _crc32c = if has_crc32c { reader.read::<u32>()? } else { 0 };
-
-        Ok(BOCRaw { cells, roots_position })
+        let idx_offset = reader.reader().position() as usize;
+        let cells_offset = match has_idx {
+            true => idx_offset + cells_cnt * off_bytes as usize,
+            false => idx_offset,
+        };
+        Ok(Self {
+            has_idx,
+            has_crc32c,
+            has_cache_bits,
+            size,
+            off_bytes,
+            cells_cnt,
+            tot_cells_size,
+            roots_position,
+            idx_offset,
+            cells_offset,
+        })
     }
 }
 
-fn read_cell(reader: &mut ByteReader<Cursor<&[u8]>, BigEndian>, size: u8) -> Result<CellRaw, TLCoreError> {
+fn read_cell(
+    reader: &mut ByteReader<Cursor<&[u8]>, BigEndian>,
+    size: u8,
+    keep_hashes: bool,
+) -> Result<CellRaw, TLCoreError> {
     let d1 = reader.read::<u8>()?;
     let d2 = reader.read::<u8>()?;
 
@@ -88,12 +156,17 @@ fn read_cell(reader: &mut ByteReader<Cursor<&[u8]>, BigEndian>, size: u8) -> Res
     let data_size = ((d2 >> 1) + (d2 & 1)).into();
     let full_bytes = (d2 & 0x01) == 0;
 
+    let mut stored_hashes = vec![];
     if has_hashes {
         let hash_count = level_mask.hash_count();
-        let skip_size = hash_count * (32 + 2);
-
-        // TODO: check depth and hashes
-        reader.skip(skip_size as u32)?;
+        if keep_hashes {
+            let hashes =
+                (0..hash_count).map(|_| reader.read::<[u8; TonHash::BYTES_LEN]>()).collect::<Result<Vec<_>, _>>()?;
+            let depths = (0..hash_count).map(|_| reader.read::<u16>()).collect::<Result<Vec<_>, _>>()?;
+            stored_hashes = hashes.into_iter().map(TonHash::from).zip(depths).collect();
+        } else {
+            reader.skip((hash_count * (TonHash::BYTES_LEN + 2)) as u32)?;
+        }
     }
 
     let mut data = reader.read_to_vec(data_size)?;
@@ -135,6 +208,7 @@ fn read_cell(reader: &mut ByteReader<Cursor<&[u8]>, BigEndian>, size: u8) -> Res
         data_bits_len,
         refs_positions,
         level_mask,
+        stored_hashes,
     };
     Ok(cell)
 }
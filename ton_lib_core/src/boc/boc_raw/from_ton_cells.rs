@@ -1,127 +1,275 @@
+use crate::boc::BOCSerializeOptions;
+use crate::cell::LevelMask;
 use crate::cell::TonCellRef;
 use crate::cell::TonHash;
 use crate::error::TLCoreError;
-use std::cell::RefCell;
 use std::collections::HashMap;
-use std::ops::Deref;
 
 use super::{BOCRaw, CellRaw};
 
-#[derive(Debug, Clone)]
-struct CellIndexed<'a> {
+// Max weight of cell subtree. Cells with heavier subtrees are "special": they store hashes if requested
+const MAX_CELL_WEIGHT: u32 = 64;
+
+// Positions are assigned the same way as reference node does:
+// https://github.com/ton-blockchain/ton/blob/master/crypto/vm/boc.cpp (import_cells, reorder_cells, revisit)
+// so serialized BOC is byte-identical to the one produced by node for the same flags.
+struct CellInfo<'a> {
     cell: &'a TonCellRef,
-    index: RefCell<usize>, // internal mutability required
+    refs: Vec<usize>,
+    weight: u32,
+    is_root: bool,
+    state: VisitState,
 }
 
-impl BOCRaw {
-    pub fn from_ton_cells(roots: &[TonCellRef]) -> Result<Self, TLCoreError> {
-        let cell_by_hash = build_and_verify_index(roots)?;
+// Steps of pre_visit/visit/allocate traversal, processed with explicit stack
+#[derive(Clone, Copy)]
+enum Step {
+    PreVisit(usize),
+    Visit(usize),
+    Allocate(usize),
+    MarkPreVisited(usize),
+    MarkVisited(usize),
+}
 
-        // Sort indexed cells by their index value.
-        let mut cell_sorted: Vec<_> = cell_by_hash.values().collect();
-        cell_sorted.sort_unstable_by(|a, b| a.index.cmp(&b.index));
+#[derive(Clone, Copy, PartialEq)]
+enum VisitState {
+    New,
+    PreVisited,
+    Visited,
+    Allocated(usize),
+}
+
+impl CellInfo<'_> {
+    fn is_special(&self) -> bool { self.weight == 0 }
+}
 
-        // Remove gaps in indices.
-        cell_sorted
-            .iter()
-            .enumerate()
-            .for_each(|(real_index, indexed_cell)| *indexed_cell.index.borrow_mut() = real_index);
+struct CellsOrder<'a> {
+    cells: Vec<CellInfo<'a>>,
+    pos_by_hash: HashMap<&'a TonHash, usize>,
+    roots: Vec<usize>,
+    allocated: Vec<usize>,
+}
 
-        let raw_cells = cell_sorted
-            .into_iter()
-            .map(|indexed| raw_from_indexed(indexed.cell, &cell_by_hash))
-            .collect::<Result<_, TLCoreError>>()?;
+/// Cells in serialization order. Keeps references to original cells, so cells data is not copied
+pub(crate) struct BOCLayout<'a> {
+    pub(crate) cells: Vec<LayoutCell<'a>>,
+    pub(crate) roots_position: Vec<usize>,
+}
 
-        let root_indices = roots.iter().map(|x| get_position(x, &cell_by_hash)).collect::<Result<_, TLCoreError>>()?;
+pub(crate) struct LayoutCell<'a> {
+    pub(crate) cell: &'a TonCellRef,
+    pub(crate) refs_positions: Vec<usize>,
+    pub(crate) store_hashes: bool,
+}
 
+impl BOCRaw {
+    /// Only `with_top_hash` & `with_int_hashes` options affect cells layout
+    pub fn from_ton_cells(roots: &[TonCellRef], options: &BOCSerializeOptions) -> Result<Self, TLCoreError> {
+        let layout = BOCLayout::new(roots, options)?;
+        let mut raw_cells = Vec::with_capacity(layout.cells.len());
+        for cell in layout.cells {
+            raw_cells.push(raw_from_cell(cell.cell, cell.refs_positions, cell.store_hashes)?);
+        }
         Ok(BOCRaw {
             cells: raw_cells,
-            roots_position: root_indices,
+            roots_position: layout.roots_position,
         })
     }
 }
 
-fn build_and_verify_index(roots: &[TonCellRef]) -> Result<HashMap<TonHash, CellIndexed<'_>>, TLCoreError> {
-    let mut cur_cells = vec![];
-    for cell in roots {
-        cur_cells.push(cell);
+impl<'a> BOCLayout<'a> {
+    pub(crate) fn new(roots: &'a [TonCellRef], options: &BOCSerializeOptions) -> Result<Self, TLCoreError> {
+        let mut order = CellsOrder {
+            cells: vec![],
+            pos_by_hash: HashMap::new(),
+            roots: Vec::with_capacity(roots.len()),
+            allocated: vec![],
+        };
+        for root in roots {
+            let root_pos = order.import_cell(root)?;
+            order.cells[root_pos].is_root = true;
+            order.roots.push(root_pos);
+        }
+        order.calc_weights();
+        order.allocate_positions();
+
+        let cells_count = order.allocated.len();
+        // cells are allocated from leaves to roots, but serialized in reverse order
+        let final_pos = |cell_pos: usize| match order.cells[cell_pos].state {
+            VisitState::Allocated(alloc_pos) => Ok(cells_count - 1 - alloc_pos),
+            _ => Err(TLCoreError::Custom(format!("cell at position {cell_pos} is not allocated"))),
+        };
+
+        let mut cells = Vec::with_capacity(cells_count);
+        for &cell_pos in order.allocated.iter().rev() {
+            let info = &order.cells[cell_pos];
+            cells.push(LayoutCell {
+                cell: info.cell,
+                refs_positions: info.refs.iter().map(|&x| final_pos(x)).collect::<Result<_, _>>()?,
+                store_hashes: (options.with_int_hashes && info.is_special()) || (options.with_top_hash && info.is_root),
+            });
+        }
+        let roots_position = order.roots.iter().map(|&x| final_pos(x)).collect::<Result<_, _>>()?;
+        Ok(Self { cells, roots_position })
     }
-    let mut new_hash_index = 0;
-    let mut cells_by_hash = HashMap::new();
+}
 
-    // Process cells to build the initial index.
-    while !cur_cells.is_empty() {
-        let mut next_cells = Vec::with_capacity(cur_cells.len() * 4);
-        for cell in cur_cells {
-            let hash = cell.hash()?;
+impl<'a> CellsOrder<'a> {
+    // collects cells in post-order, deduplicated by hash
+    fn import_cell(&mut self, root: &'a TonCellRef) -> Result<usize, TLCoreError> {
+        struct Frame<'a> {
+            cell: &'a TonCellRef,
+            refs: Vec<usize>,
+            weight: u32,
+        }
 
-            if cells_by_hash.contains_key(hash) {
-                continue; // Skip if already indexed.
+        let mut stack: Vec<Frame<'a>> = vec![];
+        let mut next_cell = root;
+        loop {
+            let mut imported_pos = self.pos_by_hash.get(next_cell.hash()?).copied();
+            if imported_pos.is_none() {
+                stack.push(Frame {
+                    cell: next_cell,
+                    refs: Vec::with_capacity(next_cell.refs.len()),
+                    weight: 1,
+                });
             }
+            loop {
+                if let Some(pos) = imported_pos.take() {
+                    let Some(parent) = stack.last_mut() else {
+                        return Ok(pos);
+                    };
+                    parent.refs.push(pos);
+                    parent.weight += self.cells[pos].weight;
+                }
+                let frame = stack.last().unwrap(); // not empty: root is returned above
+                let cell: &'a TonCellRef = frame.cell;
+                if frame.refs.len() < cell.refs.len() {
+                    next_cell = &cell.refs[frame.refs.len()];
+                    break;
+                }
+                let frame = stack.pop().unwrap();
+                self.pos_by_hash.insert(cell.hash()?, self.cells.len());
+                self.cells.push(CellInfo {
+                    cell,
+                    refs: frame.refs,
+                    weight: frame.weight.min(255),
+                    is_root: false,
+                    state: VisitState::New,
+                });
+                imported_pos = Some(self.cells.len() - 1);
+            }
+        }
+    }
 
-            let indexed_cell = CellIndexed {
-                cell,
-                index: RefCell::new(new_hash_index),
-            };
-            cells_by_hash.insert(hash.clone(), indexed_cell);
-
-            new_hash_index += 1;
-            next_cells.extend(&cell.refs);
+    fn calc_weights(&mut self) {
+        for pos in (0..self.cells.len()).rev() {
+            let refs_cnt = self.cells[pos].refs.len() as u32;
+            let mut heavy_cnt = refs_cnt;
+            let mut weight_left = MAX_CELL_WEIGHT - 1;
+            let mut light_mask = 0u32;
+            for (i, &ref_pos) in self.cells[pos].refs.iter().enumerate() {
+                let limit = (MAX_CELL_WEIGHT - 1 + i as u32) / refs_cnt;
+                if self.cells[ref_pos].weight <= limit {
+                    weight_left -= self.cells[ref_pos].weight;
+                    heavy_cnt -= 1;
+                    light_mask |= 1 << i;
+                }
+            }
+            if heavy_cnt == 0 {
+                continue;
+            }
+            for i in 0..refs_cnt as usize {
+                if light_mask & (1 << i) != 0 {
+                    continue;
+                }
+                let ref_pos = self.cells[pos].refs[i];
+                let limit = weight_left / heavy_cnt;
+                weight_left += 1;
+                self.cells[ref_pos].weight = self.cells[ref_pos].weight.min(limit);
+            }
+        }
+        for pos in 0..self.cells.len() {
+            let weight = 1 + self.cells[pos].refs.iter().map(|&x| self.cells[x].weight).sum::<u32>();
+            let info = &mut self.cells[pos];
+            info.weight = if weight <= info.weight { weight } else { 0 };
         }
+    }
 
-        cur_cells = next_cells;
+    fn allocate_positions(&mut self) {
+        for root_pos in self.roots.clone() {
+            self.traverse(root_pos);
+        }
+        for root_pos in self.roots.clone() {
+            self.allocate(root_pos);
+        }
     }
 
-    // Ensure indices are in the correct order based on cell references.
-    let mut verify_order = true;
-    while verify_order {
-        verify_order = false;
-
-        for index_cell in cells_by_hash.values() {
-            for ref_pos in 0..index_cell.cell.refs.len() {
-                let ref_cell = &index_cell.cell.refs[ref_pos];
-                let ref_hash = ref_cell.hash()?;
-                if let Some(indexed) = cells_by_hash.get(ref_hash) {
-                    if indexed.index < index_cell.index {
-                        *indexed.index.borrow_mut() = new_hash_index;
-                        new_hash_index += 1;
-                        verify_order = true; // Verify if an index was updated.
+    // pre_visit & visit of root. Steps are pushed in reverse order, so they run in the same order as recursion:
+    // - pre_visit: goes down until special cells are found, and visits them
+    // - visit: visits all children, then allocates them
+    fn traverse(&mut self, root_pos: usize) {
+        let mut stack = vec![Step::Visit(root_pos), Step::PreVisit(root_pos)];
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::PreVisit(pos) => {
+                    if self.cells[pos].state != VisitState::New {
+                        continue;
+                    }
+                    stack.push(Step::MarkPreVisited(pos));
+                    for &ref_pos in &self.cells[pos].refs {
+                        match self.cells[ref_pos].is_special() {
+                            true => stack.push(Step::Visit(ref_pos)),
+                            false => stack.push(Step::PreVisit(ref_pos)),
+                        }
+                    }
+                }
+                Step::Visit(pos) => {
+                    if matches!(self.cells[pos].state, VisitState::Visited | VisitState::Allocated(_)) {
+                        continue;
+                    }
+                    stack.push(Step::MarkVisited(pos));
+                    stack.extend(self.cells[pos].refs.iter().map(|&x| Step::Allocate(x)));
+                    stack.extend(self.cells[pos].refs.iter().map(|&x| Step::Visit(x)));
+                    if self.cells[pos].is_special() {
+                        stack.push(Step::PreVisit(pos));
                     }
                 }
+                Step::Allocate(pos) => self.allocate(pos),
+                Step::MarkPreVisited(pos) => self.cells[pos].state = VisitState::PreVisited,
+                Step::MarkVisited(pos) => self.cells[pos].state = VisitState::Visited,
             }
         }
     }
 
-    Ok(cells_by_hash)
+    fn allocate(&mut self, pos: usize) {
+        if matches!(self.cells[pos].state, VisitState::Allocated(_)) {
+            return;
+        }
+        self.cells[pos].state = VisitState::Allocated(self.allocated.len());
+        self.allocated.push(pos);
+    }
 }
 
-fn raw_from_indexed(cell: &TonCellRef, cells_by_hash: &HashMap<TonHash, CellIndexed>) -> Result<CellRaw, TLCoreError> {
-    let refs_positions = raw_cell_refs_indexes(cell, cells_by_hash)?;
+pub(crate) fn raw_from_cell(
+    cell: &TonCellRef,
+    refs_positions: Vec<usize>,
+    store_hashes: bool,
+) -> Result<CellRaw, TLCoreError> {
+    let level_mask = cell.level_mask();
+    let mut stored_hashes = vec![];
+    if store_hashes {
+        for level in (0..=level_mask.level()).filter(|&level| level_mask.is_significant(level)) {
+            let level = LevelMask::new(level);
+            stored_hashes.push((cell.hash_for_level(level)?.clone(), cell.depth_for_level(level)?));
+        }
+    }
     Ok(CellRaw {
         cell_type: cell.cell_type,
         data: cell.data.clone(),
         data_bits_len: cell.data_bits_len,
         refs_positions,
-        level_mask: cell.level_mask(),
+        level_mask,
+        stored_hashes,
     })
 }
-
-fn raw_cell_refs_indexes(
-    cell: &TonCellRef,
-    cells_by_hash: &HashMap<TonHash, CellIndexed>,
-) -> Result<Vec<usize>, TLCoreError> {
-    let mut vec = Vec::with_capacity(cell.refs.len());
-    for ref_pos in 0..cell.refs.len() {
-        let cell_ref = &cell.refs[ref_pos];
-        vec.push(get_position(cell_ref, cells_by_hash)?);
-    }
-    Ok(vec)
-}
-
-fn get_position(cell: &TonCellRef, call_by_hash: &HashMap<TonHash, CellIndexed>) -> Result<usize, TLCoreError> {
-    let hash = cell.hash()?;
-    call_by_hash
-        .get(hash)
-        .ok_or_else(|| TLCoreError::Custom(format!("cell with hash {hash:?} not found in available hashes")))
-        .map(|indexed_cell| *indexed_cell.index.borrow().deref())
-}
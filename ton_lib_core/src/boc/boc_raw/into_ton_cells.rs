@@ -1,4 +1,4 @@
-use crate::cell::{CellMeta, TonCell, TonCellRef};
+use crate::cell::{CellMeta, LevelMask, TonCell, TonCellRef, TonHash};
 use crate::error::TLCoreError;
 
 use super::BOCRaw;
@@ -9,7 +9,8 @@ impl BOCRaw {
         let cells_len = self.cells.len();
         let mut cells: Vec<TonCellRef> = Vec::with_capacity(cells_len);
 
-        for (cell_index, cell_raw) in self.cells.into_iter().enumerate().rev() {
+        for (cell_index, mut cell_raw) in self.cells.into_iter().enumerate().rev() {
+            let stored_hashes = std::mem::take(&mut cell_raw.stored_hashes);
             let mut refs = Vec::with_capacity(cell_raw.refs_positions.len());
             for ref_index in cell_raw.refs_positions {
                 if ref_index <= cell_index {
@@ -18,15 +19,18 @@ impl BOCRaw {
                 refs.push(cells[cells_len - 1 - ref_index].clone());
             }
 
-            let cell_ref = TonCell {
+            let cell = TonCell {
                 cell_type: cell_raw.cell_type,
                 data: cell_raw.data,
                 data_bits_len: cell_raw.data_bits_len,
                 refs,
                 meta: CellMeta::default(),
+            };
+            cell.meta.validate(&cell)?;
+            if !stored_hashes.is_empty() {
+                verify_stored_hashes(&cell, cell_index, &stored_hashes)?;
             }
-            .into_ref();
-            cells.push(cell_ref);
+            cells.push(cell.into_ref());
         }
 
         let mut roots = Vec::with_capacity(self.roots_position.len());
@@ -36,3 +40,33 @@ impl BOCRaw {
         Ok(roots)
     }
 }
+
+fn verify_stored_hashes(cell: &TonCell, cell_pos: usize, stored: &[(TonHash, u16)]) -> Result<(), TLCoreError> {
+    let level_mask = cell.level_mask();
+    if stored.len() != level_mask.hash_count() {
+        let err_msg = format!("cell[{cell_pos}] stores {} hashes, expected {}", stored.len(), level_mask.hash_count());
+        return Err(TLCoreError::BOCWrongData(err_msg));
+    }
+    let sign_levels = (0..=level_mask.level()).filter(|&level| level_mask.is_significant(level));
+    for ((stored_hash, stored_depth), level) in stored.iter().zip(sign_levels) {
+        let actual_hash = cell.hash_for_level(LevelMask::new(level))?;
+        if actual_hash != stored_hash {
+            return Err(TLCoreError::BOCWrongCellHash {
+                cell_pos,
+                level,
+                stored: stored_hash.clone(),
+                actual: actual_hash.clone(),
+            });
+        }
+        let actual_depth = cell.depth_for_level(LevelMask::new(level))?;
+        if actual_depth != *stored_depth {
+            return Err(TLCoreError::BOCWrongCellDepth {
+                cell_pos,
+                level,
+                stored: *stored_depth,
+                actual: actual_depth,
+            });
+        }
+    }
+    Ok(())
+}
@@ -3,10 +3,17 @@ mod from_ton_cells;
 mod into_ton_cells;
 mod to_bytes;
 
+pub(crate) use from_bytes::BOCHeader;
+pub(crate) use from_ton_cells::BOCLayout;
+pub(crate) use to_bytes::BOCWriter;
+
 use crate::cell::CellType;
 use crate::cell::LevelMask;
+use crate::cell::TonHash;
+use crc::Crc;
 
 pub const GENERIC_BOC_MAGIC: u32 = 0xb5ee9c72;
+pub(crate) static CRC_32_ISCSI: Crc<u32> = Crc::<u32>::new(&crc::CRC_32_ISCSI);
 /// `cells` must be topologically sorted.
 #[derive(PartialEq, Debug, Clone)]
 pub struct BOCRaw {
@@ -22,4 +29,6 @@ pub struct CellRaw {
     pub data_bits_len: usize,
     pub refs_positions: Vec<usize>,
     pub level_mask: LevelMask,
+    /// Hashes & depths for significant levels, stored in BOC. Empty if absent or not requested
+    pub stored_hashes: Vec<(TonHash, u16)>,
 }
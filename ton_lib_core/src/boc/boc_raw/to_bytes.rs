@@ -1,81 +1,235 @@
+use crate::boc::BOCSerializeOptions;
+use crate::cell::TonHash;
 use crate::error::TLCoreError;
 use bitstream_io::{BigEndian, BitWrite, BitWriter};
-use crc::Crc;
+use crc::Digest;
+use std::borrow::Cow;
+use std::io::Write;
 
-use super::{BOCRaw, CellRaw, GENERIC_BOC_MAGIC};
+use super::from_ton_cells::{raw_from_cell, BOCLayout};
+use super::{BOCRaw, CellRaw, CRC_32_ISCSI, GENERIC_BOC_MAGIC};
 
-const CRC_32_ISCSI: Crc<u32> = Crc::<u32>::new(&crc::CRC_32_ISCSI);
+/// Cells to serialize, already in serialization order
+pub(crate) trait BOCCells {
+    fn roots_position(&self) -> &[usize];
+    fn cells_count(&self) -> usize;
+    fn refs_positions(&self, cell_pos: usize) -> &[usize];
+    fn cell_size(&self, cell_pos: usize, ref_size_bytes: u32) -> u32;
+    fn raw_cell(&self, cell_pos: usize) -> Result<Cow<'_, CellRaw>, TLCoreError>;
+}
 
 impl BOCRaw {
     //Based on https://github.com/toncenter/tonweb/blob/c2d5d0fc23d2aec55a0412940ce6e580344a288c/src/boc/Cell.js#L198
-    pub fn to_bytes(&self, has_crc32: bool) -> Result<Vec<u8>, TLCoreError> {
-        let root_count = self.roots_position.len();
-        let num_ref_bits = 32 - (self.cells.len() as u32).leading_zeros();
-        let num_ref_bytes = num_ref_bits.div_ceil(8);
-        let has_idx = false;
+    pub fn to_bytes(&self, options: &BOCSerializeOptions) -> Result<Vec<u8>, TLCoreError> {
+        let writer = BOCWriter::new(self, options)?;
+        let mut bytes = Vec::with_capacity(writer.total_size());
+        writer.write(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Writes BOC to any `io::Write` cell by cell. Output is buffered only by `dst` itself
+pub(crate) struct BOCWriter<'a, C: BOCCells> {
+    cells: &'a C,
+    options: &'a BOCSerializeOptions,
+    num_ref_bytes: u32,
+    num_offset_bytes: u32,
+    full_size: u64,
+    // offset of each cell end, used for index
+    cells_end: Vec<u64>,
+}
 
-        let mut full_size = 0u32;
+impl<'a, C: BOCCells> BOCWriter<'a, C> {
+    pub(crate) fn new(cells: &'a C, options: &'a BOCSerializeOptions) -> Result<Self, TLCoreError> {
+        if options.has_cache_bits && !options.has_idx {
+            return Err(TLCoreError::BOCWrongData("has_cache_bits requires has_idx".to_string()));
+        }
+        let num_ref_bits = 32 - (cells.cells_count() as u32).leading_zeros();
+        let num_ref_bytes = fit_bytes_len("size", num_ref_bits.div_ceil(8).max(1), options.size, 4)?;
 
-        for cell in &self.cells {
-            full_size += raw_cell_size(cell, num_ref_bytes);
+        let mut full_size = 0u64;
+        let mut cells_end = Vec::with_capacity(if options.has_idx { cells.cells_count() } else { 0 });
+        for cell_pos in 0..cells.cells_count() {
+            full_size += cells.cell_size(cell_pos, num_ref_bytes) as u64;
+            if options.has_idx {
+                cells_end.push(full_size);
+            }
         }
 
-        let num_offset_bits = 32 - full_size.leading_zeros();
-        let num_offset_bytes = num_offset_bits.div_ceil(8);
+        let max_offset = if options.has_cache_bits {
+            full_size * 2 + 1
+        } else {
+            full_size
+        };
+        let num_offset_bits = 64 - max_offset.leading_zeros();
+        let num_offset_bytes = fit_bytes_len("off_bytes", num_offset_bits.div_ceil(8).max(1), options.off_bytes, 8)?;
+        Ok(Self {
+            cells,
+            options,
+            num_ref_bytes,
+            num_offset_bytes,
+            full_size,
+            cells_end,
+        })
+    }
 
+    pub(crate) fn total_size(&self) -> usize {
+        let cells_count = self.cells.cells_count() as u64;
         let total_size = 4 + // magic
             1 + // flags and s_bytes
             1 + // offset_bytes
-            3 * num_ref_bytes + // cells_num, roots, complete
-            num_offset_bytes + // full_size
-            num_ref_bytes + // root_idx
-            (if has_idx { self.cells.len() as u32 * num_offset_bytes } else { 0 }) +
-            full_size +
-            (if has_crc32 { 4 } else { 0 });
-
-        let mut writer = BitWriter::endian(Vec::with_capacity(total_size as usize), BigEndian);
+            3 * self.num_ref_bytes as u64 + // cells_num, roots, complete
+            self.num_offset_bytes as u64 + // full_size
+            self.cells.roots_position().len() as u64 * self.num_ref_bytes as u64 + // root_list
+            (if self.options.has_idx { cells_count * self.num_offset_bytes as u64 } else { 0 }) +
+            self.full_size +
+            (if self.options.has_crc32c { 4 } else { 0 });
+        total_size as usize
+    }
+
+    pub(crate) fn write<W: Write>(self, dst: W) -> Result<(), TLCoreError> {
+        let (options, num_ref_bytes, num_offset_bytes) = (self.options, self.num_ref_bytes, self.num_offset_bytes);
+        let crc32c_writer = Crc32cWriter {
+            inner: dst,
+            digest: options.has_crc32c.then(|| CRC_32_ISCSI.digest()),
+        };
+        let mut writer = BitWriter::endian(crc32c_writer, BigEndian);
         writer.write_var(32, GENERIC_BOC_MAGIC)?;
-        writer.write_bit(has_idx)?;
-        writer.write_bit(has_crc32)?;
-        writer.write_bit(false)?; // has_cache_bits
+        writer.write_bit(options.has_idx)?;
+        writer.write_bit(options.has_crc32c)?;
+        writer.write_bit(options.has_cache_bits)?;
         writer.write_var(2, 0)?; // flags
         writer.write_var(3, num_ref_bytes)?;
         writer.write_var(8, num_offset_bytes)?;
-        writer.write_var(8 * num_ref_bytes, self.cells.len() as u32)?;
-        writer.write_var(8 * num_ref_bytes, root_count as u32)?;
+        writer.write_var(8 * num_ref_bytes, self.cells.cells_count() as u32)?;
+        writer.write_var(8 * num_ref_bytes, self.cells.roots_position().len() as u32)?;
         writer.write_var(8 * num_ref_bytes, 0)?; // Complete BOCs only
-        writer.write_var(8 * num_offset_bytes, full_size)?;
+        writer.write_var(8 * num_offset_bytes, self.full_size)?;
 
-        for &root in &self.roots_position {
+        for &root in self.cells.roots_position() {
             writer.write_var(8 * num_ref_bytes, root as u32)?;
         }
 
-        for cell in &self.cells {
-            write_raw_cell(&mut writer, cell, num_ref_bytes)?;
+        if options.has_idx {
+            let should_cache = match options.has_cache_bits {
+                true => calc_should_cache(self.cells),
+                false => vec![],
+            };
+            for (cell_pos, cell_end) in self.cells_end.into_iter().enumerate() {
+                let offset = match options.has_cache_bits {
+                    true => cell_end * 2 + should_cache[cell_pos] as u64,
+                    false => cell_end,
+                };
+                writer.write_var(8 * num_offset_bytes, offset)?;
+            }
+        }
+
+        for cell_pos in 0..self.cells.cells_count() {
+            let raw_cell = self.cells.raw_cell(cell_pos)?;
+            write_raw_cell(&mut writer, &raw_cell, num_ref_bytes)?;
         }
         writer.byte_align()?;
-        let mut bytes = writer.into_writer();
-        if has_crc32 {
-            bytes.extend(CRC_32_ISCSI.checksum(bytes.as_slice()).to_le_bytes());
+        let mut crc32c_writer = writer.into_writer();
+        if let Some(digest) = crc32c_writer.digest.take() {
+            crc32c_writer.inner.write_all(&digest.finalize().to_le_bytes())?;
         }
-        Ok(bytes)
+        crc32c_writer.inner.flush()?;
+        Ok(())
+    }
+}
+
+impl BOCCells for BOCRaw {
+    fn roots_position(&self) -> &[usize] { &self.roots_position }
+    fn cells_count(&self) -> usize { self.cells.len() }
+    fn refs_positions(&self, cell_pos: usize) -> &[usize] { &self.cells[cell_pos].refs_positions }
+    fn cell_size(&self, cell_pos: usize, ref_size_bytes: u32) -> u32 {
+        let cell = &self.cells[cell_pos];
+        raw_cell_size(cell.data_bits_len, cell.stored_hashes.len(), cell.refs_positions.len(), ref_size_bytes)
+    }
+    fn raw_cell(&self, cell_pos: usize) -> Result<Cow<'_, CellRaw>, TLCoreError> {
+        Ok(Cow::Borrowed(&self.cells[cell_pos]))
+    }
+}
+
+// CellRaw is built for each cell right before writing it, so only one cell is copied at a time
+impl BOCCells for BOCLayout<'_> {
+    fn roots_position(&self) -> &[usize] { &self.roots_position }
+    fn cells_count(&self) -> usize { self.cells.len() }
+    fn refs_positions(&self, cell_pos: usize) -> &[usize] { &self.cells[cell_pos].refs_positions }
+    fn cell_size(&self, cell_pos: usize, ref_size_bytes: u32) -> u32 {
+        let layout_cell = &self.cells[cell_pos];
+        let hashes_count = match layout_cell.store_hashes {
+            true => layout_cell.cell.level_mask().hash_count(),
+            false => 0,
+        };
+        let (data_bits_len, refs_count) = (layout_cell.cell.data_bits_len, layout_cell.refs_positions.len());
+        raw_cell_size(data_bits_len, hashes_count, refs_count, ref_size_bytes)
+    }
+    fn raw_cell(&self, cell_pos: usize) -> Result<Cow<'_, CellRaw>, TLCoreError> {
+        let layout_cell = &self.cells[cell_pos];
+        let refs_positions = layout_cell.refs_positions.clone();
+        Ok(Cow::Owned(raw_from_cell(layout_cell.cell, refs_positions, layout_cell.store_hashes)?))
     }
 }
 
-fn raw_cell_size(cell: &CellRaw, ref_size_bytes: u32) -> u32 {
-    let data_len = cell.data_bits_len.div_ceil(8);
-    2 + data_len as u32 + cell.refs_positions.len() as u32 * ref_size_bytes
+struct Crc32cWriter<W: Write> {
+    inner: W,
+    digest: Option<Digest<'static, u32>>,
+}
+
+impl<W: Write> Write for Crc32cWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if let Some(digest) = &mut self.digest {
+            digest.update(&buf[..written]);
+        }
+        Ok(written)
+    }
+    fn flush(&mut self) -> std::io::Result<()> { self.inner.flush() }
 }
 
-fn write_raw_cell(
-    writer: &mut BitWriter<Vec<u8>, BigEndian>,
+// cell is worth caching if it's referenced more than once (roots are counted as references)
+fn calc_should_cache(cells: &impl BOCCells) -> Vec<bool> {
+    let mut parents_count = vec![0u32; cells.cells_count()];
+    for &root_pos in cells.roots_position() {
+        parents_count[root_pos] += 1;
+    }
+    for cell_pos in 0..cells.cells_count() {
+        for &ref_pos in cells.refs_positions(cell_pos) {
+            parents_count[ref_pos] += 1;
+        }
+    }
+    parents_count.into_iter().map(|count| count > 1).collect()
+}
+
+fn fit_bytes_len(name: &str, required: u32, requested: Option<u8>, max: u32) -> Result<u32, TLCoreError> {
+    let Some(requested) = requested else {
+        return Ok(required);
+    };
+    let requested = requested as u32;
+    if requested < required || requested > max {
+        let err_msg = format!("{name}={requested} is out of range [{required}, {max}]");
+        return Err(TLCoreError::BOCWrongData(err_msg));
+    }
+    Ok(requested)
+}
+
+fn raw_cell_size(data_bits_len: usize, hashes_count: usize, refs_count: usize, ref_size_bytes: u32) -> u32 {
+    let data_len = data_bits_len.div_ceil(8);
+    let hashes_len = hashes_count * (TonHash::BYTES_LEN + 2);
+    2 + hashes_len as u32 + data_len as u32 + refs_count as u32 * ref_size_bytes
+}
+
+fn write_raw_cell<W: Write>(
+    writer: &mut BitWriter<W, BigEndian>,
     cell: &CellRaw,
     ref_size_bytes: u32,
 ) -> Result<(), TLCoreError> {
     let level = cell.level_mask;
     let is_exotic = cell.cell_type.is_exotic() as u32;
+    let has_hashes = !cell.stored_hashes.is_empty() as u32;
     let num_refs = cell.refs_positions.len() as u32;
-    let d1 = num_refs + is_exotic * 8 + level.mask() as u32 * 32;
+    let d1 = num_refs + is_exotic * 8 + has_hashes * 16 + level.mask() as u32 * 32;
 
     let padding_bits = cell.data_bits_len % 8;
     let full_bytes = padding_bits == 0;
@@ -86,6 +240,13 @@ fn write_raw_cell(
     writer.write_var(8, d1)?;
     writer.write_var(8, d2)?;
 
+    for (hash, _) in &cell.stored_hashes {
+        writer.write_bytes(hash.as_slice())?;
+    }
+    for (_, depth) in &cell.stored_hashes {
+        writer.write_var(16, *depth)?;
+    }
+
     let full_bytes = cell.data_bits_len / 8;
     writer.write_bytes(&cell.data[0..full_bytes])?;
     let rest_bits_len = cell.data_bits_len % 8;
@@ -2,13 +2,19 @@
 /// Provides the basic types to interact with the TON blockchain:
 /// TonHash, TonCell, TonCellRef, CellBuilder, CellParser
 ///
+mod boc_lazy;
+mod boc_options;
 mod boc_raw;
 
-use crate::boc::boc_raw::BOCRaw;
+pub use boc_lazy::*;
+pub use boc_options::*;
+
+use crate::boc::boc_raw::{BOCLayout, BOCRaw, BOCWriter};
 use crate::cell::{TonCell, TonCellRef, TonCellStorage};
 use crate::error::TLCoreError;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use std::io::Write;
 use std::marker::PhantomData;
 
 pub struct BOC<C = TonCell> {
@@ -41,6 +47,18 @@ impl BOC {
         })
     }
 
+    /// Like `from_bytes`, but also verifies crc32c and stored cell hashes & depths (if BOC has them)
+    pub fn from_bytes_strict<T: AsRef<[u8]>>(bytes: T) -> Result<Self, TLCoreError> {
+        let bytes_ref = bytes.as_ref();
+        if bytes_ref.is_empty() {
+            return Err(TLCoreError::BOCEmpty);
+        }
+        Ok(Self {
+            roots: BOCRaw::from_bytes_strict(bytes_ref)?.into_ton_cells()?,
+            _phantom: PhantomData,
+        })
+    }
+
     pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, TLCoreError> {
         Self::from_bytes(hex::decode(hex.as_ref())?)
     }
@@ -49,7 +67,20 @@ impl BOC {
     }
 
     pub fn to_bytes(&self, add_crc32: bool) -> Result<Vec<u8>, TLCoreError> {
-        BOCRaw::from_ton_cells(self.roots.as_slice())?.to_bytes(add_crc32)
+        let options = BOCSerializeOptions {
+            has_crc32c: add_crc32,
+            ..Default::default()
+        };
+        self.to_bytes_with(&options)
+    }
+    pub fn to_bytes_with(&self, options: &BOCSerializeOptions) -> Result<Vec<u8>, TLCoreError> {
+        BOCRaw::from_ton_cells(&self.roots, options)?.to_bytes(options)
+    }
+    /// Streams BOC into `dst` without building serialized cells in memory. Output is the same as `to_bytes_with`
+    /// Writes are small, so wrap unbuffered `dst` (e.g. `File`) into `BufWriter`
+    pub fn write_to<W: Write>(&self, dst: W, options: &BOCSerializeOptions) -> Result<(), TLCoreError> {
+        let layout = BOCLayout::new(&self.roots, options)?;
+        BOCWriter::new(&layout, options)?.write(dst)
     }
     pub fn to_hex(&self, add_crc32: bool) -> Result<String, TLCoreError> { Ok(hex::encode(self.to_bytes(add_crc32)?)) }
     pub fn to_base64(&self, add_crc32: bool) -> Result<String, TLCoreError> {
@@ -62,6 +93,8 @@ impl BOC {
         }
         Ok(self.roots.pop().unwrap())
     }
+
+    pub fn into_roots(self) -> TonCellStorage { self.roots }
 }
 
 #[cfg(test)]
@@ -107,4 +140,191 @@ mod tests {
         assert_eq!(boc_hex, serial_hex);
         Ok(())
     }
+
+    // single ordinary cell without refs, stored with hash & depth and crc32c
+    fn make_boc_with_hashes(cell: &TonCell) -> anyhow::Result<Vec<u8>> {
+        let data_len = cell.data.len() as u8;
+        let mut bytes = vec![
+            0xb5,
+            0xee,
+            0x9c,
+            0x72,
+            0x41,
+            0x01,
+            0x01,
+            0x01,
+            0x00,
+            2 + 34 + data_len,
+            0x00,
+        ];
+        bytes.extend([0x10, data_len * 2]);
+        bytes.extend(cell.hash()?.as_slice());
+        bytes.extend(cell.depth()?.to_be_bytes());
+        bytes.extend(&cell.data);
+        bytes.extend(boc_raw::CRC_32_ISCSI.checksum(&bytes).to_le_bytes());
+        Ok(bytes)
+    }
+
+    fn fix_crc32c(bytes: &mut [u8]) {
+        let crc_pos = bytes.len() - 4;
+        let crc32c = boc_raw::CRC_32_ISCSI.checksum(&bytes[..crc_pos]);
+        bytes[crc_pos..].copy_from_slice(&crc32c.to_le_bytes());
+    }
+
+    #[test]
+    fn test_boc_from_bytes_strict() -> anyhow::Result<()> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xDEADBEEFu32, 32)?;
+        let cell = builder.build()?;
+        let bytes = make_boc_with_hashes(&cell)?;
+        assert_eq!(BOC::from_bytes_strict(&bytes)?.single_root()?.hash()?, cell.hash()?);
+        assert_eq!(BOC::from_bytes(&bytes)?.single_root()?.hash()?, cell.hash()?);
+
+        let mut wrong_crc = bytes.clone();
+        *wrong_crc.last_mut().unwrap() ^= 1;
+        assert!(matches!(BOC::from_bytes_strict(&wrong_crc), Err(TLCoreError::BOCWrongCrc32c { .. })));
+        assert!(BOC::from_bytes(&wrong_crc).is_ok());
+
+        let mut wrong_hash = bytes.clone();
+        wrong_hash[13] ^= 1;
+        fix_crc32c(&mut wrong_hash);
+        let result = BOC::from_bytes_strict(&wrong_hash);
+        assert!(matches!(
+            result,
+            Err(TLCoreError::BOCWrongCellHash {
+                cell_pos: 0,
+                level: 0,
+                ..
+            })
+        ));
+        assert!(BOC::from_bytes(&wrong_hash).is_ok());
+
+        let mut wrong_depth = bytes.clone();
+        wrong_depth[13 + 32 + 1] = 1;
+        fix_crc32c(&mut wrong_depth);
+        let result = BOC::from_bytes_strict(&wrong_depth);
+        assert!(matches!(
+            result,
+            Err(TLCoreError::BOCWrongCellDepth {
+                stored: 1,
+                actual: 0,
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    fn make_multi_root_boc() -> anyhow::Result<BOC> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&0x0Au8, 8)?;
+        let leaf = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&0x0Bu8, 8)?;
+        builder.write_ref(leaf.clone())?;
+        let parent = builder.build_ref()?;
+        Ok(BOC::from_roots(vec![parent, leaf]))
+    }
+
+    #[test]
+    fn test_boc_multi_root() -> anyhow::Result<()> {
+        let boc = make_multi_root_boc()?;
+        let expected_hex = "b5ee9c72010102020007000101020b0100020a";
+        assert_eq!(boc.to_hex(false)?, expected_hex);
+
+        let parsed = BOC::from_hex(expected_hex)?;
+        assert_eq!(parsed.roots, boc.roots);
+        Ok(())
+    }
+
+    #[test]
+    fn test_boc_serialize_options() -> anyhow::Result<()> {
+        let boc = make_multi_root_boc()?;
+        // leaf is referenced by parent and by roots list, so it's marked as cached in index
+        let options = BOCSerializeOptions {
+            has_idx: true,
+            has_cache_bits: true,
+            ..Default::default()
+        };
+        assert_eq!(hex::encode(boc.to_bytes_with(&options)?), "b5ee9c72a101020200070001080f01020b0100020a");
+
+        let options = BOCSerializeOptions {
+            has_crc32c: true,
+            with_top_hash: true,
+            size: Some(2),
+            off_bytes: Some(3),
+            ..Default::default()
+        };
+        let bytes = boc.to_bytes_with(&options)?;
+        assert_eq!(&bytes[4..6], &[0x42, 0x03]);
+        let parsed = BOC::from_bytes_strict(&bytes)?;
+        assert_eq!(parsed.roots, boc.roots);
+
+        let options = BOCSerializeOptions {
+            has_cache_bits: true,
+            ..Default::default()
+        };
+        assert!(matches!(boc.to_bytes_with(&options), Err(TLCoreError::BOCWrongData(_))));
+        let options = BOCSerializeOptions {
+            off_bytes: Some(9),
+            ..Default::default()
+        };
+        assert!(matches!(boc.to_bytes_with(&options), Err(TLCoreError::BOCWrongData(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_boc_write_to() -> anyhow::Result<()> {
+        let boc = make_multi_root_boc()?;
+        let all_options = [
+            BOCSerializeOptions::default(),
+            BOCSerializeOptions {
+                has_idx: true,
+                has_crc32c: true,
+                has_cache_bits: true,
+                with_top_hash: true,
+                with_int_hashes: true,
+                ..Default::default()
+            },
+            BOCSerializeOptions {
+                has_crc32c: true,
+                size: Some(3),
+                off_bytes: Some(5),
+                ..Default::default()
+            },
+        ];
+        for options in all_options {
+            let mut bytes = vec![];
+            boc.write_to(&mut bytes, &options)?;
+            assert_eq!(bytes, boc.to_bytes_with(&options)?);
+        }
+        let options = BOCSerializeOptions {
+            has_cache_bits: true,
+            ..Default::default()
+        };
+        assert!(matches!(boc.write_to(vec![], &options), Err(TLCoreError::BOCWrongData(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_boc_deep_cells_chain() -> anyhow::Result<()> {
+        const DEPTH: u32 = 500;
+        let mut cell = TonCell::EMPTY.into_ref();
+        for i in 0..DEPTH {
+            let mut builder = TonCell::builder();
+            builder.write_num(&i, 32)?;
+            builder.write_ref(cell)?;
+            cell = builder.build_ref()?;
+        }
+        let boc = BOC::from_roots(vec![cell]);
+        let options = BOCSerializeOptions {
+            with_int_hashes: true,
+            ..Default::default()
+        };
+        let raw = BOCRaw::from_ton_cells(&boc.roots, &options)?;
+        assert_eq!(raw.cells.len(), DEPTH as usize + 1);
+        assert_eq!(raw.roots_position, vec![0]);
+        let parsed = BOC::from_bytes(raw.to_bytes(&options)?)?;
+        assert_eq!(parsed.roots, boc.roots);
+        Ok(())
+    }
 }
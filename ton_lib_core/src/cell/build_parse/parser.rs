@@ -1,26 +1,73 @@
+use crate::boc::LazyCell;
+use crate::cell::merkle::VisitedCells;
 use crate::cell::ton_cell::{TonCell, TonCellRef};
 use crate::cell::ton_cell_num::TonCellNum;
 use crate::error::TLCoreError;
 use bitstream_io::{BigEndian, BitRead, BitReader};
 use num_traits::Zero;
 use std::io::{Cursor, SeekFrom};
+use std::ops::Deref;
 
 #[derive(Debug)]
 pub struct CellParser<'a> {
-    pub cell: &'a TonCell,
     pub next_ref_pos: usize,
+    source: ParserSource<'a>,
     data_reader: BitReader<Cursor<&'a [u8]>, BigEndian>,
+    tracker: Option<&'a VisitedCells>,
+}
+
+// Lazy source decodes refs from BOC bytes only when parser walks into them
+#[derive(Debug, Clone, Copy)]
+enum ParserSource<'a> {
+    Cell(&'a TonCell),
+    Lazy(LazyCell<'a>),
 }
 
 impl<'a> CellParser<'a> {
-    pub fn new(cell: &'a TonCell) -> Self {
-        let cursor = Cursor::new(cell.data.as_slice());
-        let data_reader = BitReader::endian(cursor, BigEndian);
+    pub fn new(cell: &'a TonCell) -> Self { Self::with_source(ParserSource::Cell(cell), cell.data.as_slice()) }
+
+    pub(crate) fn new_lazy(cell: LazyCell<'a>) -> Self { Self::with_source(ParserSource::Lazy(cell), cell.data) }
 
+    /// Marks parsed cell and every ref read by this parser in `tracker`.
+    /// Parsers from `read_next_ref_parser` inherit the tracker
+    pub fn with_tracker(mut self, tracker: &'a VisitedCells) -> Result<Self, TLCoreError> {
+        tracker.visit(self.cell()?)?;
+        self.tracker = Some(tracker);
+        Ok(self)
+    }
+
+    fn with_source(source: ParserSource<'a>, data: &'a [u8]) -> Self {
+        let data_reader = BitReader::endian(Cursor::new(data), BigEndian);
         Self {
-            cell,
-            data_reader,
             next_ref_pos: 0,
+            source,
+            data_reader,
+            tracker: None,
+        }
+    }
+
+    /// The whole parsed cell (replaces former public `cell` field).
+    ///
+    /// Never fails for parsers created from `TonCell`. For lazy BOC sources the cell is decoded
+    /// with all its subtree, so it fails if any cell of the subtree is malformed
+    pub fn cell(&self) -> Result<&'a TonCell, TLCoreError> {
+        match self.source {
+            ParserSource::Cell(cell) => Ok(cell),
+            ParserSource::Lazy(cell) => Ok(cell.cell_ref()?.deref()),
+        }
+    }
+
+    pub fn data_bits_len(&self) -> usize {
+        match self.source {
+            ParserSource::Cell(cell) => cell.data_bits_len,
+            ParserSource::Lazy(cell) => cell.data_bits_len,
+        }
+    }
+
+    pub fn refs_count(&self) -> usize {
+        match self.source {
+            ParserSource::Cell(cell) => cell.refs.len(),
+            ParserSource::Lazy(cell) => cell.refs_count(),
         }
     }
 
@@ -107,24 +154,53 @@ impl<'a> CellParser<'a> {
         builder.build()
     }
 
-    pub fn read_next_ref(&mut self) -> Result<&TonCellRef, TLCoreError> {
-        if self.next_ref_pos == self.cell.refs.len() {
+    /// Lazy cell ref is decoded with all its subtree. Use `read_next_ref_parser` to keep it lazy
+    pub fn read_next_ref(&mut self) -> Result<&'a TonCellRef, TLCoreError> {
+        if self.next_ref_pos == self.refs_count() {
             return Err(TLCoreError::ParserRefsUnderflow { req: self.next_ref_pos });
         }
-        let cell_ref = &self.cell.refs[self.next_ref_pos];
+        let cell_ref = match self.source {
+            ParserSource::Cell(cell) => &cell.refs[self.next_ref_pos],
+            ParserSource::Lazy(cell) => cell.reference(self.next_ref_pos)?.cell_ref()?,
+        };
+        if let Some(tracker) = self.tracker {
+            tracker.visit(cell_ref)?;
+        }
         self.next_ref_pos += 1;
         Ok(cell_ref)
     }
 
+    /// Tracked lazy ref is decoded to get its hash
+    pub fn read_next_ref_parser(&mut self) -> Result<CellParser<'a>, TLCoreError> {
+        match self.source {
+            ParserSource::Cell(_) => {
+                let mut parser = self.read_next_ref()?.parser();
+                parser.tracker = self.tracker;
+                Ok(parser)
+            }
+            ParserSource::Lazy(cell) => {
+                if self.next_ref_pos == cell.refs_count() {
+                    return Err(TLCoreError::ParserRefsUnderflow { req: self.next_ref_pos });
+                }
+                let cell_ref = cell.reference(self.next_ref_pos)?;
+                self.next_ref_pos += 1;
+                match self.tracker {
+                    Some(tracker) => CellParser::new_lazy(cell_ref).with_tracker(tracker),
+                    None => Ok(CellParser::new_lazy(cell_ref)),
+                }
+            }
+        }
+    }
+
     pub fn data_bits_remaining(&mut self) -> Result<usize, TLCoreError> {
         let reader_pos = self.data_reader.position_in_bits()? as usize;
-        Ok(self.cell.data_bits_len - reader_pos)
+        Ok(self.data_bits_len() - reader_pos)
     }
 
     pub fn seek_bits(&mut self, offset: i32) -> Result<(), TLCoreError> {
         let new_pos = self.data_reader.position_in_bits()? as i32 + offset;
-        let data_bits_len = self.cell.data_bits_len;
-        if new_pos < 0 || new_pos as usize > (data_bits_len - 1) {
+        let data_bits_len = self.data_bits_len();
+        if new_pos < 0 || new_pos as usize > data_bits_len.saturating_sub(1) {
             return Err(TLCoreError::ParserBadPosition {
                 new_pos,
                 bits_len: data_bits_len,
@@ -136,7 +212,7 @@ impl<'a> CellParser<'a> {
 
     pub fn ensure_empty(&mut self) -> Result<(), TLCoreError> {
         let bits_left = self.data_bits_remaining()?;
-        let refs_left = self.cell.refs.len() - self.next_ref_pos;
+        let refs_left = self.refs_count() - self.next_ref_pos;
         if bits_left == 0 && refs_left == 0 {
             return Ok(());
         }
@@ -190,6 +266,13 @@ mod tests {
         assert_eq!(parser.data_reader.position_in_bits()? as usize, cell_slice.data_bits_len - 1);
 
         assert_err!(parser.seek_bits(20));
+
+        let empty_cell = make_test_cell(&[], 0)?;
+        let mut parser = CellParser::new(&empty_cell);
+        assert_ok!(parser.seek_bits(0));
+        assert_eq!(parser.lookup_bits(0)?, 0);
+        assert_err!(parser.seek_bits(1));
+        assert_err!(parser.seek_bits(-1));
         Ok(())
     }
 
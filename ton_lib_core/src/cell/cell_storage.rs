@@ -0,0 +1,158 @@
+use crate::boc::BOC;
+use crate::cell::{CellBuilder, TonCell, TonCellRef, TonCellStorage, TonHash};
+use crate::error::TLCoreError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Interns cells by hash: equal cells (and equal subtrees) share the same `TonCellRef`
+///
+/// Created by [`CellStorage::new_weak`], storage doesn't keep cells alive:
+/// a cell is evicted once all refs outside the storage are dropped. Use [`CellStorage::purge`] to clean up such entries
+#[derive(Debug, Default)]
+pub struct CellStorage {
+    cells: Mutex<HashMap<TonHash, StoredCell>>,
+    weak_refs: bool,
+}
+
+#[derive(Debug)]
+enum StoredCell {
+    Strong(TonCellRef),
+    Weak(Weak<TonCell>),
+}
+
+impl StoredCell {
+    fn get(&self) -> Option<TonCellRef> {
+        match self {
+            StoredCell::Strong(cell) => Some(cell.clone()),
+            StoredCell::Weak(cell) => cell.upgrade().map(TonCellRef),
+        }
+    }
+}
+
+impl CellStorage {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn new_weak() -> Self {
+        Self {
+            weak_refs: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns stored cell with the same hash, or stores `cell` (interning its refs as well)
+    pub fn intern(&self, cell: TonCellRef) -> Result<TonCellRef, TLCoreError> {
+        let mut cells = self.cells.lock().unwrap();
+        self.intern_impl(&mut cells, cell)
+    }
+
+    pub fn intern_cell(&self, cell: TonCell) -> Result<TonCellRef, TLCoreError> { self.intern(cell.into_ref()) }
+
+    pub fn build(&self, builder: CellBuilder) -> Result<TonCellRef, TLCoreError> { self.intern_cell(builder.build()?) }
+
+    /// Parses BOC and interns all its roots
+    pub fn parse_boc<T: AsRef<[u8]>>(&self, bytes: T) -> Result<TonCellStorage, TLCoreError> {
+        let roots = BOC::from_bytes(bytes)?.into_roots();
+        roots.into_iter().map(|root| self.intern(root)).collect()
+    }
+
+    pub fn get(&self, hash: &TonHash) -> Option<TonCellRef> { self.cells.lock().unwrap().get(hash)?.get() }
+
+    /// Number of stored cells, including evicted but not purged yet
+    pub fn len(&self) -> usize { self.cells.lock().unwrap().len() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Removes evicted cells
+    pub fn purge(&self) { self.cells.lock().unwrap().retain(|_, stored| stored.get().is_some()) }
+
+    fn intern_impl(
+        &self,
+        cells: &mut HashMap<TonHash, StoredCell>,
+        cell: TonCellRef,
+    ) -> Result<TonCellRef, TLCoreError> {
+        let hash = cell.hash()?.clone();
+        if let Some(stored) = cells.get(&hash).and_then(StoredCell::get) {
+            return Ok(stored);
+        }
+        let mut refs = Vec::with_capacity(cell.refs.len());
+        for cell_ref in &cell.refs {
+            refs.push(self.intern_impl(cells, cell_ref.clone())?);
+        }
+        let interned = match refs.iter().zip(&cell.refs).all(|(new, old)| Arc::ptr_eq(&new.0, &old.0)) {
+            true => cell,
+            false => TonCell {
+                cell_type: cell.cell_type,
+                data: cell.data.clone(),
+                data_bits_len: cell.data_bits_len,
+                refs,
+                meta: cell.meta.clone(), // refs have the same hashes
+            }
+            .into_ref(),
+        };
+        let stored = match self.weak_refs {
+            true => StoredCell::Weak(Arc::downgrade(&interned.0)),
+            false => StoredCell::Strong(interned.clone()),
+        };
+        cells.insert(hash, stored);
+        Ok(interned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_tree(code: u32) -> anyhow::Result<TonCellRef> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xC0DEu16, 16)?;
+        let code_cell = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&code, 32)?;
+        builder.write_ref(code_cell)?;
+        Ok(builder.build_ref()?)
+    }
+
+    #[test]
+    fn test_cell_storage_intern() -> anyhow::Result<()> {
+        let storage = CellStorage::new();
+        let tree_1 = storage.intern(make_tree(1)?)?;
+        let tree_2 = storage.intern(make_tree(2)?)?;
+        assert!(Arc::ptr_eq(&tree_1.refs[0].0, &tree_2.refs[0].0));
+        assert_eq!(storage.len(), 3);
+
+        let tree_1_copy = storage.intern(make_tree(1)?)?;
+        assert!(Arc::ptr_eq(&tree_1.0, &tree_1_copy.0));
+        assert_eq!(storage.len(), 3);
+
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xC0DEu16, 16)?;
+        let built = storage.build(builder)?;
+        assert!(Arc::ptr_eq(&built.0, &tree_1.refs[0].0));
+
+        let boc_bytes = BOC::new(make_tree(2)?).to_bytes(false)?;
+        let parsed = storage.parse_boc(boc_bytes)?;
+        assert!(Arc::ptr_eq(&parsed[0].0, &tree_2.0));
+        assert_eq!(storage.get(tree_2.hash()?), Some(tree_2.clone()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_storage_weak() -> anyhow::Result<()> {
+        let storage = CellStorage::new_weak();
+        let tree_1 = storage.intern(make_tree(1)?)?;
+        let tree_2 = storage.intern(make_tree(2)?)?;
+        assert!(Arc::ptr_eq(&tree_1.refs[0].0, &tree_2.refs[0].0));
+
+        let tree_1_hash = tree_1.hash()?.clone();
+        drop(tree_1);
+        assert!(storage.get(&tree_1_hash).is_none());
+        assert_eq!(storage.len(), 3);
+        storage.purge();
+        assert_eq!(storage.len(), 2);
+
+        drop(tree_2);
+        storage.purge();
+        assert!(storage.is_empty());
+        Ok(())
+    }
+}
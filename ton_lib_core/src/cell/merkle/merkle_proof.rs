@@ -0,0 +1,177 @@
+use crate::cell::merkle::{PrunedBranch, VisitedCells};
+use crate::cell::meta::{CellType, LevelMask};
+use crate::cell::{CellBuilder, TonCell, TonCellRef, TonHash};
+use crate::error::TLCoreError;
+use std::collections::{HashMap, HashSet};
+
+pub struct MerkleProof;
+
+impl MerkleProof {
+    /// Builds MerkleProof cell for `root`, keeping cells with hashes from `keep` and pruning all the others
+    /// Root is always kept. Cell is kept only if its parent is kept, so `keep` must contain the whole path to it
+    pub fn create(root: &TonCell, keep: &HashSet<TonHash>) -> Result<TonCell, TLCoreError> {
+        Self::create_with(root, |hash| keep.contains(hash))
+    }
+
+    /// The same as `create`, but keeps cells visited by parsers tracked with `visited`
+    pub fn create_from_visited(root: &TonCell, visited: &VisitedCells) -> Result<TonCell, TLCoreError> {
+        Self::create_with(root, |hash| visited.contains(hash))
+    }
+
+    pub fn create_with<F>(root: &TonCell, is_kept: F) -> Result<TonCell, TLCoreError>
+    where
+        F: Fn(&TonHash) -> bool,
+    {
+        let proof_root = Self::prune_tree(root, &is_kept)?;
+        let mut builder = CellBuilder::new(CellType::MerkleProof);
+        builder.write_num(&(CellType::MerkleProof as u8), 8)?;
+        builder.write_bits(root.hash_for_level(LevelMask::MIN_LEVEL)?.as_slice(), TonHash::BITS_LEN)?;
+        builder.write_num(&root.depth_for_level(LevelMask::MIN_LEVEL)?, 16)?;
+        builder.write_ref(proof_root.into_ref())?;
+        builder.build()
+    }
+
+    /// Returns a copy of `root` with all not kept subtrees replaced by PrunedBranch cells
+    /// Root itself is never pruned
+    pub fn prune_tree<F>(root: &TonCell, is_kept: F) -> Result<TonCell, TLCoreError>
+    where
+        F: Fn(&TonHash) -> bool,
+    {
+        let mut pruner = TreePruner {
+            is_kept,
+            cache: HashMap::new(),
+        };
+        pruner.rebuild(root, 0)
+    }
+}
+
+struct TreePruner<F: Fn(&TonHash) -> bool> {
+    is_kept: F,
+    cache: HashMap<(TonHash, u8), TonCellRef>,
+}
+
+impl<F: Fn(&TonHash) -> bool> TreePruner<F> {
+    fn process_ref(&mut self, cell_ref: &TonCellRef, merkle_depth: u8) -> Result<TonCellRef, TLCoreError> {
+        let key = (cell_ref.hash()?.clone(), merkle_depth);
+        if let Some(cached) = self.cache.get(&key) {
+            return Ok(cached.clone());
+        }
+        let new_ref = match (self.is_kept)(&key.0) {
+            true => self.rebuild(cell_ref, merkle_depth)?.into_ref(),
+            false => PrunedBranch::create(cell_ref, merkle_depth + 1)?.into_ref(),
+        };
+        self.cache.insert(key, new_ref.clone());
+        Ok(new_ref)
+    }
+
+    fn rebuild(&mut self, cell: &TonCell, merkle_depth: u8) -> Result<TonCell, TLCoreError> {
+        if cell.refs.is_empty() {
+            return Ok(cell.clone());
+        }
+        let child_merkle_depth = match cell.cell_type {
+            CellType::MerkleProof | CellType::MerkleUpdate => merkle_depth + 1,
+            _ => merkle_depth,
+        };
+        let mut builder = CellBuilder::new(cell.cell_type);
+        builder.write_bits(&cell.data, cell.data_bits_len)?;
+        for cell_ref in &cell.refs {
+            builder.write_ref(self.process_ref(cell_ref, child_merkle_depth)?)?;
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boc::BOC;
+
+    fn make_cell(value: u32, refs: &[TonCellRef]) -> anyhow::Result<TonCellRef> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&value, 32)?;
+        for cell_ref in refs {
+            builder.write_ref(cell_ref.clone())?;
+        }
+        Ok(builder.build_ref()?)
+    }
+
+    //        root
+    //       /    \
+    //   left      right
+    //   /  \
+    // ll    lr
+    fn make_tree() -> anyhow::Result<(TonCellRef, TonCellRef, TonCellRef, TonCellRef)> {
+        let ll = make_cell(3, &[])?;
+        let lr = make_cell(4, &[])?;
+        let left = make_cell(1, &[ll.clone(), lr])?;
+        let right = make_cell(2, &[])?;
+        let root = make_cell(0, &[left.clone(), right.clone()])?;
+        Ok((root, left, right, ll))
+    }
+
+    #[test]
+    fn test_merkle_proof_create() -> anyhow::Result<()> {
+        let (root, left, right, ll) = make_tree()?;
+        let keep = HashSet::from([left.hash()?.clone(), ll.hash()?.clone()]);
+        let proof = MerkleProof::create(&root, &keep)?;
+
+        assert_eq!(proof.cell_type, CellType::MerkleProof);
+        assert_eq!(proof.level_mask(), LevelMask::MIN_LEVEL);
+        assert_eq!(&proof.data[1..33], root.hash()?.as_slice());
+
+        let proof_root = &proof.refs[0];
+        assert_eq!(proof_root.hash_for_level(LevelMask::MIN_LEVEL)?, root.hash()?);
+        assert_eq!(proof_root.level_mask(), LevelMask::new(1));
+
+        let proof_left = &proof_root.refs[0];
+        assert_eq!(proof_left.cell_type, CellType::Ordinary);
+        assert_eq!(proof_left.refs[0].cell_type, CellType::Ordinary);
+        assert_eq!(proof_left.refs[1].cell_type, CellType::PrunedBranch);
+        assert_eq!(proof_root.refs[1].cell_type, CellType::PrunedBranch);
+        assert_eq!(proof_root.refs[1].hash_for_level(LevelMask::MIN_LEVEL)?, right.hash()?);
+
+        // must survive serialization with validation
+        let parsed = BOC::from_bytes(BOC::new(proof.clone().into_ref()).to_bytes(false)?)?.single_root()?;
+        assert_eq!(parsed.hash()?, proof.hash()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_proof_from_visited() -> anyhow::Result<()> {
+        let (root, left, right, ll) = make_tree()?;
+
+        let visited = VisitedCells::new();
+        let mut parser = root.parser().with_tracker(&visited)?;
+        parser.read_next_ref_parser()?.read_next_ref()?;
+
+        let proof = MerkleProof::create_from_visited(&root, &visited)?;
+        let keep = HashSet::from([left.hash()?.clone(), ll.hash()?.clone()]);
+        assert_eq!(proof, MerkleProof::create(&root, &keep)?);
+        assert!(!visited.contains(right.hash()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_proof_prune_all() -> anyhow::Result<()> {
+        let (root, ..) = make_tree()?;
+        let proof = MerkleProof::create(&root, &HashSet::new())?;
+        let proof_root = &proof.refs[0];
+        assert_eq!(proof_root.data, root.data);
+        assert!(proof_root.refs.iter().all(|x| x.cell_type == CellType::PrunedBranch));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_proof_nested() -> anyhow::Result<()> {
+        let (root, _, right, ll) = make_tree()?;
+        let proof = MerkleProof::create_with(&root, |hash| hash != right.hash().unwrap())?;
+        // cells under inner MerkleProof must be pruned with level 2
+        let nested = MerkleProof::create_with(&proof, |hash| hash != ll.hash().unwrap())?;
+        let nested_ll = &nested.refs[0].refs[0].refs[0].refs[0];
+        assert_eq!(nested_ll.cell_type, CellType::PrunedBranch);
+        assert_eq!(nested_ll.level_mask(), LevelMask::new(2));
+        assert_eq!(nested.refs[0].hash_for_level(LevelMask::MIN_LEVEL)?, proof.hash()?);
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+use crate::cell::merkle::MerkleProof;
+use crate::cell::meta::{CellType, LevelMask};
+use crate::cell::{CellBuilder, TonCell, TonCellRef, TonHash};
+use crate::error::TLCoreError;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Deref;
+
+pub struct MerkleUpdate;
+
+impl MerkleUpdate {
+    /// Builds MerkleUpdate cell which transforms `old_root` into `new_root`
+    /// Old side keeps only cells removed by the update, new side keeps only created cells.
+    /// All the rest is replaced by PrunedBranch cells
+    pub fn create(old_root: &TonCell, new_root: &TonCell) -> Result<TonCell, TLCoreError> {
+        let new_hashes = collect_hashes(new_root)?;
+        let old_side = MerkleProof::prune_tree(old_root, |hash| !new_hashes.contains(hash))?;
+
+        // new side can prune only cells which are present in old side (node's MerkleUpdate validation),
+        // so cells hidden under old side pruned branches must be kept
+        let old_side_hashes = collect_old_side_hashes(old_root, &new_hashes)?;
+        let new_side = MerkleProof::prune_tree(new_root, |hash| !old_side_hashes.contains(hash))?;
+
+        let mut builder = CellBuilder::new(CellType::MerkleUpdate);
+        builder.write_num(&(CellType::MerkleUpdate as u8), 8)?;
+        builder.write_bits(old_root.hash_for_level(LevelMask::MIN_LEVEL)?.as_slice(), TonHash::BITS_LEN)?;
+        builder.write_bits(new_root.hash_for_level(LevelMask::MIN_LEVEL)?.as_slice(), TonHash::BITS_LEN)?;
+        builder.write_num(&old_root.depth_for_level(LevelMask::MIN_LEVEL)?, 16)?;
+        builder.write_num(&new_root.depth_for_level(LevelMask::MIN_LEVEL)?, 16)?;
+        builder.write_ref(old_side.into_ref())?;
+        builder.write_ref(new_side.into_ref())?;
+        builder.build()
+    }
+
+    /// Applies MerkleUpdate to `old_root` and returns the new root
+    /// Fails if `old_root` is not the one the update was built for, or if the result doesn't match the new hash
+    pub fn apply(update: &TonCell, old_root: &TonCell) -> Result<TonCell, TLCoreError> {
+        if update.cell_type != CellType::MerkleUpdate {
+            return Err(TLCoreError::UnexpectedValue {
+                expected: format!("{:?}", CellType::MerkleUpdate),
+                actual: format!("{:?}", update.cell_type),
+            });
+        }
+        // cell fields are public, so layout is checked even for cells validated by CellBuilder/BOC
+        if update.refs.len() != 2 || update.data.len() < 65 {
+            return Err(TLCoreError::UnexpectedValue {
+                expected: "MerkleUpdate with 2 refs and at least 65 data bytes".to_string(),
+                actual: format!("{} refs and {} data bytes", update.refs.len(), update.data.len()),
+            });
+        }
+        let (expected_old_hash, actual_old_hash) =
+            (update.refs[0].hash_for_level(LevelMask::MIN_LEVEL)?, old_root.hash()?);
+        if expected_old_hash != actual_old_hash {
+            return Err(TLCoreError::MerkleUpdateWrongOldRoot {
+                expected: expected_old_hash.clone(),
+                actual: actual_old_hash.clone(),
+            });
+        }
+
+        let mut applier = UpdateApplier {
+            old_cells: collect_cells(old_root.clone().into_ref())?,
+            cache: HashMap::new(),
+        };
+        let new_root = applier.rebuild(&update.refs[1], 0)?;
+
+        // tag(8) + old_hash(256) + new_hash(256)
+        let expected_new_hash = TonHash::from_slice(&update.data[33..65])?;
+        let actual_new_hash = new_root.hash()?;
+        if &expected_new_hash != actual_new_hash {
+            return Err(TLCoreError::MerkleUpdateWrongNewRoot {
+                expected: expected_new_hash,
+                actual: actual_new_hash.clone(),
+            });
+        }
+        Ok(new_root)
+    }
+}
+
+struct UpdateApplier {
+    old_cells: HashMap<TonHash, TonCellRef>,
+    cache: HashMap<(TonHash, u8), TonCellRef>,
+}
+
+impl UpdateApplier {
+    fn process_ref(&mut self, cell_ref: &TonCellRef, merkle_depth: u8) -> Result<TonCellRef, TLCoreError> {
+        let key = (cell_ref.hash()?.clone(), merkle_depth);
+        if let Some(cached) = self.cache.get(&key) {
+            return Ok(cached.clone());
+        }
+        let is_update_pruned =
+            cell_ref.cell_type == CellType::PrunedBranch && cell_ref.level_mask().level() == merkle_depth + 1;
+        let new_ref = match is_update_pruned {
+            true => {
+                let hash = cell_ref.hash_for_level(LevelMask::new(merkle_depth))?;
+                match self.old_cells.get(hash) {
+                    Some(old_cell) => old_cell.clone(),
+                    None => return Err(TLCoreError::MerkleUpdateUnknownCell(hash.clone())),
+                }
+            }
+            false => self.rebuild(cell_ref, merkle_depth)?.into_ref(),
+        };
+        self.cache.insert(key, new_ref.clone());
+        Ok(new_ref)
+    }
+
+    fn rebuild(&mut self, cell: &TonCell, merkle_depth: u8) -> Result<TonCell, TLCoreError> {
+        if cell.refs.is_empty() {
+            return Ok(cell.clone());
+        }
+        let child_merkle_depth = match cell.cell_type {
+            CellType::MerkleProof | CellType::MerkleUpdate => merkle_depth + 1,
+            _ => merkle_depth,
+        };
+        let mut builder = CellBuilder::new(cell.cell_type);
+        builder.write_bits(&cell.data, cell.data_bits_len)?;
+        for cell_ref in &cell.refs {
+            builder.write_ref(self.process_ref(cell_ref, child_merkle_depth)?)?;
+        }
+        builder.build()
+    }
+}
+
+fn collect_hashes(root: &TonCell) -> Result<HashSet<TonHash>, TLCoreError> {
+    let mut hashes = HashSet::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(cell) = queue.pop_front() {
+        if hashes.insert(cell.hash()?.clone()) {
+            queue.extend(cell.refs.iter().map(Deref::deref));
+        }
+    }
+    Ok(hashes)
+}
+
+// Hashes of cells presented in old side: the kept ones and roots of pruned branches
+fn collect_old_side_hashes(old_root: &TonCell, new_hashes: &HashSet<TonHash>) -> Result<HashSet<TonHash>, TLCoreError> {
+    let mut hashes = HashSet::from([old_root.hash()?.clone()]);
+    let mut queue = VecDeque::from([old_root]);
+    while let Some(cell) = queue.pop_front() {
+        for cell_ref in &cell.refs {
+            let hash = cell_ref.hash()?;
+            if hashes.insert(hash.clone()) && !new_hashes.contains(hash) {
+                queue.push_back(cell_ref);
+            }
+        }
+    }
+    Ok(hashes)
+}
+
+fn collect_cells(root: TonCellRef) -> Result<HashMap<TonHash, TonCellRef>, TLCoreError> {
+    let mut cells = HashMap::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(cell_ref) = queue.pop_front() {
+        if !cells.contains_key(cell_ref.hash()?) {
+            queue.extend(cell_ref.refs.iter().cloned());
+            cells.insert(cell_ref.hash()?.clone(), cell_ref);
+        }
+    }
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boc::BOC;
+
+    fn make_cell(value: u32, refs: &[TonCellRef]) -> anyhow::Result<TonCellRef> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&value, 32)?;
+        for cell_ref in refs {
+            builder.write_ref(cell_ref.clone())?;
+        }
+        Ok(builder.build_ref()?)
+    }
+
+    #[test]
+    fn test_merkle_update_create_apply() -> anyhow::Result<()> {
+        let shared = make_cell(100, &[make_cell(101, &[])?])?;
+        let removed = make_cell(1, &[])?;
+        let added = make_cell(2, &[make_cell(3, &[])?])?;
+        let old_root = make_cell(0, &[shared.clone(), removed.clone()])?;
+        let new_root = make_cell(0, &[shared.clone(), added.clone()])?;
+
+        let update = MerkleUpdate::create(&old_root, &new_root)?;
+        assert_eq!(update.cell_type, CellType::MerkleUpdate);
+        assert_eq!(update.level_mask(), LevelMask::MIN_LEVEL);
+
+        let (old_side, new_side) = (&update.refs[0], &update.refs[1]);
+        assert_eq!(old_side.refs[0].cell_type, CellType::PrunedBranch);
+        assert_eq!(old_side.refs[1].cell_type, CellType::Ordinary);
+        assert_eq!(new_side.refs[0].cell_type, CellType::PrunedBranch);
+        assert_eq!(new_side.refs[1].cell_type, CellType::Ordinary);
+        assert_eq!(new_side.refs[1].refs[0].cell_type, CellType::Ordinary);
+
+        let parsed_update = BOC::from_bytes(BOC::new(update.into_ref()).to_bytes(false)?)?.single_root()?;
+        let applied = MerkleUpdate::apply(&parsed_update, &old_root)?;
+        assert_eq!(applied.hash()?, new_root.hash()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_update_wrong_old_root() -> anyhow::Result<()> {
+        let old_root = make_cell(0, &[make_cell(1, &[])?])?;
+        let new_root = make_cell(0, &[make_cell(2, &[])?])?;
+        let update = MerkleUpdate::create(&old_root, &new_root)?;
+
+        let err = MerkleUpdate::apply(&update, &new_root).unwrap_err();
+        assert!(matches!(err, TLCoreError::MerkleUpdateWrongOldRoot { .. }));
+        let err = MerkleUpdate::apply(&old_root, &old_root).unwrap_err();
+        assert!(matches!(err, TLCoreError::UnexpectedValue { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_update_wrap_old_root() -> anyhow::Result<()> {
+        let old_root = make_cell(0, &[make_cell(1, &[])?])?;
+        let new_root = make_cell(10, std::slice::from_ref(&old_root))?;
+        let update = MerkleUpdate::create(&old_root, &new_root)?;
+        assert_eq!(update.refs[1].refs[0].cell_type, CellType::PrunedBranch);
+        assert_eq!(MerkleUpdate::apply(&update, &old_root)?, *new_root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_update_no_changes() -> anyhow::Result<()> {
+        let root = make_cell(0, &[make_cell(1, &[])?, make_cell(2, &[])?])?;
+        let update = MerkleUpdate::create(&root, &root)?;
+        assert!(update.refs[1].refs.iter().all(|x| x.cell_type == CellType::PrunedBranch));
+        assert_eq!(MerkleUpdate::apply(&update, &root)?, *root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_update_keeps_cells_hidden_in_old_side() -> anyhow::Result<()> {
+        //  old: root -> a -> x      new: root' -> a -> x
+        //                                     -> b -> x
+        // a is unchanged, so it's pruned in old side and x is not presented there
+        let x = make_cell(3, &[])?;
+        let a = make_cell(1, std::slice::from_ref(&x))?;
+        let b = make_cell(2, std::slice::from_ref(&x))?;
+        let old_root = make_cell(0, std::slice::from_ref(&a))?;
+        let new_root = make_cell(10, &[a.clone(), b.clone()])?;
+
+        let update = MerkleUpdate::create(&old_root, &new_root)?;
+        let (old_side, new_side) = (&update.refs[0], &update.refs[1]);
+        assert_eq!(old_side.refs[0].cell_type, CellType::PrunedBranch);
+        assert_eq!(new_side.refs[0].cell_type, CellType::PrunedBranch);
+        assert_eq!(new_side.refs[1].refs[0].cell_type, CellType::Ordinary);
+        assert_eq!(MerkleUpdate::apply(&update, &old_root)?, *new_root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_update_wrong_new_root() -> anyhow::Result<()> {
+        let old_root = make_cell(0, &[make_cell(1, &[])?])?;
+        let new_root = make_cell(0, &[make_cell(2, &[])?])?;
+        let update = MerkleUpdate::create(&old_root, &new_root)?;
+
+        // replace new side, keeping new hash in update data (CellBuilder would reject it)
+        let other_root = make_cell(0, &[make_cell(3, &[])?])?;
+        let other_new_side = MerkleUpdate::create(&old_root, &other_root)?.refs[1].clone();
+        let tampered = TonCell {
+            cell_type: CellType::MerkleUpdate,
+            data: update.data.clone(),
+            data_bits_len: update.data_bits_len,
+            refs: vec![update.refs[0].clone(), other_new_side],
+            meta: Default::default(),
+        };
+        let err = MerkleUpdate::apply(&tampered, &old_root).unwrap_err();
+        assert!(matches!(err, TLCoreError::MerkleUpdateWrongNewRoot { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merkle_update_truncated() -> anyhow::Result<()> {
+        let old_root = make_cell(0, &[make_cell(1, &[])?])?;
+        let new_root = make_cell(0, &[make_cell(2, &[])?])?;
+        let update = MerkleUpdate::create(&old_root, &new_root)?;
+
+        let mut truncated_data = update.clone();
+        truncated_data.data.truncate(33);
+        truncated_data.data_bits_len = 33 * 8;
+        let err = MerkleUpdate::apply(&truncated_data, &old_root).unwrap_err();
+        assert!(matches!(err, TLCoreError::UnexpectedValue { .. }));
+
+        let mut truncated_refs = update.clone();
+        truncated_refs.refs.truncate(1);
+        let err = MerkleUpdate::apply(&truncated_refs, &old_root).unwrap_err();
+        assert!(matches!(err, TLCoreError::UnexpectedValue { .. }));
+        Ok(())
+    }
+}
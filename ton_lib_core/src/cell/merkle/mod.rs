@@ -0,0 +1,9 @@
+mod merkle_proof;
+mod merkle_update;
+mod pruned_branch;
+mod visited_cells;
+
+pub use merkle_proof::*;
+pub use merkle_update::*;
+pub use pruned_branch::*;
+pub use visited_cells::*;
@@ -0,0 +1,64 @@
+use crate::cell::meta::{CellType, LevelMask};
+use crate::cell::{CellBuilder, TonCell};
+use crate::error::TLCoreError;
+
+pub struct PrunedBranch;
+
+impl PrunedBranch {
+    /// Replaces the cell with PrunedBranch, which keeps its hashes & depths for all levels below `new_level`
+    /// `new_level` is `merkle_depth + 1`, so it's 1 for a cell inside a top-level MerkleProof
+    /// https://github.com/ton-blockchain/ton/blob/master/crypto/vm/cells/CellBuilder.cpp (do_create_pruned_branch)
+    pub fn create(cell: &TonCell, new_level: u8) -> Result<TonCell, TLCoreError> {
+        let level_mask = cell.level_mask();
+        let cell_level = level_mask.level();
+        if new_level == 0 || new_level > LevelMask::MAX_LEVEL.mask() || new_level <= cell_level {
+            return Err(TLCoreError::PrunedBranchWrongLevel { cell_level, new_level });
+        }
+        let new_level_mask = level_mask.apply(new_level - 1) | (1u8 << (new_level - 1));
+
+        let mut builder = CellBuilder::new(CellType::PrunedBranch);
+        builder.write_num(&(CellType::PrunedBranch as u8), 8)?;
+        builder.write_num(&new_level_mask.mask(), 8)?;
+        let sign_levels = (0..=cell_level).filter(|&level| level_mask.is_significant(level));
+        for level in sign_levels.clone() {
+            builder.write_bits(cell.hash_for_level(LevelMask::new(level))?.as_slice(), 256)?;
+        }
+        for level in sign_levels {
+            builder.write_num(&cell.depth_for_level(LevelMask::new(level))?, 16)?;
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pruned_branch_create() -> anyhow::Result<()> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xDEADBEEFu32, 32)?;
+        builder.write_ref(TonCell::EMPTY.into_ref())?;
+        let cell = builder.build()?;
+
+        let pruned = PrunedBranch::create(&cell, 1)?;
+        assert_eq!(pruned.cell_type, CellType::PrunedBranch);
+        assert_eq!(pruned.level_mask(), LevelMask::new(1));
+        assert_eq!(pruned.hash_for_level(LevelMask::MIN_LEVEL)?, cell.hash()?);
+        assert_eq!(pruned.depth_for_level(LevelMask::MIN_LEVEL)?, cell.depth()?);
+        assert_ne!(pruned.hash()?, cell.hash()?);
+
+        let err = PrunedBranch::create(&pruned, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            TLCoreError::PrunedBranchWrongLevel {
+                cell_level: 1,
+                new_level: 1
+            }
+        ));
+        let pruned_2 = PrunedBranch::create(&pruned, 2)?;
+        assert_eq!(pruned_2.level_mask(), LevelMask::new(3));
+        assert_eq!(pruned_2.hash_for_level(LevelMask::new(1))?, pruned.hash_for_level(LevelMask::new(1))?);
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use crate::cell::{TonCell, TonHash};
+use crate::error::TLCoreError;
+use crate::traits::tlb::TLB;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Collects hashes of cells touched by tracked parsers
+///
+/// Parser is tracked if it's created by [`crate::cell::CellParser::with_tracker`]
+/// or by `read_next_ref_parser` of tracked parser - so all nested `TLB::read` calls are tracked as well.
+/// Cells parsed later by their own parsers (e.g. `TLBObject::get`) must be tracked explicitly.
+#[derive(Debug, Default)]
+pub struct VisitedCells {
+    hashes: Mutex<HashSet<TonHash>>,
+}
+
+impl VisitedCells {
+    pub fn new() -> Self { Self::default() }
+
+    /// Reads `T` from `cell`, tracking all cells parsed on the way
+    pub fn read<T: TLB>(&self, cell: &TonCell) -> Result<T, TLCoreError> {
+        T::read(&mut cell.parser().with_tracker(self)?)
+    }
+
+    pub fn visit(&self, cell: &TonCell) -> Result<(), TLCoreError> {
+        let hash = cell.hash()?.clone();
+        self.hashes.lock().unwrap().insert(hash);
+        Ok(())
+    }
+
+    pub fn contains(&self, hash: &TonHash) -> bool { self.hashes.lock().unwrap().contains(hash) }
+
+    pub fn into_hashes(self) -> HashSet<TonHash> { self.hashes.into_inner().unwrap() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{CellBuilder, CellParser, TonCellRef};
+
+    #[derive(Debug, PartialEq)]
+    struct FirstRefValue(u8);
+
+    impl TLB for FirstRefValue {
+        fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+            // nested parser inherits the tracker
+            let mut ref_parser = parser.read_next_ref_parser()?;
+            Ok(Self(ref_parser.read_num(8)?))
+        }
+
+        fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> {
+            let mut ref_builder = TonCell::builder();
+            ref_builder.write_num(&self.0, 8)?;
+            builder.write_ref(ref_builder.build_ref()?)
+        }
+    }
+
+    fn make_root() -> anyhow::Result<(TonCell, TonCellRef, TonCellRef, TonCellRef)> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&3u8, 8)?;
+        let grandchild = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&1u8, 8)?;
+        builder.write_ref(grandchild.clone())?;
+        let child_1 = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_num(&2u8, 8)?;
+        let child_2 = builder.build_ref()?;
+
+        let mut builder = TonCell::builder();
+        builder.write_ref(child_1.clone())?;
+        builder.write_ref(child_2.clone())?;
+        Ok((builder.build()?, child_1, child_2, grandchild))
+    }
+
+    #[test]
+    fn test_visited_cells() -> anyhow::Result<()> {
+        let (root, child_1, child_2, grandchild) = make_root()?;
+
+        let visited = VisitedCells::new();
+        let value = visited.read::<FirstRefValue>(&root)?;
+        assert_eq!(value, FirstRefValue(1));
+        assert_eq!(FirstRefValue::from_cell(&value.to_cell()?)?, value);
+
+        assert!(visited.contains(root.hash()?));
+        assert!(visited.contains(child_1.hash()?));
+        assert!(!visited.contains(child_2.hash()?));
+        assert!(!visited.contains(grandchild.hash()?));
+
+        // parsers without tracker are not tracked
+        root.parser().read_next_ref()?;
+        child_2.parser().read_num::<u8>(8)?;
+        assert!(!visited.contains(child_2.hash()?));
+        assert_eq!(visited.into_hashes().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_visited_cells_other_thread() -> anyhow::Result<()> {
+        let (root, child_1, child_2, grandchild) = make_root()?;
+        let visited = VisitedCells::new();
+        let mut parser = root.parser().with_tracker(&visited)?;
+        parser.read_next_ref()?;
+        let mut child_2_parser = parser.read_next_ref_parser()?;
+        std::thread::scope(|scope| scope.spawn(move || child_2_parser.read_num::<u8>(8)).join().unwrap())?;
+
+        assert!(visited.contains(child_1.hash()?));
+        assert!(visited.contains(child_2.hash()?));
+        assert!(!visited.contains(grandchild.hash()?));
+        Ok(())
+    }
+}
@@ -115,20 +115,58 @@ impl<'a> CellMetaBuilder<'a> {
             return Err(TLCoreError::BuilderMeta("Merkle Proof cell must have exactly 1 ref".to_owned()));
         }
 
-        let mut data_slice = &self.data[1..];
-        let _proof_hash = TonHash::from_slice(&data_slice[..TonHash::BYTES_LEN])
-            .map_err(|err| TLCoreError::BuilderMeta(format!("Can't get proof hash bytes from cell data: {err}")))?;
-
-        data_slice = &data_slice[TonHash::BYTES_LEN..];
-        let _proof_depth = u16::from_be_bytes(data_slice[..CellMeta::DEPTH_BYTES].try_into().unwrap());
-        log::trace!("validate_merkle_proof is not implemented yet!"); // TODO
-        Ok(())
+        let proof_hash = TonHash::from_slice(&self.data[1..1 + TonHash::BYTES_LEN])?;
+        let depth_offset = 1 + TonHash::BYTES_LEN;
+        let proof_depth = u16::from_be_bytes([self.data[depth_offset], self.data[depth_offset + 1]]);
+        self.validate_merkle_ref(0, &proof_hash, proof_depth)
     }
 
     fn validate_merkle_update(&self) -> Result<(), TLCoreError> {
         // type + hash + hash + depth + depth
-        // const MERKLE_UPDATE_BITS_LEN: usize = 8 + (2 * (256 + 16));
-        log::trace!("validate_merkle_update is not implemented yet"); // TODO
+        const MERKLE_UPDATE_BITS_LEN: usize = (1 + 2 * (TonHash::BYTES_LEN + CellMeta::DEPTH_BYTES)) * 8;
+
+        if self.data_bits_len != MERKLE_UPDATE_BITS_LEN {
+            let err_msg =
+                format!("MerkleUpdate must have exactly {MERKLE_UPDATE_BITS_LEN} bits, got {}", self.data_bits_len);
+            return Err(TLCoreError::BuilderMeta(err_msg));
+        }
+
+        if self.refs.len() != 2 {
+            return Err(TLCoreError::BuilderMeta("Merkle Update cell must have exactly 2 refs".to_owned()));
+        }
+
+        let depths_offset = 1 + 2 * TonHash::BYTES_LEN;
+        for ref_pos in 0..2 {
+            let hash_offset = 1 + ref_pos * TonHash::BYTES_LEN;
+            let hash = TonHash::from_slice(&self.data[hash_offset..hash_offset + TonHash::BYTES_LEN])?;
+            let depth_offset = depths_offset + ref_pos * CellMeta::DEPTH_BYTES;
+            let depth = u16::from_be_bytes([self.data[depth_offset], self.data[depth_offset + 1]]);
+            self.validate_merkle_ref(ref_pos, &hash, depth)?;
+        }
+        Ok(())
+    }
+
+    /// Stored hash & depth must match level-0 hash & depth of the referenced cell
+    fn validate_merkle_ref(&self, ref_pos: usize, stored_hash: &TonHash, stored_depth: u16) -> Result<(), TLCoreError> {
+        let cell_ref = &self.refs[ref_pos];
+        let ref_hash = cell_ref.hash_for_level(LevelMask::MIN_LEVEL)?;
+        if ref_hash != stored_hash {
+            return Err(TLCoreError::ExoticWrongRefHash {
+                cell_type: self.cell_type,
+                ref_pos,
+                stored: stored_hash.clone(),
+                actual: ref_hash.clone(),
+            });
+        }
+        let ref_depth = cell_ref.depth_for_level(LevelMask::MIN_LEVEL)?;
+        if ref_depth != stored_depth {
+            return Err(TLCoreError::ExoticWrongRefDepth {
+                cell_type: self.cell_type,
+                ref_pos,
+                stored: stored_depth,
+                actual: ref_depth,
+            });
+        }
         Ok(())
     }
 
@@ -311,7 +349,8 @@ impl<'a> CellMetaBuilder<'a> {
         let cursor = Cursor::new(&self.data[current_index..]);
         let mut reader = ByteReader::endian(cursor, BigEndian);
 
-        let level = level_mask.level() as usize;
+        // pruned branch stores hashes for all significant levels below its own level
+        let level = level_mask.hash_index();
         let hashes = (0..level).map(|_| reader.read::<[u8; TonHash::BYTES_LEN]>()).collect::<Result<Vec<_>, _>>()?;
         let depths = (0..level).map(|_| reader.read::<u16>()).collect::<Result<Vec<_>, _>>()?;
         let result = hashes
@@ -418,4 +457,130 @@ mod test {
         }
         Ok(())
     }
+
+    fn merkle_child(value: u32) -> anyhow::Result<TonCellRef> {
+        let mut builder = crate::cell::CellBuilder::new(CellType::Ordinary);
+        builder.write_num(&value, 32)?;
+        builder.write_ref(empty_cell_ref())?;
+        Ok(builder.build_ref()?)
+    }
+
+    fn merkle_cell(cell_type: CellType, data: Vec<u8>, refs: Vec<TonCellRef>) -> TonCell {
+        let data_bits_len = data.len() * 8;
+        TonCell {
+            cell_type,
+            data,
+            data_bits_len,
+            refs,
+            meta: CellMeta::default(),
+        }
+    }
+
+    fn merkle_proof_data(child: &TonCell) -> anyhow::Result<Vec<u8>> {
+        let mut data = vec![CellType::MerkleProof as u8];
+        data.extend_from_slice(child.hash_for_level(LevelMask::MIN_LEVEL)?.as_slice());
+        data.extend_from_slice(&child.depth_for_level(LevelMask::MIN_LEVEL)?.to_be_bytes());
+        Ok(data)
+    }
+
+    fn merkle_update_data(old: &TonCell, new: &TonCell) -> anyhow::Result<Vec<u8>> {
+        let mut data = vec![CellType::MerkleUpdate as u8];
+        data.extend_from_slice(old.hash_for_level(LevelMask::MIN_LEVEL)?.as_slice());
+        data.extend_from_slice(new.hash_for_level(LevelMask::MIN_LEVEL)?.as_slice());
+        data.extend_from_slice(&old.depth_for_level(LevelMask::MIN_LEVEL)?.to_be_bytes());
+        data.extend_from_slice(&new.depth_for_level(LevelMask::MIN_LEVEL)?.to_be_bytes());
+        Ok(data)
+    }
+
+    #[test]
+    fn test_validate_merkle_proof() -> anyhow::Result<()> {
+        let child = merkle_child(1)?;
+        let data = merkle_proof_data(&child)?;
+        let proof = merkle_cell(CellType::MerkleProof, data.clone(), vec![child.clone()]);
+        CellMetaBuilder::new(&proof).validate()?;
+
+        let mut wrong_hash_data = data.clone();
+        wrong_hash_data[1] ^= 1;
+        let proof = merkle_cell(CellType::MerkleProof, wrong_hash_data, vec![child.clone()]);
+        let err = CellMetaBuilder::new(&proof).validate().unwrap_err();
+        assert!(matches!(err, TLCoreError::ExoticWrongRefHash { ref_pos: 0, .. }));
+
+        let mut wrong_depth_data = data.clone();
+        *wrong_depth_data.last_mut().unwrap() += 1;
+        let proof = merkle_cell(CellType::MerkleProof, wrong_depth_data, vec![child.clone()]);
+        let err = CellMetaBuilder::new(&proof).validate().unwrap_err();
+        assert!(matches!(
+            err,
+            TLCoreError::ExoticWrongRefDepth {
+                stored: 2,
+                actual: 1,
+                ..
+            }
+        ));
+
+        let proof = merkle_cell(CellType::MerkleProof, data.clone(), vec![child.clone(), child.clone()]);
+        let err = CellMetaBuilder::new(&proof).validate().unwrap_err();
+        assert!(matches!(err, TLCoreError::BuilderMeta(_)));
+
+        let proof = merkle_cell(CellType::MerkleProof, data[..data.len() - 1].to_vec(), vec![child]);
+        let err = CellMetaBuilder::new(&proof).validate().unwrap_err();
+        assert!(matches!(err, TLCoreError::BuilderMeta(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_merkle_update() -> anyhow::Result<()> {
+        let old = merkle_child(1)?;
+        let new = merkle_child(2)?;
+        let data = merkle_update_data(&old, &new)?;
+        let update = merkle_cell(CellType::MerkleUpdate, data.clone(), vec![old.clone(), new.clone()]);
+        CellMetaBuilder::new(&update).validate()?;
+
+        // swapped sides
+        let update = merkle_cell(CellType::MerkleUpdate, data.clone(), vec![new.clone(), old.clone()]);
+        let err = CellMetaBuilder::new(&update).validate().unwrap_err();
+        assert!(matches!(err, TLCoreError::ExoticWrongRefHash { ref_pos: 0, .. }));
+
+        // new side is checked too
+        let mut wrong_new_data = data.clone();
+        wrong_new_data[1 + TonHash::BYTES_LEN] ^= 1;
+        let update = merkle_cell(CellType::MerkleUpdate, wrong_new_data, vec![old.clone(), new.clone()]);
+        let err = CellMetaBuilder::new(&update).validate().unwrap_err();
+        assert!(matches!(err, TLCoreError::ExoticWrongRefHash { ref_pos: 1, .. }));
+
+        let mut wrong_depth_data = data.clone();
+        *wrong_depth_data.last_mut().unwrap() = 0;
+        let update = merkle_cell(CellType::MerkleUpdate, wrong_depth_data, vec![old.clone(), new.clone()]);
+        let err = CellMetaBuilder::new(&update).validate().unwrap_err();
+        assert!(matches!(
+            err,
+            TLCoreError::ExoticWrongRefDepth {
+                ref_pos: 1,
+                stored: 0,
+                actual: 1,
+                ..
+            }
+        ));
+
+        let update = merkle_cell(CellType::MerkleUpdate, data, vec![old]);
+        let err = CellMetaBuilder::new(&update).validate().unwrap_err();
+        assert!(matches!(err, TLCoreError::BuilderMeta(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_boc_rejects_invalid_merkle_proof() -> anyhow::Result<()> {
+        let child = merkle_child(1)?;
+        let mut data = merkle_proof_data(&child)?;
+        let proof = merkle_cell(CellType::MerkleProof, data.clone(), vec![child.clone()]).into_ref();
+        let boc_bytes = crate::boc::BOC::new(proof).to_bytes(false)?;
+        assert!(crate::boc::BOC::from_bytes(&boc_bytes).is_ok());
+
+        data[1] ^= 1;
+        let proof = merkle_cell(CellType::MerkleProof, data, vec![child]).into_ref();
+        let boc_bytes = crate::boc::BOC::new(proof).to_bytes(false)?;
+        let result = crate::boc::BOC::from_bytes(&boc_bytes);
+        assert!(matches!(result, Err(TLCoreError::ExoticWrongRefHash { .. })));
+        Ok(())
+    }
 }
@@ -1,12 +1,13 @@
 use crate::error::TLCoreError;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+/// Exotic variants are equal to the first data byte of the cell
 pub enum CellType {
-    Ordinary,
-    PrunedBranch,
-    LibraryRef,
-    MerkleProof,
-    MerkleUpdate,
+    Ordinary = 0,
+    PrunedBranch = 1,
+    LibraryRef = 2,
+    MerkleProof = 3,
+    MerkleUpdate = 4,
 }
 
 impl CellType {
@@ -3,13 +3,18 @@
 /// TonHash, TonCell, TonCellRef, CellBuilder, CellParser
 ///
 mod build_parse;
+mod cell_storage;
+mod merkle;
 mod meta;
 mod ton_cell;
 mod ton_cell_num;
+mod ton_cell_text;
 mod ton_cell_utils;
 mod ton_hash;
 
 pub use build_parse::*;
+pub use cell_storage::*;
+pub use merkle::*;
 pub use meta::*;
 pub use ton_cell::*;
 pub use ton_cell_num::*;
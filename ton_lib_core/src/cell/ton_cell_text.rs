@@ -0,0 +1,206 @@
+use crate::bits_utils::BitsUtils;
+use crate::cell::meta::CellType;
+use crate::cell::TonCell;
+use crate::error::TLCoreError;
+use std::fmt::Write;
+
+/// Text format based on Fift `x{...}` notation:
+/// ```text
+/// x{DEADBEEF}
+///  x{C_}
+///  PrunedBranch x{0101...}
+/// ```
+/// - data is printed as hex nibbles. If bits_len is not multiple of 4, completion tag is added and `_` is appended
+/// - exotic cells are prefixed with cell type
+/// - refs are placed on the following lines with one more space of indentation
+impl TonCell {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        write_cell_text(&mut text, self, 0);
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<TonCell, TLCoreError> {
+        let mut lines = vec![];
+        for (line_num, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            lines.push(parse_line(line_num + 1, line)?);
+        }
+        let mut pos = 0;
+        let cell = build_cell(&lines, &mut pos, 0)?;
+        if let Some(line) = lines.get(pos) {
+            return Err(wrong_format(line.line_num, "only one root cell is allowed"));
+        }
+        Ok(cell)
+    }
+}
+
+struct CellLine {
+    line_num: usize,
+    indent: usize,
+    cell_type: CellType,
+    data: Vec<u8>,
+    data_bits_len: usize,
+}
+
+fn write_cell_text(text: &mut String, cell: &TonCell, indent: usize) {
+    text.push_str(&" ".repeat(indent));
+    if cell.cell_type.is_exotic() {
+        let _ = write!(text, "{:?} ", cell.cell_type);
+    }
+    let _ = writeln!(text, "x{{{}}}", BitsUtils::to_fift_hex(&cell.data, cell.data_bits_len));
+    for cell_ref in &cell.refs {
+        write_cell_text(text, cell_ref, indent + 1);
+    }
+}
+
+fn parse_line(line_num: usize, line: &str) -> Result<CellLine, TLCoreError> {
+    let line = line.trim_end();
+    let content = line.trim_start_matches(' ');
+    let indent = line.len() - content.len();
+    let (cell_type, hex) = match content.split_once(' ') {
+        Some((type_name, hex)) => (parse_cell_type(line_num, type_name)?, hex.trim_start()),
+        None => (CellType::Ordinary, content),
+    };
+    let hex = match hex.strip_prefix("x{").and_then(|x| x.strip_suffix('}')) {
+        Some(hex) => hex,
+        None => return Err(wrong_format(line_num, "expected data in x{...} format")),
+    };
+    let (hex, has_tag) = match hex.strip_suffix('_') {
+        Some(hex) => (hex, true),
+        None => (hex, false),
+    };
+    let mut data = vec![0u8; hex.len().div_ceil(2)];
+    for (nibble_pos, char) in hex.chars().enumerate() {
+        let nibble = match char.to_digit(16) {
+            Some(nibble) => nibble as u8,
+            None => return Err(wrong_format(line_num, &format!("unexpected symbol '{char}' in data"))),
+        };
+        data[nibble_pos / 2] |= if nibble_pos % 2 == 0 { nibble << 4 } else { nibble };
+    }
+    let mut data_bits_len = hex.len() * 4;
+    if has_tag {
+        // drop trailing zeros and completion tag itself
+        while data_bits_len > 0 && data[(data_bits_len - 1) / 8] & (0x80 >> ((data_bits_len - 1) % 8)) == 0 {
+            data_bits_len -= 1;
+        }
+        if data_bits_len == 0 {
+            return Err(wrong_format(line_num, "completion tag is not found"));
+        }
+        data_bits_len -= 1;
+        data[data_bits_len / 8] &= !(0x80 >> (data_bits_len % 8));
+    }
+    Ok(CellLine {
+        line_num,
+        indent,
+        cell_type,
+        data,
+        data_bits_len,
+    })
+}
+
+fn parse_cell_type(line_num: usize, type_name: &str) -> Result<CellType, TLCoreError> {
+    let cell_type = match type_name {
+        "Ordinary" => CellType::Ordinary,
+        "PrunedBranch" => CellType::PrunedBranch,
+        "LibraryRef" => CellType::LibraryRef,
+        "MerkleProof" => CellType::MerkleProof,
+        "MerkleUpdate" => CellType::MerkleUpdate,
+        _ => return Err(wrong_format(line_num, &format!("unknown cell type '{type_name}'"))),
+    };
+    Ok(cell_type)
+}
+
+fn build_cell(lines: &[CellLine], pos: &mut usize, indent: usize) -> Result<TonCell, TLCoreError> {
+    let line = match lines.get(*pos) {
+        Some(line) => line,
+        None => return Err(wrong_format(0, "no cells found")),
+    };
+    if line.indent != indent {
+        let err_msg = format!("expected indent={indent}, got {}", line.indent);
+        return Err(wrong_format(line.line_num, &err_msg));
+    }
+    *pos += 1;
+    let mut builder = TonCell::builder_typed(line.cell_type);
+    builder.write_bits(&line.data, line.data_bits_len)?;
+    while let Some(next_line) = lines.get(*pos) {
+        if next_line.indent <= indent {
+            break;
+        }
+        builder.write_ref(build_cell(lines, pos, indent + 1)?.into_ref())?;
+    }
+    builder.build()
+}
+
+fn wrong_format(line: usize, msg: &str) -> TLCoreError {
+    TLCoreError::CellTextWrongFormat {
+        line,
+        msg: msg.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{MerkleProof, TonCellRef};
+    use std::collections::HashSet;
+
+    fn make_cell(data: &[u8], bits_len: usize, refs: &[TonCellRef]) -> anyhow::Result<TonCellRef> {
+        let mut builder = TonCell::builder();
+        builder.write_bits(data, bits_len)?;
+        for cell_ref in refs {
+            builder.write_ref(cell_ref.clone())?;
+        }
+        Ok(builder.build_ref()?)
+    }
+
+    #[test]
+    fn test_cell_text_to_from() -> anyhow::Result<()> {
+        let empty = make_cell(&[], 0, &[])?;
+        let one_bit = make_cell(&[0x80], 1, &[])?;
+        let nibbles = make_cell(&[0xAB, 0xC0], 12, &[])?;
+        let child = make_cell(&[0x12, 0x34, 0x50], 21, &[one_bit, nibbles, empty])?;
+        let root = make_cell(&[0xDE, 0xAD, 0xBE, 0xEF], 32, &[child])?;
+
+        let text = root.to_text();
+        assert_eq!(text, "x{DEADBEEF}\n x{123454_}\n  x{C_}\n  x{ABC}\n  x{}\n");
+        assert_eq!(TonCell::from_text(&text)?, *root);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_text_exotic() -> anyhow::Result<()> {
+        let leaf = make_cell(&[0x01], 8, &[])?;
+        let root = make_cell(&[0xFF, 0x00], 9, &[leaf.clone(), make_cell(&[0x02], 8, &[])?])?;
+        let proof = MerkleProof::create(&root, &HashSet::from([root.hash()?.clone(), leaf.hash()?.clone()]))?;
+
+        let text = proof.to_text();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("MerkleProof x{03"));
+        assert_eq!(lines[1], " x{FF4_}");
+        assert!(lines[3].starts_with("  PrunedBranch x{0101"));
+        assert_eq!(TonCell::from_text(&text)?, proof);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_text_wrong_format() {
+        let cases = [
+            ("x{ABC", 1),
+            ("x{0_}", 1),
+            ("x{AB}\n  x{CD}", 2),
+            ("x{AB}\nx{CD}", 2),
+            ("Unknown x{AB}", 1),
+            ("x{AZ}", 1),
+        ];
+        for (text, line_num) in cases {
+            let result = TonCell::from_text(text);
+            assert!(
+                matches!(result, Err(TLCoreError::CellTextWrongFormat { line, .. }) if line == line_num),
+                "text: {text}"
+            );
+        }
+        assert_eq!(TonCell::from_text("x{8_}").unwrap(), TonCell::EMPTY);
+        assert!(TonCell::from_text(" x{8_}\n").is_err());
+        assert!(TonCell::from_text("x{}\n x{}\n x{}\n x{}\n x{}\n x{}").is_err());
+    }
+}
@@ -7,6 +7,14 @@ use std::ops::Deref;
 
 pub struct TonCellUtils;
 
+/// Unique cells & bits of cell trees, and max depth of the roots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CellStorageStats {
+    pub cells: u64,
+    pub bits: u64,
+    pub max_depth: u16,
+}
+
 impl TonCellUtils {
     /// Runs over cell trees, collect library_cells and extracts corresponding lib_ids.
     pub fn extract_lib_ids<'a, I>(cells_iter: I) -> Result<HashSet<TonHash>, TLCoreError>
@@ -30,6 +38,31 @@ impl TonCellUtils {
         Ok(result)
     }
 
+    /// Runs over cell trees and counts unique cells & bits. Cells are deduplicated by hash, as the node does for StorageUsed.
+    pub fn storage_stats<'a, I>(cells_iter: I) -> Result<CellStorageStats, TLCoreError>
+    where
+        I: IntoIterator<Item = &'a TonCell>,
+    {
+        let mut stats = CellStorageStats::default();
+        let mut visited = HashSet::new();
+
+        let mut queue = VecDeque::new();
+        for root in cells_iter {
+            stats.max_depth = stats.max_depth.max(root.depth()?);
+            queue.push_back(root);
+        }
+
+        while let Some(cell) = queue.pop_front() {
+            if !visited.insert(cell.hash()?) {
+                continue;
+            }
+            stats.cells += 1;
+            stats.bits += cell.data_bits_len as u64;
+            queue.extend(cell.refs.iter().map(Deref::deref));
+        }
+        Ok(stats)
+    }
+
     // Read lib_ids from library_cell
     pub fn read_lib_id(cell: &TonCell) -> Result<Option<TonHash>, TLCoreError> {
         if cell.cell_type != CellType::LibraryRef {
@@ -82,4 +115,28 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_storage_stats() -> anyhow::Result<()> {
+        let leaf = TonCell::from_boc_hex("b5ee9c7201010101000a00000fabacabadabacaba8")?.into_ref();
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xFFu8, 8)?;
+        builder.write_ref(leaf.clone())?;
+        builder.write_ref(leaf.clone())?;
+        let root = builder.build()?;
+
+        // leaf is counted once
+        let stats = TonCellUtils::storage_stats([&root])?;
+        let expected = CellStorageStats {
+            cells: 2,
+            bits: 8 + leaf.data_bits_len as u64,
+            max_depth: 1,
+        };
+        assert_eq!(stats, expected);
+
+        // shared cells are deduplicated across roots
+        assert_eq!(TonCellUtils::storage_stats([&root, leaf.deref()])?, expected);
+        assert_eq!(TonCellUtils::storage_stats([])?, CellStorageStats::default());
+        Ok(())
+    }
 }
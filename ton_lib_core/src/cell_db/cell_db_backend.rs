@@ -0,0 +1,40 @@
+use crate::cell::TonHash;
+use crate::error::TLCoreError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Key-value storage for [`crate::cell_db::CellDB`]. Values are serialized cells
+pub trait CellDBBackend: Send + Sync {
+    fn get(&self, hash: &TonHash) -> Result<Option<Vec<u8>>, TLCoreError>;
+    fn put(&self, hash: &TonHash, value: &[u8]) -> Result<(), TLCoreError>;
+    fn delete(&self, hash: &TonHash) -> Result<(), TLCoreError>;
+    fn contains(&self, hash: &TonHash) -> Result<bool, TLCoreError>;
+    fn keys(&self) -> Result<Vec<TonHash>, TLCoreError>;
+}
+
+#[derive(Debug, Default)]
+pub struct CellDBMemoryBackend {
+    cells: Mutex<HashMap<TonHash, Vec<u8>>>,
+}
+
+impl CellDBBackend for CellDBMemoryBackend {
+    fn get(&self, hash: &TonHash) -> Result<Option<Vec<u8>>, TLCoreError> {
+        Ok(self.cells.lock().unwrap().get(hash).cloned())
+    }
+
+    fn put(&self, hash: &TonHash, value: &[u8]) -> Result<(), TLCoreError> {
+        self.cells.lock().unwrap().insert(hash.clone(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, hash: &TonHash) -> Result<(), TLCoreError> {
+        self.cells.lock().unwrap().remove(hash);
+        Ok(())
+    }
+
+    fn contains(&self, hash: &TonHash) -> Result<bool, TLCoreError> {
+        Ok(self.cells.lock().unwrap().contains_key(hash))
+    }
+
+    fn keys(&self) -> Result<Vec<TonHash>, TLCoreError> { Ok(self.cells.lock().unwrap().keys().cloned().collect()) }
+}
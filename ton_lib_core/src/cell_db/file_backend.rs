@@ -0,0 +1,131 @@
+use crate::cell::TonHash;
+use crate::cell_db::CellDBBackend;
+use crate::error::TLCoreError;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// makes temporary paths unique across threads & backends of the current process
+static TMP_FILE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Stores every cell in a separate file: `{root_dir}/{first hash byte}/{hash}`
+/// Files are written to temporary path and renamed, so partially written cells are never visible
+#[derive(Debug, Clone)]
+pub struct CellDBFileBackend {
+    root_dir: PathBuf,
+}
+
+impl CellDBFileBackend {
+    pub fn new<P: AsRef<Path>>(root_dir: P) -> Result<Self, TLCoreError> {
+        fs::create_dir_all(root_dir.as_ref())?;
+        Ok(Self {
+            root_dir: root_dir.as_ref().to_path_buf(),
+        })
+    }
+
+    fn cell_path(&self, hash: &TonHash) -> PathBuf {
+        let hash_hex = hash.to_hex();
+        self.root_dir.join(&hash_hex[..2]).join(hash_hex)
+    }
+}
+
+impl CellDBBackend for CellDBFileBackend {
+    fn get(&self, hash: &TonHash) -> Result<Option<Vec<u8>>, TLCoreError> {
+        match fs::read(self.cell_path(hash)) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn put(&self, hash: &TonHash, value: &[u8]) -> Result<(), TLCoreError> {
+        let path = self.cell_path(hash);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_id = TMP_FILE_ID.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = path.with_extension(format!("tmp{}_{tmp_id}", std::process::id()));
+        fs::write(&tmp_path, value)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn delete(&self, hash: &TonHash) -> Result<(), TLCoreError> {
+        match fs::remove_file(self.cell_path(hash)) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn contains(&self, hash: &TonHash) -> Result<bool, TLCoreError> { Ok(self.cell_path(hash).try_exists()?) }
+
+    fn keys(&self) -> Result<Vec<TonHash>, TLCoreError> {
+        let mut keys = vec![];
+        for dir in fs::read_dir(&self.root_dir)? {
+            let dir = dir?;
+            if !dir.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(dir.path())? {
+                let file_name = file?.file_name();
+                // skip temporary & foreign files
+                if let Ok(hash) = TonHash::from_str(&file_name.to_string_lossy()) {
+                    keys.push(hash);
+                }
+            }
+        }
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::TonCell;
+    use crate::cell_db::CellDB;
+
+    #[test]
+    fn test_cell_db_file_backend() -> anyhow::Result<()> {
+        let root_dir = std::env::temp_dir().join(format!("ton_lib_cell_db_{}", std::process::id()));
+        let db = CellDB::new(CellDBFileBackend::new(&root_dir)?);
+
+        let mut builder = TonCell::builder();
+        builder.write_num(&0xDEADBEEFu32, 32)?;
+        let child = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_bits([0b1010_0000], 3)?;
+        builder.write_ref(child.clone())?;
+        builder.write_ref(child.clone())?;
+        let root = builder.build_ref()?;
+
+        let root_hash = db.put(&root)?;
+        assert_eq!(db.backend().keys()?.len(), 2);
+        // reopen
+        let db = CellDB::new(CellDBFileBackend::new(&root_dir)?);
+        assert_eq!(db.get(&root_hash)?, Some(root.clone()));
+
+        assert_eq!(db.gc(&[child.hash()?.clone()])?, 1);
+        assert_eq!(db.get(&root_hash)?, None);
+        assert_eq!(db.get(child.hash()?)?, Some(child.clone()));
+        fs::remove_dir_all(root_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_db_file_backend_concurrent_put() -> anyhow::Result<()> {
+        let root_dir = std::env::temp_dir().join(format!("ton_lib_cell_db_concurrent_{}", std::process::id()));
+        let backend = CellDBFileBackend::new(&root_dir)?;
+        let hash = TonHash::from([1u8; 32]);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> =
+                (0..8).map(|_| scope.spawn(|| (0..50).try_for_each(|_| backend.put(&hash, b"value")))).collect();
+            handles.into_iter().try_for_each(|handle| handle.join().unwrap())
+        })?;
+        assert_eq!(backend.get(&hash)?, Some(b"value".to_vec()));
+        assert_eq!(backend.keys()?, vec![hash]);
+        fs::remove_dir_all(root_dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+mod cell_db_backend;
+mod file_backend;
+
+pub use cell_db_backend::*;
+pub use file_backend::*;
+
+use crate::cell::{CellMeta, CellType, TonCell, TonCellRef, TonHash};
+use crate::error::TLCoreError;
+use std::collections::{HashMap, HashSet};
+
+/// Content-addressed cell storage on top of [`CellDBBackend`]
+///
+/// Each cell is stored separately by its representation hash, refs are stored as hashes.
+/// So any stored subtree can be loaded by its root hash, and shared subtrees are stored once.
+pub struct CellDB<B: CellDBBackend> {
+    backend: B,
+}
+
+// Serialized form of a single cell:
+// flags:u8 (bit0: is_exotic) data_bits_len:u16 data:[u8; data_bits_len.div_ceil(8)] refs_cnt:u8 refs:[TonHash; refs_cnt]
+struct CellRecord {
+    is_exotic: bool,
+    data: Vec<u8>,
+    data_bits_len: usize,
+    refs: Vec<TonHash>,
+}
+
+impl<B: CellDBBackend> CellDB<B> {
+    pub fn new(backend: B) -> Self { Self { backend } }
+
+    pub fn backend(&self) -> &B { &self.backend }
+
+    /// Stores the whole tree and returns its root hash. Already stored subtrees are skipped
+    pub fn put(&self, root: &TonCell) -> Result<TonHash, TLCoreError> {
+        let mut stored = HashSet::new();
+        self.put_impl(root, &mut stored)?;
+        Ok(root.hash()?.clone())
+    }
+
+    /// Loads tree with root `hash`. Cell may be any cell of stored tree, not only the root passed to `put`
+    pub fn get(&self, hash: &TonHash) -> Result<Option<TonCellRef>, TLCoreError> {
+        if !self.backend.contains(hash)? {
+            return Ok(None);
+        }
+        let mut loaded = HashMap::new();
+        Ok(Some(self.get_impl(hash, &mut loaded)?))
+    }
+
+    pub fn contains(&self, hash: &TonHash) -> Result<bool, TLCoreError> { self.backend.contains(hash) }
+
+    /// Removes all cells unreachable from `roots`. Returns the number of removed cells
+    pub fn gc(&self, roots: &[TonHash]) -> Result<usize, TLCoreError> {
+        let mut reachable = HashSet::new();
+        let mut queue: Vec<TonHash> = roots.to_vec();
+        while let Some(hash) = queue.pop() {
+            if reachable.contains(&hash) {
+                continue;
+            }
+            if let Some(record) = self.backend.get(&hash)? {
+                queue.extend(CellRecord::from_bytes(&hash, &record)?.refs);
+            }
+            reachable.insert(hash);
+        }
+        let mut removed = 0;
+        for hash in self.backend.keys()? {
+            if !reachable.contains(&hash) {
+                self.backend.delete(&hash)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    // refs are stored before parent, so if cell is stored, its subtree is stored as well
+    fn put_impl(&self, cell: &TonCell, stored: &mut HashSet<TonHash>) -> Result<(), TLCoreError> {
+        let hash = cell.hash()?;
+        if stored.contains(hash) || self.backend.contains(hash)? {
+            return Ok(());
+        }
+        for cell_ref in &cell.refs {
+            self.put_impl(cell_ref, stored)?;
+        }
+        self.backend.put(hash, &CellRecord::from_cell(cell)?.to_bytes())?;
+        stored.insert(hash.clone());
+        Ok(())
+    }
+
+    fn get_impl(&self, hash: &TonHash, loaded: &mut HashMap<TonHash, TonCellRef>) -> Result<TonCellRef, TLCoreError> {
+        if let Some(cell) = loaded.get(hash) {
+            return Ok(cell.clone());
+        }
+        let record = match self.backend.get(hash)? {
+            Some(record) => CellRecord::from_bytes(hash, &record)?,
+            None => return Err(TLCoreError::CellDBWrongData(format!("cell {hash} is not found"))),
+        };
+        let mut refs = Vec::with_capacity(record.refs.len());
+        for ref_hash in &record.refs {
+            refs.push(self.get_impl(ref_hash, loaded)?);
+        }
+        let cell_type = match record.is_exotic {
+            true => CellType::new_exotic(*record.data.first().unwrap_or(&0))?,
+            false => CellType::Ordinary,
+        };
+        let cell = TonCell {
+            cell_type,
+            data: record.data,
+            data_bits_len: record.data_bits_len,
+            refs,
+            meta: CellMeta::default(),
+        };
+        cell.meta.validate(&cell)?;
+        if cell.hash()? != hash {
+            return Err(TLCoreError::CellDBWrongHash {
+                expected: hash.clone(),
+                actual: cell.hash()?.clone(),
+            });
+        }
+        let cell = cell.into_ref();
+        loaded.insert(hash.clone(), cell.clone());
+        Ok(cell)
+    }
+}
+
+impl CellRecord {
+    fn from_cell(cell: &TonCell) -> Result<Self, TLCoreError> {
+        let refs = cell.refs.iter().map(|x| x.hash().cloned()).collect::<Result<_, _>>()?;
+        Ok(Self {
+            is_exotic: cell.cell_type.is_exotic(),
+            data: cell.data[..cell.data_bits_len.div_ceil(8)].to_vec(),
+            data_bits_len: cell.data_bits_len,
+            refs,
+        })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.data.len() + self.refs.len() * TonHash::BYTES_LEN);
+        bytes.push(self.is_exotic as u8);
+        bytes.extend((self.data_bits_len as u16).to_be_bytes());
+        bytes.extend(&self.data);
+        bytes.push(self.refs.len() as u8);
+        for ref_hash in &self.refs {
+            bytes.extend(ref_hash.as_slice());
+        }
+        bytes
+    }
+
+    fn from_bytes(hash: &TonHash, bytes: &[u8]) -> Result<Self, TLCoreError> {
+        let wrong_data = || TLCoreError::CellDBWrongData(format!("cell {hash} has malformed record"));
+        let (&flags, bytes) = bytes.split_first().ok_or_else(wrong_data)?;
+        let (bits_len_bytes, bytes) = bytes.split_at_checked(2).ok_or_else(wrong_data)?;
+        let data_bits_len = u16::from_be_bytes([bits_len_bytes[0], bits_len_bytes[1]]) as usize;
+        let (data, bytes) = bytes.split_at_checked(data_bits_len.div_ceil(8)).ok_or_else(wrong_data)?;
+        let (&refs_cnt, refs) = bytes.split_first().ok_or_else(wrong_data)?;
+        if refs.len() != refs_cnt as usize * TonHash::BYTES_LEN {
+            return Err(wrong_data());
+        }
+        Ok(Self {
+            is_exotic: flags & 1 != 0,
+            data: data.to_vec(),
+            data_bits_len,
+            refs: refs.chunks(TonHash::BYTES_LEN).map(TonHash::from_slice).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::PrunedBranch;
+
+    fn make_cell(value: u32, refs: &[TonCellRef]) -> anyhow::Result<TonCellRef> {
+        let mut builder = TonCell::builder();
+        builder.write_num(&value, 31)?;
+        for cell_ref in refs {
+            builder.write_ref(cell_ref.clone())?;
+        }
+        Ok(builder.build_ref()?)
+    }
+
+    #[test]
+    fn test_cell_db_put_get() -> anyhow::Result<()> {
+        let db = CellDB::new(CellDBMemoryBackend::default());
+        let shared = make_cell(1, &[])?;
+        let pruned = PrunedBranch::create(&*make_cell(2, &[])?, 1)?.into_ref();
+        let root = make_cell(0, &[shared.clone(), make_cell(3, &[shared.clone(), pruned])?])?;
+
+        let root_hash = db.put(&root)?;
+        assert_eq!(&root_hash, root.hash()?);
+        assert_eq!(db.backend().keys()?.len(), 4);
+        assert_eq!(db.get(&root_hash)?, Some(root.clone()));
+        assert_eq!(db.get(root.refs[1].hash()?)?, Some(root.refs[1].clone()));
+        assert_eq!(db.get(&TonHash::ZERO)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_db_gc() -> anyhow::Result<()> {
+        let db = CellDB::new(CellDBMemoryBackend::default());
+        let shared = make_cell(1, &[])?;
+        let root_1 = make_cell(10, &[shared.clone(), make_cell(11, &[])?])?;
+        let root_2 = make_cell(20, std::slice::from_ref(&shared))?;
+        db.put(&root_1)?;
+        db.put(&root_2)?;
+        assert_eq!(db.backend().keys()?.len(), 4);
+
+        assert_eq!(db.gc(&[root_2.hash()?.clone()])?, 2);
+        assert!(!db.contains(root_1.hash()?)?);
+        assert_eq!(db.get(root_2.hash()?)?, Some(root_2.clone()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_db_wrong_data() -> anyhow::Result<()> {
+        let db = CellDB::new(CellDBMemoryBackend::default());
+        let child = make_cell(1, &[])?;
+        let root = make_cell(0, std::slice::from_ref(&child))?;
+        db.put(&root)?;
+
+        db.backend().put(child.hash()?, &[0, 0, 8, 0xFF, 0])?;
+        assert!(matches!(db.get(root.hash()?), Err(TLCoreError::CellDBWrongHash { .. })));
+        db.backend().put(child.hash()?, &[0, 0, 8])?;
+        assert!(matches!(db.get(root.hash()?), Err(TLCoreError::CellDBWrongData(_))));
+        db.backend().delete(child.hash()?)?;
+        assert!(matches!(db.get(root.hash()?), Err(TLCoreError::CellDBWrongData(_))));
+        Ok(())
+    }
+}
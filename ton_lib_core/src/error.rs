@@ -1,3 +1,4 @@
+use crate::cell::{CellType, TonHash};
 use hex::FromHexError;
 use std::env::VarError;
 use std::sync::Arc;
@@ -44,6 +45,36 @@ pub enum TLCoreError {
     #[error("BuilderMeta: Cell validation error: {0}")]
     BuilderMeta(String),
 
+    // cell_text
+    #[error("CellTextWrongFormat: line {line}: {msg}")]
+    CellTextWrongFormat { line: usize, msg: String },
+
+    // exotic cells
+    #[error(
+        "ExoticWrongRefHash: {cell_type:?} cell stores hash={stored} for ref[{ref_pos}], but ref level-0 hash={actual}"
+    )]
+    ExoticWrongRefHash {
+        cell_type: CellType,
+        ref_pos: usize,
+        stored: TonHash,
+        actual: TonHash,
+    },
+    #[error("ExoticWrongRefDepth: {cell_type:?} cell stores depth={stored} for ref[{ref_pos}], but ref level-0 depth={actual}")]
+    ExoticWrongRefDepth {
+        cell_type: CellType,
+        ref_pos: usize,
+        stored: u16,
+        actual: u16,
+    },
+    #[error("PrunedBranchWrongLevel: can't prune cell with level={cell_level} to level={new_level}")]
+    PrunedBranchWrongLevel { cell_level: u8, new_level: u8 },
+    #[error("MerkleUpdateWrongOldRoot: update expects old root hash={expected}, got {actual}")]
+    MerkleUpdateWrongOldRoot { expected: TonHash, actual: TonHash },
+    #[error("MerkleUpdateWrongNewRoot: update expects new root hash={expected}, got {actual}")]
+    MerkleUpdateWrongNewRoot { expected: TonHash, actual: TonHash },
+    #[error("MerkleUpdateUnknownCell: pruned cell with hash={0} is not found in old tree")]
+    MerkleUpdateUnknownCell(TonHash),
+
     // boc
     #[error("BOCEmpty: can't parse BOC from empty slice")]
     BOCEmpty,
@@ -51,6 +82,28 @@ pub enum TLCoreError {
     BOCWrongData(String),
     #[error("BOCSingleRoot: Expected 1 root, got {0}")]
     BOCSingleRoot(usize),
+    #[error("BOCWrongCrc32c: stored crc32c={stored:#010x}, calculated crc32c={actual:#010x}")]
+    BOCWrongCrc32c { stored: u32, actual: u32 },
+    #[error("BOCWrongCellHash: cell[{cell_pos}] stores hash={stored} for level={level}, calculated hash={actual}")]
+    BOCWrongCellHash {
+        cell_pos: usize,
+        level: u8,
+        stored: TonHash,
+        actual: TonHash,
+    },
+    #[error("BOCWrongCellDepth: cell[{cell_pos}] stores depth={stored} for level={level}, calculated depth={actual}")]
+    BOCWrongCellDepth {
+        cell_pos: usize,
+        level: u8,
+        stored: u16,
+        actual: u16,
+    },
+
+    // cell_db
+    #[error("CellDBWrongData: {0}")]
+    CellDBWrongData(String),
+    #[error("CellDBWrongHash: cell is stored with key={expected}, but its hash={actual}")]
+    CellDBWrongHash { expected: TonHash, actual: TonHash },
 
     // tlb
     #[error("TLBWrongData: {0}")]
@@ -2,7 +2,10 @@ pub use ton_lib_macros::*; // re-export
 pub mod bits_utils;
 pub mod boc;
 pub mod cell;
+pub mod cell_db;
 pub mod constants;
 pub mod error;
+#[cfg(feature = "serde")]
+pub mod serde_utils;
 pub mod traits;
 pub mod types;
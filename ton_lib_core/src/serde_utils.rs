@@ -0,0 +1,258 @@
+/// Human-readable serde encodings (`serde` feature):
+/// - TonHash: hex string
+/// - TonAddress, MsgAddress, MsgAddressInt: user-friendly string.
+///   Addresses which can't be represented in user-friendly form (var/anycast/external) are encoded as base64 BOC
+/// - TonCell, TonCellRef: base64 BOC
+/// - TLBObject: as inner value (parsed on serialization).
+///   Deserialization drops the original cell: the value is serialized again, so the hash may differ
+///   from the original one if it had non-canonical encoding (e.g. extra data ignored on parsing)
+/// - VarLen numbers (Coins, VarUInteger): decimal string. Minimal bits_len is restored on deserialization
+///
+/// Use `as_string` and `as_boc_base64` with `#[serde(with = "...")]` for other types
+use crate::cell::{TonCell, TonCellNum, TonCellRef, TonHash};
+use crate::traits::tlb::TLB;
+use crate::types::tlb_core::{MsgAddress, MsgAddressExt, MsgAddressInt, VarLen};
+use crate::types::{TLBObject, TonAddress};
+use num_bigint::BigInt;
+use num_traits::Zero;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub use serde; // re-export for TLBDerive
+
+/// Serialize with `Display`, deserialize with `FromStr`
+pub mod as_string {
+    use super::*;
+
+    pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Any TLB object as base64 BOC
+pub mod as_boc_base64 {
+    use super::*;
+
+    pub fn serialize<T: TLB, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_boc_base64().map_err(serde::ser::Error::custom)?)
+    }
+
+    pub fn deserialize<'de, T: TLB, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::from_boc_b64(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+macro_rules! serde_impl {
+    ($ty:ty, $module:ident) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $module::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $module::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+serde_impl!(TonAddress, as_string);
+serde_impl!(TonCell, as_boc_base64);
+serde_impl!(TonCellRef, as_boc_base64);
+
+impl Serialize for TonHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for TonHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_string::deserialize(deserializer)
+    }
+}
+
+impl<T: TonCellNum, const L: usize, const BL: bool> Serialize for VarLen<T, L, BL> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_string::serialize(&self.data, serializer)
+    }
+}
+
+impl<'de, T, const L: usize, const BL: bool> Deserialize<'de> for VarLen<T, L, BL>
+where
+    T: TonCellNum + FromStr,
+    T::Err: Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data: T = as_string::deserialize(deserializer)?;
+        let value = BigInt::from_str(&data.to_string()).map_err(D::Error::custom)?;
+        let bits_len = match value.is_zero() {
+            true => 0,
+            false => value.bits() as usize + T::SIGNED as usize,
+        };
+        let bits_len = if BL { bits_len.div_ceil(8) * 8 } else { bits_len };
+        Ok(Self { data, bits_len })
+    }
+}
+
+impl<T: TLB + Serialize> Serialize for TLBObject<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().map_err(serde::ser::Error::custom)?.serialize(serializer)
+    }
+}
+
+// original cell is not restored, see module docs
+impl<'de, T: TLB + Deserialize<'de>> Deserialize<'de> for TLBObject<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::new(T::deserialize(deserializer)?))
+    }
+}
+
+impl Serialize for MsgAddressInt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MsgAddressInt::Std(std) if std.anycast.is_none() => {
+                TonAddress::new(std.workchain as i32, std.address.clone()).serialize(serializer)
+            }
+            _ => as_boc_base64::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MsgAddressInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        match TonAddress::from_str(&value) {
+            Ok(address) => Ok(address.to_msg_address_int()),
+            Err(_) => MsgAddressInt::from_boc_b64(&value).map_err(D::Error::custom),
+        }
+    }
+}
+
+/// MsgAddressNone is encoded as null
+impl Serialize for MsgAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MsgAddress::Int(address) => address.serialize(serializer),
+            MsgAddress::Ext(MsgAddressExt::None(_)) => serializer.serialize_none(),
+            MsgAddress::Ext(_) => as_boc_base64::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MsgAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Some(value) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(MsgAddress::NONE);
+        };
+        match TonAddress::from_str(&value) {
+            Ok(address) => Ok(address.to_msg_address()),
+            Err(_) => MsgAddress::from_boc_b64(&value).map_err(D::Error::custom),
+        }
+    }
+}
+
+/// MsgAddressNone is encoded as null
+impl Serialize for MsgAddressExt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MsgAddressExt::None(_) => serializer.serialize_none(),
+            MsgAddressExt::Extern(_) => as_boc_base64::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MsgAddressExt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            None => Ok(MsgAddressExt::NONE),
+            Some(value) => MsgAddressExt::from_boc_b64(&value).map_err(D::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellType;
+    use crate::types::tlb_core::{Anycast, MsgAddressIntStd};
+    use serde_json::json;
+    use ton_lib_macros::TLBDerive;
+
+    #[derive(Debug, PartialEq, TLBDerive)]
+    #[tlb_derive(prefix = 0x1, bits_len = 4, serde_boc = true)]
+    struct TestSerdeBoc<T: TLB> {
+        value: T,
+    }
+
+    #[test]
+    fn test_serde_core_types() -> anyhow::Result<()> {
+        let hash = TonHash::from([0xAB; 32]);
+        assert_eq!(serde_json::to_value(&hash)?, json!("ab".repeat(32)));
+        assert_eq!(serde_json::from_value::<TonHash>(json!("AB".repeat(32)))?, hash);
+
+        let address = TonAddress::from_str("EQDk2VTvn04SUKJrW7rXahzdF8_Qi6utb0wj43InCu9vdjrR")?;
+        let address_json = serde_json::to_value(&address)?;
+        assert_eq!(address_json, json!("EQDk2VTvn04SUKJrW7rXahzdF8_Qi6utb0wj43InCu9vdjrR"));
+        assert_eq!(serde_json::from_value::<TonAddress>(address_json.clone())?, address);
+        assert_eq!(serde_json::to_value(address.to_msg_address())?, address_json);
+        assert_eq!(serde_json::from_value::<MsgAddress>(address_json)?, address.to_msg_address());
+
+        let mut builder = TonCell::builder();
+        builder.write_num(&(CellType::MerkleProof as u8), 8)?;
+        let cell = builder.build()?;
+        let cell_json = serde_json::to_value(&cell)?;
+        assert_eq!(cell_json, json!(cell.to_boc_base64()?));
+        assert_eq!(serde_json::from_value::<TonCellRef>(cell_json)?, cell.into_ref());
+
+        let var_len = VarLen::<u64, 4, true>::new(1000u64, 16);
+        assert_eq!(serde_json::to_value(&var_len)?, json!("1000"));
+        assert_eq!(serde_json::from_value::<VarLen<u64, 4, true>>(json!("1000"))?, var_len);
+        let var_len_bits = serde_json::from_value::<VarLen<i32, 5, false>>(json!("-5"))?;
+        assert_eq!(var_len_bits.bits_len, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_msg_address_fallbacks() -> anyhow::Result<()> {
+        assert_eq!(serde_json::to_value(&MsgAddress::NONE)?, json!(null));
+        assert_eq!(serde_json::from_value::<MsgAddress>(json!(null))?, MsgAddress::NONE);
+
+        let addresses = [
+            MsgAddressExt::new(vec![0xAB], 8).into(),
+            MsgAddress::Int(MsgAddressInt::Std(MsgAddressIntStd {
+                anycast: Some(Anycast::new(3, vec![0b1010_0000])),
+                workchain: 0,
+                address: TonHash::from([0x12; 32]),
+            })),
+        ];
+        for address in addresses {
+            let address_json = serde_json::to_value(&address)?;
+            assert_eq!(address_json, json!(address.to_boc_base64()?));
+            assert_eq!(serde_json::from_value::<MsgAddress>(address_json)?, address);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_tlb_derive_boc() -> anyhow::Result<()> {
+        let obj = TestSerdeBoc { value: 5u16 };
+        let obj_json = serde_json::to_value(&obj)?;
+        assert_eq!(obj_json, json!(obj.to_boc_base64()?));
+        assert_eq!(serde_json::from_value::<TestSerdeBoc<u16>>(obj_json)?, obj);
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ mod tlb_cell;
 mod tlb_num;
 mod tlb_opt;
 mod tlb_ptr;
+mod tlb_unit;
 
 use crate::boc::BOC;
 use crate::cell::CellBuilder;
@@ -140,3 +141,100 @@ impl TLBPrefix {
     pub const NULL: TLBPrefix = TLBPrefix::new(0, 0);
     pub const fn new(value: usize, bits_len: usize) -> Self { TLBPrefix { value, bits_len } }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ton_lib_macros::TLBDerive;
+
+    #[derive(Debug, PartialEq, TLBDerive)]
+    struct TestCondType {
+        flags: u8,
+        bits_len: u8,
+        #[tlb_derive(bits_len_from = "bits_len")]
+        value: u32,
+        #[tlb_derive(if = "self.flags & 1 != 0")]
+        ext: Option<u16>,
+        #[tlb_derive(if = "self.flags & 2 != 0")]
+        ext_ref: Option<TonCellRef>,
+    }
+
+    #[derive(Debug, PartialEq, TLBDerive)]
+    #[tlb_derive(prefix = 0b101, bits_len = 3)]
+    struct TestGenericType<T: TLB> {
+        value: T,
+    }
+
+    #[derive(Debug, PartialEq, TLBDerive)]
+    enum TestTaggedEnum {
+        #[tlb_derive(prefix = 0b0, bits_len = 1)]
+        Empty,
+        #[tlb_derive(prefix = 0b10, bits_len = 2)]
+        Named {
+            len: u8,
+            #[tlb_derive(bits_len_from = "len")]
+            value: u16,
+        },
+        #[tlb_derive(prefix = 0b11, bits_len = 2)]
+        Tuple(u8, bool),
+    }
+
+    #[test]
+    fn test_tlb_derive_cond_fields() -> anyhow::Result<()> {
+        let obj = TestCondType {
+            flags: 1,
+            bits_len: 5,
+            value: 31,
+            ext: Some(0xABCD),
+            ext_ref: None,
+        };
+        let cell = obj.to_cell()?;
+        assert_eq!(cell.data_bits_len, 8 + 8 + 5 + 16);
+        assert_eq!(TestCondType::from_cell(&cell)?, obj);
+
+        // value is ignored if condition is false
+        let obj_no_ext = TestCondType { flags: 0, ..obj };
+        let parsed = TestCondType::from_cell(&obj_no_ext.to_cell()?)?;
+        assert_eq!(parsed.ext, None);
+
+        let obj_missing = TestCondType {
+            flags: 2,
+            bits_len: 5,
+            value: 1,
+            ext: None,
+            ext_ref: None,
+        };
+        assert!(obj_missing.to_cell().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_derive_enum_tagged_variants() -> anyhow::Result<()> {
+        let variants = [
+            (TestTaggedEnum::Empty, 1),
+            (TestTaggedEnum::Named { len: 9, value: 300 }, 2 + 8 + 9),
+            (TestTaggedEnum::Tuple(7, true), 2 + 8 + 1),
+        ];
+        for (variant, bits_len) in variants {
+            let cell = variant.to_cell()?;
+            assert_eq!(cell.data_bits_len, bits_len);
+            assert_eq!(TestTaggedEnum::from_cell(&cell)?, variant);
+        }
+        assert!(TestTaggedEnum::Empty.is_empty());
+
+        let mut builder = TonCell::builder();
+        builder.write_bit(true)?;
+        assert!(TestTaggedEnum::from_cell(&builder.build()?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_derive_generic() -> anyhow::Result<()> {
+        let obj = TestGenericType { value: 7u16 };
+        let cell = obj.to_cell()?;
+        assert_eq!(cell.data_bits_len, 3 + 16);
+        assert_eq!(TestGenericType::<u16>::from_cell(&cell)?, obj);
+        assert!(TestGenericType::<u32>::from_cell(&cell).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use crate::cell::CellBuilder;
+use crate::cell::CellParser;
+use crate::error::TLCoreError;
+use crate::traits::tlb::TLB;
+
+// true$_ = True;
+impl TLB for () {
+    fn read_definition(_parser: &mut CellParser) -> Result<Self, TLCoreError> { Ok(()) }
+    fn write_definition(&self, _builder: &mut CellBuilder) -> Result<(), TLCoreError> { Ok(()) }
+}
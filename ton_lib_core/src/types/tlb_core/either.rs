@@ -7,6 +7,7 @@ use crate::traits::tlb::TLB;
 ///
 /// https://github.com/ton-blockchain/ton/blame/cac968f77dfa5a14e63db40190bda549f0eaf746/crypto/block/block.tlb#L10
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TLBEither<L, R> {
     Left(L),
     Right(R),
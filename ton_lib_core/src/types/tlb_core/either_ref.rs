@@ -8,12 +8,14 @@ use std::ops::{Deref, DerefMut};
 ///
 /// https://github.com/ton-blockchain/ton/blame/cac968f77dfa5a14e63db40190bda549f0eaf746/crypto/block/block.tlb#L10
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TLBEitherRef<T> {
     pub value: T,
     pub layout: EitherRefLayout,
 }
 
 #[derive(Clone, Debug, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EitherRefLayout {
     ToCell,
     ToRef,
@@ -39,7 +41,7 @@ impl<T: TLB> TLB for TLBEitherRef<T> {
                 layout: EitherRefLayout::ToCell,
             },
             true => TLBEitherRef {
-                value: TLB::from_cell(parser.read_next_ref()?)?,
+                value: TLB::read(&mut parser.read_next_ref_parser()?)?,
                 layout: EitherRefLayout::ToRef,
             },
         };
@@ -27,6 +27,14 @@ impl<T, const LEN_BITS_LEN: usize, const LEN_IN_BYTES: bool> VarLen<T, LEN_BITS_
     }
 }
 
+impl<const LEN_BITS_LEN: usize> VarLenBytes<u64, LEN_BITS_LEN> {
+    /// Uses minimal number of bytes to store data
+    pub fn new_min_len(data: u64) -> Self {
+        let bits_len = (64 - data.leading_zeros()).div_ceil(8) * 8;
+        Self::new(data, bits_len as usize)
+    }
+}
+
 impl<T, const L: usize, const BL: bool> Deref for VarLen<T, L, BL> {
     type Target = T;
     fn deref(&self) -> &Self::Target { &self.data }
@@ -120,6 +128,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_block_tlb_var_len_bytes_min_len() -> anyhow::Result<()> {
+        assert_eq!(VarLenBytes::<u64, 3>::new_min_len(0).bits_len, 0);
+        assert_eq!(VarLenBytes::<u64, 3>::new_min_len(255).bits_len, 8);
+        assert_eq!(VarLenBytes::<u64, 3>::new_min_len(256).bits_len, 16);
+        assert_eq!(VarLenBytes::<u64, 3>::new_min_len(u64::MAX).bits_len, 64);
+
+        let obj = VarLenBytes::<u64, 3>::new_min_len(1000);
+        assert_eq!(VarLenBytes::<u64, 3>::from_cell(&obj.to_cell()?)?, obj);
+        Ok(())
+    }
+
     #[test]
     fn test_block_tlb_var_len_bytes_big_uint() -> anyhow::Result<()> {
         // len in bytes
@@ -1,68 +1,188 @@
-use crate::cell::TonCellRef;
+use crate::cell::{CellBuilder, CellParser, TonCellRef};
+use crate::error::TLCoreError;
 use crate::traits::tlb::TLB;
+use once_cell::sync::OnceCell;
+use std::fmt::{Debug, Formatter};
 
-// use crate::cell::build_parse::builder::CellBuilder;
-// use crate::cell::build_parse::parser::CellParser;
-// use crate::cell::ton_cell::TonCell;
-// use crate::errors::TonLibError;
-//
-/// Can be use for Lazy loading of TLB objects
-#[allow(unused)]
-#[derive(Debug, Clone)]
+/// Lazy wrapper for `^T` fields
+///
+/// Keeps the original cell and parses it on the first access only, parsed value is cached.
+/// Until the value is modified with `get_mut`, the original cell is written back unchanged
+#[derive(Clone)]
 pub struct TLBObject<T: TLB> {
-    plain: Option<T>,
-    cell_ref: Option<TonCellRef>,
+    cell_ref: OnceCell<TonCellRef>,
+    plain: OnceCell<T>,
+}
+
+impl<T: TLB> TLBObject<T> {
+    pub fn new(plain: T) -> Self {
+        Self {
+            cell_ref: OnceCell::new(),
+            plain: OnceCell::from(plain),
+        }
+    }
+
+    pub fn from_cell_ref(cell_ref: TonCellRef) -> Self {
+        Self {
+            cell_ref: OnceCell::from(cell_ref),
+            plain: OnceCell::new(),
+        }
+    }
+
+    pub fn is_parsed(&self) -> bool { self.plain.get().is_some() }
+
+    /// Parses the cell on the first call
+    pub fn get(&self) -> Result<&T, TLCoreError> {
+        self.plain.get_or_try_init(|| match self.cell_ref.get() {
+            Some(cell_ref) => T::from_cell(cell_ref),
+            None => Err(TLCoreError::TLBObjectNoValue("TLBObject::get".to_string())),
+        })
+    }
+
+    /// Drops the original cell: it will be rebuilt from the modified value
+    pub fn get_mut(&mut self) -> Result<&mut T, TLCoreError> {
+        self.get()?;
+        self.cell_ref.take();
+        self.plain.get_mut().ok_or(TLCoreError::TLBObjectNoValue("TLBObject::get_mut".to_string()))
+    }
+
+    pub fn into_inner(self) -> Result<T, TLCoreError> {
+        self.get()?;
+        self.plain.into_inner().ok_or(TLCoreError::TLBObjectNoValue("TLBObject::into_inner".to_string()))
+    }
+
+    /// Serializes the value on the first call if the object was created from plain value
+    pub fn cell_ref(&self) -> Result<&TonCellRef, TLCoreError> {
+        self.cell_ref.get_or_try_init(|| match self.plain.get() {
+            Some(plain) => plain.to_cell_ref(),
+            None => Err(TLCoreError::TLBObjectNoValue("TLBObject::cell_ref".to_string())),
+        })
+    }
+}
+
+impl<T: TLB> TLB for TLBObject<T> {
+    fn read_definition(parser: &mut CellParser) -> Result<Self, TLCoreError> {
+        Ok(Self::from_cell_ref(TonCellRef::read(parser)?))
+    }
+
+    fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TLCoreError> { self.cell_ref()?.write(builder) }
+}
+
+impl<T: TLB> From<T> for TLBObject<T> {
+    fn from(plain: T) -> Self { Self::new(plain) }
+}
+
+impl<T: TLB + Default> Default for TLBObject<T> {
+    fn default() -> Self { Self::new(T::default()) }
+}
+
+/// Parsed values are compared if both objects are parsed, cell hashes are compared otherwise
+///
+/// Comparing by hash serializes the plain value if the object has no cell yet (the cell is cached).
+/// If serialization or parsing fails, objects are considered not equal
+impl<T: TLB + PartialEq> PartialEq for TLBObject<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if let (Some(plain), Some(other_plain)) = (self.plain.get(), other.plain.get()) {
+            return plain == other_plain;
+        }
+        let hash = self.cell_ref().and_then(|cell| cell.hash().cloned());
+        let other_hash = other.cell_ref().and_then(|cell| cell.hash().cloned());
+        match (hash, other_hash) {
+            (Ok(hash), Ok(other_hash)) => hash == other_hash,
+            _ => false,
+        }
+    }
+}
+
+impl<T: TLB + Debug> Debug for TLBObject<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.plain.get(), self.cell_ref.get()) {
+            (Some(plain), _) => f.debug_tuple("TLBObject").field(plain).finish(),
+            (None, Some(cell_ref)) => write!(f, "TLBObject(not parsed, hash={:?})", cell_ref.hash()),
+            (None, None) => write!(f, "TLBObject(empty)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::TonCell;
+    use ton_lib_macros::TLBDerive;
+
+    #[derive(Debug, Clone, PartialEq, TLBDerive)]
+    struct TestObject {
+        value: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, TLBDerive)]
+    struct TestParent {
+        child: TLBObject<TestObject>,
+        opt_child: Option<TLBObject<TestObject>>,
+    }
+
+    #[test]
+    fn test_tlb_object_lazy() -> anyhow::Result<()> {
+        let parent = TestParent {
+            child: TestObject { value: 1 }.into(),
+            opt_child: Some(TestObject { value: 2 }.into()),
+        };
+        let cell = parent.to_cell()?;
+        assert_eq!(cell.refs.len(), 2);
+
+        let parsed = TestParent::from_cell(&cell)?;
+        assert!(!parsed.child.is_parsed());
+        assert_eq!(parsed, parent);
+        assert_eq!(parsed.child.get()?.value, 1);
+        assert!(parsed.child.is_parsed());
+        assert_eq!(parsed.opt_child.clone().unwrap().into_inner()?.value, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_object_eq() -> anyhow::Result<()> {
+        let plain = TLBObject::new(TestObject { value: 1 });
+        let cell_ref = TestObject { value: 1 }.to_cell_ref()?;
+        assert_eq!(plain, TLBObject::from_cell_ref(cell_ref.clone()));
+        assert_ne!(plain, TLBObject::new(TestObject { value: 2 }));
+
+        // parsed values are equal, but cells are not: extra data is ignored on parsing
+        let mut builder = TonCell::builder();
+        builder.write_num(&1u32, 32)?;
+        builder.write_num(&0xFFu8, 8)?;
+        let extra_data = TLBObject::<TestObject>::from_cell_ref(builder.build_ref()?);
+        let not_parsed = TLBObject::<TestObject>::from_cell_ref(cell_ref);
+        assert_ne!(extra_data, not_parsed);
+        extra_data.get()?;
+        not_parsed.get()?;
+        assert_eq!(extra_data, not_parsed);
+
+        // object which can't be parsed is compared by hash
+        let broken = TLBObject::<TestObject>::from_cell_ref(TonCell::EMPTY.into_ref());
+        assert!(broken.get().is_err());
+        assert_ne!(broken, plain);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_object_writes_original_cell() -> anyhow::Result<()> {
+        // ensure_empty is not set, so extra data is ignored on parsing, but must be kept on writing
+        let mut builder = TonCell::builder();
+        builder.write_num(&7u32, 32)?;
+        builder.write_num(&0xFFu8, 8)?;
+        let child_cell = builder.build_ref()?;
+        let mut builder = TonCell::builder();
+        builder.write_ref(child_cell.clone())?;
+        let cell = builder.build()?;
+
+        let mut object = TLBObject::<TestObject>::from_cell(&cell)?;
+        assert_eq!(object.get()?.value, 7);
+        assert_eq!(object.to_cell()?, cell);
+
+        object.get_mut()?.value = 8;
+        let written = object.to_cell()?;
+        assert_ne!(written.refs[0], child_cell);
+        assert_eq!(TestObject::from_cell(&written.refs[0])?.value, 8);
+        Ok(())
+    }
 }
-//
-// impl<T: TLBType> TLBObject<T> {
-//     pub fn from_cell(cell: &TonCell) -> Self {
-//         Self {
-//             plain: None,
-//             cell: Some(cell.clone()),
-//         }
-//     }
-//
-//     pub fn from_plain(plain: T) -> Self {
-//         Self {
-//             plain: Some(plain),
-//             cell: None,
-//         }
-//     }
-//
-//     pub fn read(&self) -> Result<T, TonLibError> {
-//         if let Some(plain) = &self.plain {
-//             return Ok(plain.clone());
-//         }
-//         if let Some(cell) = &self.cell {
-//             return T::from_cell(cell);
-//         }
-//         Err(TonLibError::TLBObjectNoValue("TLBObject::read".to_string()))
-//     }
-//
-//     pub fn read_inplace(&mut self) -> Result<&T, TonLibError> {
-//         if self.plain.is_none() {
-//             self.plain = Some(self.read()?);
-//         }
-//         Ok(self.plain.as_ref().unwrap())
-//     }
-// }
-//
-// impl<T: TLBType> TLBType for TLBObject<T> {
-//     fn read_definition(parser: &mut CellParser) -> Result<Self, TonLibError> {
-//         let cell = parser.read_cell()?;
-//         Ok(Self {
-//             plain: None,
-//             cell: Some(cell),
-//         })
-//     }
-//
-//     fn write_definition(&self, builder: &mut CellBuilder) -> Result<(), TonLibError> {
-//         if let Some(cell) = &self.cell {
-//             return cell.write(builder);
-//         }
-//         if let Some(plain) = &self.plain {
-//             return plain.write(builder);
-//         }
-//         Err(TonLibError::TLBObjectNoValue("TLBObject::<TLBType>::write_def".to_string()))
-//     }
-// }
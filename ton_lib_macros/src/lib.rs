@@ -1,9 +1,11 @@
 mod tlb_derive;
 mod tlb_derive_enum;
 mod tlb_derive_struct;
+mod tlb_schema;
 mod ton_contract;
 
 use crate::tlb_derive::{tlb_derive_impl, TLBHeaderAttrs};
+use crate::tlb_schema::{tlb_schema_file_impl, tlb_schema_impl};
 use crate::ton_contract::ton_contract_impl;
 use proc_macro::TokenStream;
 
@@ -11,6 +13,22 @@ use proc_macro::TokenStream;
 #[proc_macro_derive(TLBDerive, attributes(tlb_derive))]
 pub fn tlb_derive(input: TokenStream) -> TokenStream { tlb_derive_impl(input).into() }
 
+/// Generates types with `TLB` implementation from TL-B schema
+///
+/// Single-constructor type becomes a struct, otherwise every constructor becomes a struct and type becomes an enum.
+/// Supported: tags (`#hex`, `$bin`), `##`, `#<=`, `#<`, `#`, `uintN`, `intN`, `bits256`, `Bool`, `^X`, `^Cell`,
+/// `Maybe X`, `Either X Y`, `HashmapE n X`, conditional `cond?X`, `flags.bit?X` (as `Option`) and other types in scope
+/// Constructor without explicit tag gets implicit CRC32 tag like in tlbc, only `#_`, `$_` and `_` mean empty tag
+///
+/// Not supported: implicit fields and constraints `{...}`, parametrized types, anonymous cells `^[...]`,
+/// so it doesn't cover whole `block.tlb` (see ton_lib_tlb_schema README)
+#[proc_macro]
+pub fn tlb_schema(input: TokenStream) -> TokenStream { tlb_schema_impl(input).into() }
+
+/// Same as `tlb_schema!`, but reads schema from file (path is relative to CARGO_MANIFEST_DIR)
+#[proc_macro]
+pub fn tlb_schema_file(input: TokenStream) -> TokenStream { tlb_schema_file_impl(input).into() }
+
 /// Automatic `TonContract` implementation
 #[proc_macro_attribute]
 pub fn ton_contract(_attr: TokenStream, item: TokenStream) -> TokenStream { ton_contract_impl(_attr, item) }
@@ -11,6 +11,7 @@ pub(crate) struct TLBHeaderAttrs {
     pub(crate) prefix: Option<usize>,      // use 0 as default
     pub(crate) bits_len: Option<usize>,    // use 0 as default
     pub(crate) ensure_empty: Option<bool>, // use false as default
+    pub(crate) serde_boc: Option<bool>,    // implement serde as base64 BOC, requires `serde` feature of ton_lib_core
 }
 
 #[derive(deluxe::ExtractAttributes)]
@@ -18,17 +19,13 @@ pub(crate) struct TLBHeaderAttrs {
 pub(crate) struct TLBFieldAttrs {
     pub(crate) bits_len: Option<u32>, // alias for ConstLen adapter
     pub(crate) adapter: Option<String>,
+    #[deluxe(rename = if)]
+    pub(crate) cond: Option<syn::LitStr>, // Option<T> field is read only if condition (using self.other_field) is true
+    pub(crate) bits_len_from: Option<String>, // field with bits_len of number
 }
 
-pub(crate) fn tlb_derive_impl(input: proc_macro::TokenStream) -> TokenStream {
-    let mut input = syn::parse::<syn::DeriveInput>(input).unwrap();
-    // Extract a description, modifying `input.attrs` to remove the matched attributes.
-    let header_attrs: TLBHeaderAttrs = match deluxe::extract_attributes(&mut input) {
-        Ok(desc) => desc,
-        Err(e) => return e.into_compile_error(),
-    };
-
-    let crate_path = if let Ok(ton_lib_core_crate) = crate_name("ton_lib_core") {
+pub(crate) fn core_crate_path() -> TokenStream {
+    if let Ok(ton_lib_core_crate) = crate_name("ton_lib_core") {
         match ton_lib_core_crate {
             FoundCrate::Itself => quote::quote! { crate },
             FoundCrate::Name(name) => {
@@ -46,21 +43,44 @@ pub(crate) fn tlb_derive_impl(input: proc_macro::TokenStream) -> TokenStream {
         }
     } else {
         panic!("Can't find ton_lib_core or ton_lib crate");
+    }
+}
+
+pub(crate) fn tlb_derive_impl(input: proc_macro::TokenStream) -> TokenStream {
+    let mut input = syn::parse::<syn::DeriveInput>(input).unwrap();
+    // Extract a description, modifying `input.attrs` to remove the matched attributes.
+    let header_attrs: TLBHeaderAttrs = match deluxe::extract_attributes(&mut input) {
+        Ok(desc) => desc,
+        Err(e) => return e.into_compile_error(),
     };
 
+    let crate_path = core_crate_path();
+
     let ident = &input.ident;
 
-    let (read_def_tokens, write_def_tokens, extra_impl_tokens) = match &mut input.data {
-        Data::Struct(data) => tlb_derive_struct(&header_attrs, data),
-        Data::Enum(data) => tlb_derive_enum(&crate_path, ident, data),
+    let generics = input.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let derived = match &mut input.data {
+        Data::Struct(data) => tlb_derive_struct(&crate_path, &header_attrs, data),
+        Data::Enum(data) => tlb_derive_enum(&crate_path, ident, &generics, data),
         _ => panic!("TLBDerive only supports structs and enums"),
     };
+    let (read_def_tokens, write_def_tokens, extra_impl_tokens) = match derived {
+        Ok(derived) => derived,
+        Err(err) => return err.into_compile_error(),
+    };
+
+    let serde_impl_tokens = match header_attrs.serde_boc.unwrap_or(false) {
+        true => serde_boc_impl(&crate_path, ident, &generics),
+        false => quote! {},
+    };
 
     let prefix_val = header_attrs.prefix.unwrap_or(0);
     let prefix_bits_len = header_attrs.bits_len.unwrap_or(0);
 
     quote::quote! {
-        impl #crate_path::traits::tlb::TLB for #ident {
+        impl #impl_generics #crate_path::traits::tlb::TLB for #ident #ty_generics #where_clause {
             const PREFIX: #crate_path::traits::tlb::TLBPrefix = #crate_path::traits::tlb::TLBPrefix::new(#prefix_val, #prefix_bits_len);
 
             fn read_definition(parser: &mut #crate_path::cell::CellParser) -> Result<Self, #crate_path::error::TLCoreError> {
@@ -75,5 +95,28 @@ pub(crate) fn tlb_derive_impl(input: proc_macro::TokenStream) -> TokenStream {
         }
 
         #extra_impl_tokens
+        #serde_impl_tokens
+    }
+}
+
+fn serde_boc_impl(crate_path: &TokenStream, ident: &syn::Ident, generics: &syn::Generics) -> TokenStream {
+    let serde_utils = quote! { #crate_path::serde_utils };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut de_generics = generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #serde_utils::serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<S: #serde_utils::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #serde_utils::as_boc_base64::serialize(self, serializer)
+            }
+        }
+
+        impl #de_impl_generics #serde_utils::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<D: #serde_utils::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #serde_utils::as_boc_base64::deserialize(deserializer)
+            }
+        }
     }
 }
@@ -1,48 +1,88 @@
+use crate::tlb_derive::TLBHeaderAttrs;
+use crate::tlb_derive_struct::{extract_fields_info, field_read_write, FieldInfo};
 use convert_case::{Case, Casing};
 use deluxe::____private::Ident;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{DataEnum, Fields};
+use syn::{parse_quote, DataEnum, Expr, Fields, Generics, Variant};
+
+struct VariantInfo {
+    ident: Ident,
+    tag: Option<(usize, usize)>, // (prefix, bits_len)
+    ensure_empty: bool,
+    fields: Vec<FieldInfo>,
+    locals: Vec<Ident>,
+    pattern: TokenStream, // Self::Variant { a, b } / Self::Variant(field_0) / Self::Variant
+}
 
 pub(crate) fn tlb_derive_enum(
     crate_path: &TokenStream,
     ident: &Ident,
+    generics: &Generics,
     data: &mut DataEnum,
-) -> (TokenStream, TokenStream, TokenStream) {
-    let variant_readers = data.variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        // Expect single unnamed field (like `Std(...)`)
-        let Fields::Unnamed(fields) = &variant.fields else {
-            panic!("tlb_derive_enum only supports tuple-like enums");
-        };
-        if fields.unnamed.len() != 1 {
-            panic!("Each enum variant must have exactly one unnamed field");
-        }
-        let field_type = &fields.unnamed.first().unwrap().ty;
-        quote! {
-                match #field_type::read(parser) {
-                    Ok(res) => return Ok(#ident::#variant_name(res)),
+) -> syn::Result<(TokenStream, TokenStream, TokenStream)> {
+    let variants = data.variants.iter_mut().map(extract_variant_info).collect::<Vec<_>>();
+
+    let mut variant_readers = Vec::with_capacity(variants.len());
+    for variant in &variants {
+        let pattern = &variant.pattern;
+        let Some((prefix, bits_len)) = variant.tag else {
+            // no tag: try to read inner type, which is expected to check its own prefix
+            let field_type = &variant.fields[0].ty;
+            let variant_name = &variant.ident;
+            variant_readers.push(quote! {
+                match <#field_type as #crate_path::traits::tlb::TLB>::read(parser) {
+                    Ok(res) => return Ok(Self::#variant_name(res)),
                     Err(#crate_path::error::TLCoreError::TLBWrongPrefix { .. }) => {},
                     Err(#crate_path::error::TLCoreError::TLBEnumOutOfOptions { .. }) => {},
                     Err(err) => return Err(err),
                 };
+            });
+            continue;
+        };
+        let mut field_readers = Vec::with_capacity(variant.fields.len());
+        for (pos, field) in variant.fields.iter().enumerate() {
+            let local = field.local_ident();
+            let (read_value, _) = field_read_write(crate_path, field, &variant.locals[..pos], deref_local)?;
+            field_readers.push(quote!(let #local = #read_value;));
         }
-    });
-
-    let variant_writers = data.variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        // Expect single unnamed field (like `Std(...)`)
-        let Fields::Unnamed(fields) = &variant.fields else {
-            panic!("tlb_derive_enum only supports tuple-like enums");
+        let ensure_empty = match variant.ensure_empty {
+            true => quote!(parser.ensure_empty()?;),
+            false => quote!(),
         };
+        variant_readers.push(quote! {
+            if parser.data_bits_remaining()? >= #bits_len && parser.lookup_bits(#bits_len)? == #prefix as u128 {
+                parser.read_num::<u128>(#bits_len)?;
+                #(#field_readers)*
+                #ensure_empty
+                return Ok(#pattern);
+            }
+        });
+    }
 
-        if fields.unnamed.len() != 1 {
-            panic!("Each enum variant must have exactly one unnamed field");
-        }
-        quote! {
-            Self::#variant_name(ref value) => value.write(builder)?,
+    let mut variant_writers = Vec::with_capacity(variants.len());
+    for variant in &variants {
+        let pattern = &variant.pattern;
+        let write_tag = match variant.tag {
+            Some((prefix, bits_len)) => quote!(builder.write_num(&#prefix, #bits_len)?;),
+            None => quote!(),
+        };
+        let mut field_writers = Vec::with_capacity(variant.fields.len());
+        for (pos, field) in variant.fields.iter().enumerate() {
+            let local = field.local_ident();
+            let (_, write_value) = field_read_write(crate_path, field, &variant.locals[..pos], deref_local)?;
+            field_writers.push(quote!({
+                let value = #local;
+                #write_value
+            }));
         }
-    });
+        variant_writers.push(quote! {
+            #pattern => {
+                #write_tag
+                #(#field_writers)*
+            }
+        });
+    }
 
     let ident_str = ident.to_string();
 
@@ -58,20 +98,59 @@ pub(crate) fn tlb_derive_enum(
         Ok(())
     };
 
-    let variants_access = variants_access_impl(ident, data);
-    let variants_into = variants_into_impl(ident, data);
+    let variants_access = variants_access_impl(ident, generics, data);
+    let variants_into = variants_into_impl(ident, generics, data);
     let extra_impl = quote! {
         #variants_access
         #variants_into
     };
 
     // impl
-    (read_impl, write_impl, extra_impl)
+    Ok((read_impl, write_impl, extra_impl))
+}
+
+// variant fields are bound by reference in write pattern
+fn deref_local(local: &Ident) -> Expr { parse_quote!((*#local)) }
+
+fn extract_variant_info(variant: &mut Variant) -> VariantInfo {
+    let variant_name = variant.ident.clone();
+    let attrs: TLBHeaderAttrs = match deluxe::extract_attributes(&mut variant.attrs) {
+        Ok(attrs) => attrs,
+        Err(err) => panic!("Failed to parse attributes of variant {variant_name}: {err}"),
+    };
+    let tag = match attrs.bits_len {
+        Some(0) | None => None,
+        Some(bits_len) => Some((attrs.prefix.unwrap_or(0), bits_len)),
+    };
+    let is_single_tuple = matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1);
+    if tag.is_none() && !is_single_tuple {
+        panic!("Variant {variant_name} must have tlb_derive(prefix, bits_len) or exactly one unnamed field");
+    }
+
+    let fields = extract_fields_info(&mut variant.fields);
+    if fields.iter().any(|x| x.attrs.cond.is_some()) {
+        panic!("'if' attribute is not supported in enum variants");
+    }
+    let locals = fields.iter().map(|x| x.local_ident()).collect::<Vec<_>>();
+    let pattern = match &variant.fields {
+        Fields::Named(_) => quote!(Self::#variant_name { #(#locals),* }),
+        Fields::Unnamed(_) => quote!(Self::#variant_name(#(#locals),*)),
+        Fields::Unit => quote!(Self::#variant_name),
+    };
+    VariantInfo {
+        ident: variant_name,
+        tag,
+        ensure_empty: attrs.ensure_empty.unwrap_or(false),
+        fields,
+        locals,
+        pattern,
+    }
 }
 
-// generate as_X and is_X methods for each enum variant
-fn variants_into_impl(ident: &Ident, data: &mut DataEnum) -> TokenStream {
-    let from_impls = data.variants.iter().map(|variant| {
+// generate From<T> for each single-field tuple variant
+fn variants_into_impl(ident: &Ident, generics: &Generics, data: &mut DataEnum) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let from_impls = data.variants.iter().filter_map(|variant| {
         let variant_name = &variant.ident;
 
         match &variant.fields {
@@ -79,14 +158,14 @@ fn variants_into_impl(ident: &Ident, data: &mut DataEnum) -> TokenStream {
                 let ty = &fields.unnamed.first().unwrap().ty;
 
                 Some(quote! {
-                    impl From<#ty> for #ident {
+                    impl #impl_generics From<#ty> for #ident #ty_generics #where_clause {
                         fn from(v: #ty) -> Self {
                             #ident::#variant_name(v)
                         }
                     }
                 })
             }
-            _ => panic!("variants_into_impl supports only tuple-like enums "),
+            _ => None,
         }
     });
     quote! {
@@ -94,14 +173,16 @@ fn variants_into_impl(ident: &Ident, data: &mut DataEnum) -> TokenStream {
     }
 }
 
-// generate as_X and is_X methods for each enum variant
-fn variants_access_impl(ident: &Ident, data: &mut DataEnum) -> TokenStream {
+// generate as_X, as_X_mut and into_X methods for single-field tuple variants, is_X for unit variants
+fn variants_access_impl(ident: &Ident, generics: &Generics, data: &mut DataEnum) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let methods = data.variants.iter().map(|variant| {
         let variant_name = &variant.ident;
         let method_suffix = variant_name.to_string().to_case(Case::Snake);
         let as_fn = Ident::new(&format!("as_{method_suffix}"), variant_name.span());
         let as_fn_mut = Ident::new(&format!("as_{method_suffix}_mut"), variant_name.span());
         let into_fn = Ident::new(&format!("into_{method_suffix}"), variant_name.span());
+        let is_fn = Ident::new(&format!("is_{method_suffix}"), variant_name.span());
 
         match &variant.fields {
             Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
@@ -130,12 +211,15 @@ fn variants_access_impl(ident: &Ident, data: &mut DataEnum) -> TokenStream {
                     }
                 }
             }
-            _ => panic!("variants_access_impl supports only tuple-like enums "),
+            Fields::Unit => quote! {
+                pub fn #is_fn(&self) -> bool { matches!(self, #ident::#variant_name) }
+            },
+            _ => quote! {},
         }
     });
 
     quote! {
-        impl #ident {
+        impl #impl_generics #ident #ty_generics #where_clause {
             #(#methods)*
         }
     }
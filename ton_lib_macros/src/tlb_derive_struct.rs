@@ -3,25 +3,48 @@ use crate::TLBHeaderAttrs;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use syn::punctuated::Punctuated;
-use syn::{DataStruct, Fields, Index};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+use syn::{parse_quote, DataStruct, Expr, Fields, GenericArgument, Ident, Index, Member, PathArguments, Type};
 
-struct FieldInfo {
-    ident: Option<syn::Ident>,
-    position: usize,
-    attrs: TLBFieldAttrs,
+pub(crate) struct FieldInfo {
+    pub(crate) ident: Option<Ident>,
+    pub(crate) position: usize,
+    pub(crate) ty: Type,
+    pub(crate) attrs: TLBFieldAttrs,
+}
+
+impl FieldInfo {
+    // local variable name used for reading (and binding in enum variants)
+    pub(crate) fn local_ident(&self) -> Ident {
+        match &self.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("field_{}", self.position),
+        }
+    }
 }
 
 pub(crate) fn tlb_derive_struct(
+    crate_path: &TokenStream,
     header_attrs: &TLBHeaderAttrs,
     data: &mut DataStruct,
-) -> (TokenStream, TokenStream, TokenStream) {
-    let fields = match &mut data.fields {
+) -> syn::Result<(TokenStream, TokenStream, TokenStream)> {
+    let fields_info = extract_fields_info(&mut data.fields);
+    if fields_info.is_empty() || fields_info[0].ident.is_some() {
+        derive_named_struct(crate_path, header_attrs, &fields_info)
+    } else {
+        Ok(derive_unnamed_struct(header_attrs, &fields_info))
+    }
+}
+
+pub(crate) fn extract_fields_info(fields: &mut Fields) -> Vec<FieldInfo> {
+    let fields = match fields {
         Fields::Named(fields) => &mut fields.named, // For struct { field1: T, field2: T }
         Fields::Unnamed(fields) => &mut fields.unnamed, // For tuple struct (T, T)
         Fields::Unit => &mut Punctuated::new(),     // For unit struct (`struct Unit;`)
     };
 
-    let fields_info = fields
+    fields
         .iter_mut()
         .enumerate()
         .map(|(position, field)| {
@@ -31,7 +54,14 @@ pub(crate) fn tlb_derive_struct(
                 Err(_err) => panic!("Attribute does not exist at position {position}"),
             };
 
-            let ty_token_stream = field.ty.to_token_stream();
+            // conditional field is Option<T>, adapter is applied to T
+            let ty_token_stream = match &field_attrs.cond {
+                Some(_) => match option_inner_type(&field.ty) {
+                    Some(inner) => inner.to_token_stream(),
+                    None => panic!("Field with 'if' attribute must be Option<T> at position {position}"),
+                },
+                None => field.ty.to_token_stream(),
+            };
             // bits_len=XXX is alias for ConstLen adapter
             if let Some(bits_len) = &field_attrs.bits_len {
                 let adapter_str = format!("ConstLen::<{ty_token_stream}>::new({bits_len})");
@@ -50,35 +80,156 @@ pub(crate) fn tlb_derive_struct(
             FieldInfo {
                 ident: ident.clone(),
                 position,
+                ty: field.ty.clone(),
                 attrs: field_attrs,
             }
         })
-        .collect::<Vec<_>>();
+        .collect::<Vec<_>>()
+}
 
-    if fields_info.is_empty() || fields[0].ident.is_some() {
-        derive_named_struct(header_attrs, &fields_info)
+/// Returns (read expression, write statement for `value: &T`)
+/// `prev_fields` are available as locals on reading, `field_access` resolves them on writing
+pub(crate) fn field_read_write(
+    crate_path: &TokenStream,
+    field: &FieldInfo,
+    prev_fields: &[Ident],
+    field_access: impl Fn(&Ident) -> Expr,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    if let Some(adapter) = &field.attrs.adapter {
+        // adapter may depend on previous fields, like `MyAdapter::new(self.flags)`
+        let adapter = syn::parse_str::<Expr>(adapter).map_err(|err| field_error(field, err))?;
+        let read_adapter =
+            resolve_self_fields(&adapter, &field.local_ident(), prev_fields, |ident| parse_quote!(#ident))?;
+        let write_adapter = resolve_self_fields(&adapter, &field.local_ident(), prev_fields, field_access)?;
+        Ok((quote!(#read_adapter.read(parser)?.into()), quote!(#write_adapter.write(builder, value)?;)))
+    } else if let Some(bits_len_from) = &field.attrs.bits_len_from {
+        let from_ident = format_ident!("{bits_len_from}");
+        let from_access = field_access(&from_ident);
+        Ok((quote!(parser.read_num(#from_ident as usize)?), quote!(builder.write_num(value, #from_access as usize)?;)))
     } else {
-        derive_unnamed_struct(header_attrs, &fields_info)
+        Ok((quote!(TLB::read(parser)?), quote!(#crate_path::traits::tlb::TLB::write(value, builder)?;)))
+    }
+}
+
+fn field_error(field: &FieldInfo, err: impl std::fmt::Display) -> syn::Error {
+    let msg = format!("Invalid tlb_derive attribute of field {}: {err}", field.local_ident());
+    match &field.ident {
+        Some(ident) => syn::Error::new(ident.span(), msg),
+        None => syn::Error::new(field.ty.span(), msg),
+    }
+}
+
+/// Replaces `self.<field>` in attribute expression with `field_access(field)`
+/// Only `prev_fields` (declared before `current`) can be used: they are already read when `current` is reading
+fn resolve_self_fields(
+    expr: &Expr,
+    current: &Ident,
+    prev_fields: &[Ident],
+    field_access: impl Fn(&Ident) -> Expr,
+) -> syn::Result<Expr> {
+    struct SelfFieldsResolver<'a, F> {
+        current: &'a Ident,
+        prev_fields: &'a [Ident],
+        field_access: F,
+        errors: Vec<syn::Error>,
+    }
+
+    impl<F: Fn(&Ident) -> Expr> VisitMut for SelfFieldsResolver<'_, F> {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            let self_field = match &*expr {
+                Expr::Field(field) => match (&*field.base, &field.member) {
+                    (Expr::Path(base), Member::Named(ident)) if base.path.is_ident("self") => Some(ident.clone()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match self_field {
+                Some(ident) if self.prev_fields.contains(&ident) => *expr = (self.field_access)(&ident),
+                Some(ident) => {
+                    let msg = format!(
+                        "self.{ident} is not available: {} can depend only on fields declared before it",
+                        self.current
+                    );
+                    self.errors.push(syn::Error::new(ident.span(), msg));
+                }
+                None if matches!(expr, Expr::Path(path) if path.path.is_ident("self")) => {
+                    self.errors.push(syn::Error::new_spanned(&*expr, "only self.<field> access is supported"));
+                }
+                None => syn::visit_mut::visit_expr_mut(self, expr),
+            }
+        }
+    }
+
+    let mut resolver = SelfFieldsResolver {
+        current,
+        prev_fields,
+        field_access,
+        errors: vec![],
+    };
+    let mut expr = expr.clone();
+    resolver.visit_expr_mut(&mut expr);
+    match resolver.errors.into_iter().reduce(|mut all, err| {
+        all.combine(err);
+        all
+    }) {
+        Some(err) => Err(err),
+        None => Ok(expr),
+    }
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else { return None };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
-fn derive_named_struct(header_attrs: &TLBHeaderAttrs, fields: &[FieldInfo]) -> (TokenStream, TokenStream, TokenStream) {
+fn derive_named_struct(
+    crate_path: &TokenStream,
+    header_attrs: &TLBHeaderAttrs,
+    fields: &[FieldInfo],
+) -> syn::Result<(TokenStream, TokenStream, TokenStream)> {
     let mut read_tokens = Vec::with_capacity(fields.len());
     let mut init_tokens = Vec::with_capacity(fields.len());
     let mut write_tokens = Vec::with_capacity(fields.len());
-    for field in fields {
-        let ident = field.ident.as_ref().unwrap();
-        if let Some(adapter) = &field.attrs.adapter {
-            let adapter_ident: TokenStream = syn::parse_str(adapter).unwrap();
-            read_tokens.push(quote!(let #ident = #adapter_ident.read(parser)?.into();));
-            init_tokens.push(quote!(#ident,));
-            write_tokens.push(quote!(#adapter_ident.write(builder, &self.#ident)?;));
+    let idents = fields.iter().map(|x| x.local_ident()).collect::<Vec<_>>();
+    for (pos, field) in fields.iter().enumerate() {
+        let (ident, prev_fields) = (&idents[pos], &idents[..pos]);
+        let (read_value, write_value) =
+            field_read_write(crate_path, field, prev_fields, |from| parse_quote!(self.#from))?;
+
+        init_tokens.push(quote!(#ident,));
+        let Some(cond) = &field.attrs.cond else {
+            read_tokens.push(quote!(let #ident = #read_value;));
+            write_tokens.push(quote!({
+                let value = &self.#ident;
+                #write_value
+            }));
             continue;
-        } else {
-            read_tokens.push(quote!(let #ident = TLB::read(parser)?;));
-            init_tokens.push(quote!(#ident,));
-            write_tokens.push(quote!(self.#ident.write(builder)?;));
-        }
+        };
+        // condition is written against self, but on reading previous fields are available as locals
+        let write_cond = cond.parse::<Expr>()?;
+        let read_cond = resolve_self_fields(&write_cond, ident, prev_fields, |from| parse_quote!(#from))?;
+        let err_msg = format!("{ident} is None, but condition '{}' is true", cond.value());
+        read_tokens.push(quote! {
+            let #ident = if #read_cond { Some(#read_value) } else { None };
+        });
+        write_tokens.push(quote! {
+            if #write_cond {
+                match &self.#ident {
+                    Some(value) => { #write_value }
+                    None => return Err(#crate_path::error::TLCoreError::TLBWrongData(#err_msg.to_string())),
+                }
+            }
+        });
     }
 
     if header_attrs.ensure_empty.unwrap_or(false) {
@@ -96,7 +247,7 @@ fn derive_named_struct(header_attrs: &TLBHeaderAttrs, fields: &[FieldInfo]) -> (
         #(#write_tokens)*
         Ok(())
     };
-    (read_impl_token, write_impl_token, quote::quote! {})
+    Ok((read_impl_token, write_impl_token, quote::quote! {}))
 }
 
 fn derive_unnamed_struct(
@@ -107,8 +258,11 @@ fn derive_unnamed_struct(
     let mut init_tokens = Vec::with_capacity(fields.len());
     let mut write_tokens = Vec::with_capacity(fields.len());
     for field in fields {
+        if field.attrs.cond.is_some() || field.attrs.bits_len_from.is_some() {
+            panic!("'if' and 'bits_len_from' attributes are supported only for named fields");
+        }
         let position = Index::from(field.position);
-        let read_ident = format_ident!("field_{}", field.position);
+        let read_ident = field.local_ident();
         if let Some(adapter) = &field.attrs.adapter {
             let adapter_ident: TokenStream = syn::parse_str(adapter).unwrap();
             read_tokens.push(quote!(let #read_ident = #adapter_ident.read(parser)?;));
@@ -139,3 +293,24 @@ fn derive_unnamed_struct(
     };
     (read_impl_token, write_impl_token, quote::quote! {})
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_self_fields() {
+        let (current, prev_fields) = (format_ident!("ext"), [format_ident!("flags"), format_ident!("len")]);
+        let resolve = |expr: &str| {
+            let expr = syn::parse_str::<Expr>(expr).unwrap();
+            resolve_self_fields(&expr, &current, &prev_fields, |from| parse_quote!(#from))
+        };
+        let resolved = resolve("self.flags & 1 != 0 && self.len.count_ones() > 2").unwrap();
+        assert_eq!(resolved, parse_quote!(flags & 1 != 0 && len.count_ones() > 2));
+
+        let err = resolve("self.flags & 1 != 0 && self.next > 0").unwrap_err();
+        assert!(err.to_string().contains("self.next is not available"), "{err}");
+        let err = resolve("self.is_ext()").unwrap_err();
+        assert!(err.to_string().contains("only self.<field> access is supported"), "{err}");
+    }
+}
@@ -0,0 +1,515 @@
+use crate::tlb_derive::core_crate_path;
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::{format_ident, quote};
+use syn::LitStr;
+use ton_lib_tlb_schema::{parse_schema, Constructor, TypeExpr};
+
+// Subset of TL-B types supported by codegen. Conditional fields are handled by generate_constructor
+enum TLBFieldType {
+    Nat {
+        bits_len: usize,
+        signed: bool,
+    }, // (## n), (#<= n), (#< n), #, uintN, intN
+    Bool,
+    Bits256,
+    Cell, // only as ^Cell
+    Named(String),
+    Ref(Box<TLBFieldType>),
+    Maybe(Box<TLBFieldType>),
+    Either(Box<TLBFieldType>, Box<TLBFieldType>),
+    HashmapE {
+        key_bits_len: usize,
+        value: Box<TLBFieldType>,
+    },
+}
+
+struct CratePaths {
+    core: TokenStream,
+    lib: Option<TokenStream>, // tlb_adapters live in ton_lib
+}
+
+pub(crate) fn tlb_schema_impl(input: proc_macro::TokenStream) -> TokenStream {
+    let source = match syn::parse::<LitStr>(input) {
+        Ok(source) => source,
+        Err(err) => return err.into_compile_error(),
+    };
+    match generate_schema(&source.value()) {
+        Ok(tokens) => tokens,
+        Err(err) => syn::Error::new(source.span(), err).into_compile_error(),
+    }
+}
+
+pub(crate) fn tlb_schema_file_impl(input: proc_macro::TokenStream) -> TokenStream {
+    let file_path = match syn::parse::<LitStr>(input) {
+        Ok(file_path) => file_path,
+        Err(err) => return err.into_compile_error(),
+    };
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = std::path::Path::new(&manifest_dir).join(file_path.value());
+    let source = match std::fs::read_to_string(&full_path) {
+        Ok(source) => source,
+        Err(err) => {
+            let err_msg = format!("Fail to read {}: {err}", full_path.display());
+            return syn::Error::new(file_path.span(), err_msg).into_compile_error();
+        }
+    };
+    let tokens = match generate_schema(&source) {
+        Ok(tokens) => tokens,
+        Err(err) => return syn::Error::new(file_path.span(), err).into_compile_error(),
+    };
+    // include_str! makes cargo rebuild the crate when schema file is changed
+    let full_path_str = full_path.to_string_lossy().to_string();
+    quote! {
+        const _: &str = include_str!(#full_path_str);
+        #tokens
+    }
+}
+
+fn generate_schema(source: &str) -> Result<TokenStream, String> {
+    let paths = CratePaths {
+        core: core_crate_path(),
+        lib: lib_crate_path(),
+    };
+    // types in order of first appearance, constructors of each type are grouped together
+    let mut types: Vec<(String, Vec<Constructor>)> = vec![];
+    for (type_name, constructor) in parse_schema(source).map_err(|err| err.to_string())? {
+        if !constructor.params.is_empty() {
+            return Err(format!("implicit fields are not supported in '{}'", constructor.source));
+        }
+        if !constructor.result_args.is_empty() {
+            return Err(format!("parametrized type {type_name} is not supported in '{}'", constructor.source));
+        }
+        match types.iter_mut().find(|(name, _)| name == &type_name) {
+            Some((_, constructors)) => constructors.push(constructor),
+            None => types.push((type_name, vec![constructor])),
+        }
+    }
+    let mut tokens = vec![];
+    for (type_name, constructors) in &types {
+        tokens.push(generate_type(&paths, type_name, constructors)?);
+    }
+    Ok(quote!(#(#tokens)*))
+}
+
+fn lib_crate_path() -> Option<TokenStream> {
+    match crate_name("ton_lib").ok()? {
+        FoundCrate::Itself => Some(quote!(crate)),
+        FoundCrate::Name(name) => {
+            let ident = format_ident!("{name}");
+            Some(quote!(#ident))
+        }
+    }
+}
+
+// Type with single constructor is generated as struct,
+// otherwise every constructor is generated as struct, and type itself - as enum over them
+fn generate_type(paths: &CratePaths, type_name: &str, constructors: &[Constructor]) -> Result<TokenStream, String> {
+    let type_ident = format_ident!("{type_name}");
+    if let [constructor] = constructors {
+        return generate_constructor(paths, &type_ident, constructor);
+    }
+
+    let core = &paths.core;
+    let mut structs = vec![];
+    let mut variants = vec![];
+    for constructor in constructors {
+        let ident = format_ident!("{}", constructor.name.to_case(Case::Pascal));
+        if ident == type_ident {
+            return Err(format!("constructor {} has the same name as its type", constructor.name));
+        }
+        structs.push(generate_constructor(paths, &ident, constructor)?);
+        variants.push(quote!(#ident(#ident),));
+    }
+    Ok(quote! {
+        #(#structs)*
+
+        #[derive(Debug, Clone, PartialEq, #core::TLBDerive)]
+        pub enum #type_ident {
+            #(#variants)*
+        }
+    })
+}
+
+fn generate_constructor(paths: &CratePaths, ident: &Ident, constructor: &Constructor) -> Result<TokenStream, String> {
+    let core = &paths.core;
+    let mut field_defs = vec![];
+    let mut field_reads = vec![];
+    let mut field_writes = vec![];
+    let mut field_idents = vec![];
+    // named fields which can be used as condition: name -> is_bool
+    let mut cond_fields = vec![];
+    for (position, field) in constructor.fields.iter().enumerate() {
+        let field_ident = match &field.name {
+            Some(name) => field_ident(name),
+            None => format_ident!("field_{position}"),
+        };
+        let with_source = |err: String| format!("{err} in '{}'", constructor.source);
+        let (cond, expr) = match &field.expr {
+            TypeExpr::Cond { cond, bit, inner } => {
+                let cond = FieldCond::new(&cond_fields, cond, *bit).map_err(with_source)?;
+                (Some(cond), inner.as_ref())
+            }
+            expr => (None, expr),
+        };
+        let field_type = field_type(expr).map_err(with_source)?;
+        let read_expr = read_expr(paths, &field_type);
+        let rust_type = rust_type(paths, &field_type)?;
+        match cond {
+            Some(cond) => {
+                let cond_read = cond.expr(quote!());
+                let cond_write = cond.expr(quote!(self.));
+                let inner_write = write_stmt(paths, &field_type, quote!(value));
+                let err_msg = format!("{field_ident} must be set if and only if condition is true");
+                field_defs.push(quote!(pub #field_ident: Option<#rust_type>,));
+                field_reads.push(quote!(let #field_ident = match #cond_read {
+                    true => Some(#read_expr),
+                    false => None,
+                };));
+                field_writes.push(quote!(match (#cond_write, &self.#field_ident) {
+                    (true, Some(value)) => { #inner_write }
+                    (false, None) => {}
+                    _ => return Err(#core::error::TLCoreError::TLBWrongData(#err_msg.to_string())),
+                }));
+            }
+            None => {
+                field_defs.push(quote!(pub #field_ident: #rust_type,));
+                field_reads.push(quote!(let #field_ident = #read_expr;));
+                field_writes.push(write_stmt(paths, &field_type, quote!(&self.#field_ident)));
+            }
+        }
+        if let (Some(name), TLBFieldType::Bool | TLBFieldType::Nat { .. }) = (&field.name, &field_type) {
+            cond_fields.push((name.clone(), matches!(field_type, TLBFieldType::Bool)));
+        }
+        field_idents.push(field_ident);
+    }
+
+    let doc = format!(" `{}`", constructor.source);
+    let prefix_val = constructor.tag.value as usize;
+    let prefix_bits_len = constructor.tag.bits_len;
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct #ident {
+            #(#field_defs)*
+        }
+
+        impl #core::traits::tlb::TLB for #ident {
+            const PREFIX: #core::traits::tlb::TLBPrefix = #core::traits::tlb::TLBPrefix::new(#prefix_val, #prefix_bits_len);
+
+            fn read_definition(parser: &mut #core::cell::CellParser) -> Result<Self, #core::error::TLCoreError> {
+                #(#field_reads)*
+                Ok(Self {
+                    #(#field_idents,)*
+                })
+            }
+
+            fn write_definition(&self, builder: &mut #core::cell::CellBuilder) -> Result<(), #core::error::TLCoreError> {
+                #(#field_writes)*
+                Ok(())
+            }
+        }
+    })
+}
+
+// Condition of `cond?X` or `cond.bit?X` field, `cond` is one of previous Bool or nat fields
+struct FieldCond {
+    ident: Ident,
+    is_bool: bool,
+    bit: Option<u32>,
+}
+
+impl FieldCond {
+    fn new(cond_fields: &[(String, bool)], cond: &TypeExpr, bit: Option<u32>) -> Result<Self, String> {
+        let name = match cond {
+            TypeExpr::Apply { name, args } if args.is_empty() => name,
+            cond => return Err(format!("condition must be a field name, got {cond}")),
+        };
+        let Some((_, is_bool)) = cond_fields.iter().find(|(field_name, _)| field_name == name) else {
+            return Err(format!("condition {name} must be previous Bool or nat field"));
+        };
+        if *is_bool && bit.is_some() {
+            return Err(format!("bit of Bool condition {name} is not supported"));
+        }
+        Ok(Self {
+            ident: field_ident(name),
+            is_bool: *is_bool,
+            bit,
+        })
+    }
+
+    // `prefix` is empty for local variables in read_definition and `self.` in write_definition
+    fn expr(&self, prefix: TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        match (self.is_bool, self.bit) {
+            (true, _) => quote!(#prefix #ident),
+            (false, None) => quote!(#prefix #ident != 0),
+            (false, Some(bit)) => quote!((#prefix #ident >> #bit) & 1 != 0),
+        }
+    }
+}
+
+fn field_type(expr: &TypeExpr) -> Result<TLBFieldType, String> {
+    let nat = |bits_len| TLBFieldType::Nat {
+        bits_len,
+        signed: false,
+    };
+    let (name, args) = match expr {
+        TypeExpr::Apply { name, args } => (name.as_str(), args.as_slice()),
+        TypeExpr::Ref(inner) => return Ok(TLBFieldType::Ref(Box::new(field_type(inner)?))),
+        TypeExpr::Cond { .. } => return Err("nested conditional fields are not supported".to_string()),
+    };
+    let field_type = match (name, args) {
+        ("#", []) => nat(32),
+        ("##", [bits_len]) => nat(number(bits_len)?),
+        // #<= n: number of bits to store n, #< n: number of bits to store n - 1
+        ("#<=", [max_value]) => nat(bits_to_store(number(max_value)?)),
+        ("#<", [upper_bound]) => nat(bits_to_store(number(upper_bound)?.saturating_sub(1))),
+        ("Maybe", [inner]) => TLBFieldType::Maybe(Box::new(field_type(inner)?)),
+        ("Either", [left, right]) => TLBFieldType::Either(Box::new(field_type(left)?), Box::new(field_type(right)?)),
+        ("HashmapE", [key_bits_len, value]) => TLBFieldType::HashmapE {
+            key_bits_len: number(key_bits_len)?,
+            value: Box::new(field_type(value)?),
+        },
+        ("bits", [bits_len]) => match number(bits_len)? {
+            256 => TLBFieldType::Bits256,
+            bits_len => return Err(format!("bits {bits_len} is not supported, only bits256 is")),
+        },
+        ("Bool" | "Bit", []) => TLBFieldType::Bool,
+        ("Cell", []) => TLBFieldType::Cell,
+        ("bits256", []) => TLBFieldType::Bits256,
+        (name, []) if name.starts_with("uint") || name.starts_with("int") => {
+            let signed = name.starts_with("int");
+            let bits_len = name.trim_start_matches('u').trim_start_matches("int");
+            TLBFieldType::Nat {
+                bits_len: bits_len.parse().map_err(|_| format!("invalid integer type {name}"))?,
+                signed,
+            }
+        }
+        (name, []) if name.starts_with(|c: char| c.is_ascii_uppercase()) => TLBFieldType::Named(name.to_string()),
+        (name, []) => return Err(format!("unknown type {name}")),
+        (name, _) => return Err(format!("type application {name} is not supported")),
+    };
+    Ok(field_type)
+}
+
+fn number(expr: &TypeExpr) -> Result<usize, String> {
+    match expr {
+        TypeExpr::Apply { name, args } if args.is_empty() => {
+            name.parse().map_err(|_| format!("number is expected, got {name}"))
+        }
+        expr => Err(format!("number is expected, got {expr}")),
+    }
+}
+
+fn bits_to_store(value: usize) -> usize { (usize::BITS - value.leading_zeros()) as usize }
+
+fn field_ident(name: &str) -> Ident {
+    match syn::parse_str::<Ident>(name) {
+        Ok(ident) => ident,
+        Err(_) => Ident::new_raw(name, Span::call_site()), // keywords like `type`
+    }
+}
+
+fn nat_type(bits_len: usize, signed: bool) -> Result<TokenStream, String> {
+    let type_bits_len = match bits_len {
+        0..=8 => 8,
+        9..=16 => 16,
+        17..=32 => 32,
+        33..=64 => 64,
+        65..=128 => 128,
+        _ => return Err(format!("integer with {bits_len} bits is not supported, use bits256 for hashes")),
+    };
+    let type_name = match signed {
+        true => format_ident!("i{type_bits_len}"),
+        false => format_ident!("u{type_bits_len}"),
+    };
+    Ok(quote!(#type_name))
+}
+
+fn rust_type(paths: &CratePaths, field_type: &TLBFieldType) -> Result<TokenStream, String> {
+    let core = &paths.core;
+    let tokens = match field_type {
+        TLBFieldType::Nat { bits_len, signed } => nat_type(*bits_len, *signed)?,
+        TLBFieldType::Bool => quote!(bool),
+        TLBFieldType::Bits256 => quote!(#core::cell::TonHash),
+        TLBFieldType::Cell => return Err("Cell is supported only as reference: ^Cell".to_string()),
+        TLBFieldType::Named(name) => {
+            let ident = format_ident!("{name}");
+            quote!(#ident)
+        }
+        TLBFieldType::Ref(inner) => match inner.as_ref() {
+            TLBFieldType::Cell => quote!(#core::cell::TonCellRef),
+            inner => rust_type(paths, inner)?,
+        },
+        TLBFieldType::Maybe(inner) => {
+            let inner = rust_type(paths, inner)?;
+            quote!(Option<#inner>)
+        }
+        TLBFieldType::Either(left, right) => {
+            let left = rust_type(paths, left)?;
+            let right = rust_type(paths, right)?;
+            quote!(#core::types::tlb_core::TLBEither<#left, #right>)
+        }
+        TLBFieldType::HashmapE { key_bits_len, value } => {
+            let (key_type, _) = dict_key(paths, *key_bits_len)?;
+            let (value_type, _) = dict_value(paths, value)?;
+            quote!(std::collections::HashMap<#key_type, #value_type>)
+        }
+    };
+    Ok(tokens)
+}
+
+// returns (key_type, key_adapter)
+fn dict_key(paths: &CratePaths, key_bits_len: usize) -> Result<(TokenStream, TokenStream), String> {
+    let (core, lib) = (&paths.core, lib_path(paths)?);
+    match key_bits_len {
+        256 => Ok((quote!(#core::cell::TonHash), quote!(#lib::tlb_adapters::DictKeyAdapterTonHash))),
+        _ => Ok((nat_type(key_bits_len, false)?, quote!(#lib::tlb_adapters::DictKeyAdapterInto))),
+    }
+}
+
+// returns (value_type, value_adapter)
+fn dict_value(paths: &CratePaths, value: &TLBFieldType) -> Result<(TokenStream, TokenStream), String> {
+    let lib = lib_path(paths)?;
+    match value {
+        TLBFieldType::Nat { bits_len, signed } => {
+            Ok((nat_type(*bits_len, *signed)?, quote!(#lib::tlb_adapters::DictValAdapterNum<#bits_len>)))
+        }
+        TLBFieldType::Ref(inner) if !matches!(inner.as_ref(), TLBFieldType::Cell) => {
+            Ok((rust_type(paths, inner)?, quote!(#lib::tlb_adapters::DictValAdapterTLBRef)))
+        }
+        value => Ok((rust_type(paths, value)?, quote!(#lib::tlb_adapters::DictValAdapterTLB))),
+    }
+}
+
+fn lib_path(paths: &CratePaths) -> Result<&TokenStream, String> {
+    paths.lib.as_ref().ok_or("HashmapE requires ton_lib crate".to_string())
+}
+
+// Expression reading value from `parser`. Types are already validated by rust_type
+fn read_expr(paths: &CratePaths, field_type: &TLBFieldType) -> TokenStream {
+    let core = &paths.core;
+    match field_type {
+        TLBFieldType::Nat { bits_len, .. } => quote!(parser.read_num(#bits_len)?),
+        TLBFieldType::Bool => quote!(parser.read_bit()?),
+        TLBFieldType::Bits256 | TLBFieldType::Named(_) | TLBFieldType::Cell => {
+            let rust_type = rust_type(paths, field_type).unwrap();
+            quote!(<#rust_type as #core::traits::tlb::TLB>::read(parser)?)
+        }
+        TLBFieldType::Ref(inner) => match inner.as_ref() {
+            TLBFieldType::Cell => quote!(parser.read_next_ref()?.clone()),
+            inner => {
+                let inner_read = read_expr(paths, inner);
+                quote!({
+                    let cell_ref = parser.read_next_ref()?.clone();
+                    let parser = &mut cell_ref.parser();
+                    #inner_read
+                })
+            }
+        },
+        TLBFieldType::Maybe(inner) => {
+            let inner_read = read_expr(paths, inner);
+            quote!(match parser.read_bit()? {
+                true => Some(#inner_read),
+                false => None,
+            })
+        }
+        TLBFieldType::Either(left, right) => {
+            let left_read = read_expr(paths, left);
+            let right_read = read_expr(paths, right);
+            quote!(match parser.read_bit()? {
+                false => #core::types::tlb_core::TLBEither::Left(#left_read),
+                true => #core::types::tlb_core::TLBEither::Right(#right_read),
+            })
+        }
+        TLBFieldType::HashmapE { key_bits_len, value } => {
+            let adapter = dict_adapter(paths, *key_bits_len, value);
+            quote!(#adapter.read(parser)?)
+        }
+    }
+}
+
+// Statement writing `value` (expression of reference type) to `builder`
+fn write_stmt(paths: &CratePaths, field_type: &TLBFieldType, value: TokenStream) -> TokenStream {
+    let core = &paths.core;
+    match field_type {
+        TLBFieldType::Nat { bits_len, .. } => quote!(builder.write_num(#value, #bits_len)?;),
+        TLBFieldType::Bool => quote!(builder.write_bit(*#value)?;),
+        TLBFieldType::Bits256 | TLBFieldType::Named(_) | TLBFieldType::Cell => {
+            quote!(#core::traits::tlb::TLB::write(#value, builder)?;)
+        }
+        TLBFieldType::Ref(inner) => match inner.as_ref() {
+            TLBFieldType::Cell => quote!(builder.write_ref((#value).clone())?;),
+            inner => {
+                let inner_write = write_stmt(paths, inner, quote!(#value));
+                quote!({
+                    let mut ref_builder = #core::cell::TonCell::builder();
+                    {
+                        let builder = &mut ref_builder;
+                        #inner_write
+                    }
+                    builder.write_ref(ref_builder.build()?.into_ref())?;
+                })
+            }
+        },
+        TLBFieldType::Maybe(inner) => {
+            let inner_write = write_stmt(paths, inner, quote!(value));
+            quote!({
+                builder.write_bit((#value).is_some())?;
+                if let Some(value) = #value {
+                    #inner_write
+                }
+            })
+        }
+        TLBFieldType::Either(left, right) => {
+            let left_write = write_stmt(paths, left, quote!(value));
+            let right_write = write_stmt(paths, right, quote!(value));
+            quote!(match #value {
+                #core::types::tlb_core::TLBEither::Left(value) => {
+                    builder.write_bit(false)?;
+                    #left_write
+                }
+                #core::types::tlb_core::TLBEither::Right(value) => {
+                    builder.write_bit(true)?;
+                    #right_write
+                }
+            })
+        }
+        TLBFieldType::HashmapE {
+            key_bits_len,
+            value: dict_value,
+        } => {
+            let adapter = dict_adapter(paths, *key_bits_len, dict_value);
+            quote!(#adapter.write(builder, #value)?;)
+        }
+    }
+}
+
+fn dict_adapter(paths: &CratePaths, key_bits_len: usize, value: &TLBFieldType) -> TokenStream {
+    let lib = lib_path(paths).unwrap();
+    let (_, key_adapter) = dict_key(paths, key_bits_len).unwrap();
+    let (_, value_adapter) = dict_value(paths, value).unwrap();
+    let key_bits_len = key_bits_len as u32;
+    quote!(#lib::tlb_adapters::TLBHashMapE::<#key_adapter, #value_adapter, _, _>::new(#key_bits_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_schema_unsupported() {
+        let err = generate_schema("a$0 {n:#} x:(## n) = A;").unwrap_err();
+        assert!(err.contains("implicit fields are not supported"), "{err}");
+        let err = generate_schema("a$0 x:flags.0?uint8 flags:(## 8) = A;").unwrap_err();
+        assert!(err.contains("condition flags must be previous Bool or nat field"), "{err}");
+        let err = generate_schema("a$0 flag:Bool x:flag.0?uint8 = A;").unwrap_err();
+        assert!(err.contains("bit of Bool condition flag is not supported"), "{err}");
+        let err = generate_schema("a$0 x:uint8 = A 1;").unwrap_err();
+        assert!(err.contains("parametrized type A is not supported"), "{err}");
+        let err = generate_schema("a$0 x:uint8 = A;\nb$1 x:(## 8) = B").unwrap_err();
+        assert!(err.starts_with("line 2:"), "{err}");
+    }
+}
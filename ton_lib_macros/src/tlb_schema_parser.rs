@@ -0,0 +1,300 @@
+// Parser for the subset of TL-B language supported by tlb_schema! macro
+// https://docs.ton.org/v3/documentation/data-formats/tlb/tl-b-language
+
+pub(crate) struct TLBSchemaType {
+    pub(crate) name: String,
+    pub(crate) constructors: Vec<TLBConstructor>,
+}
+
+pub(crate) struct TLBConstructor {
+    pub(crate) name: String,
+    pub(crate) tag: TLBTag,
+    pub(crate) fields: Vec<TLBField>,
+    pub(crate) source: String,
+}
+
+#[derive(Default)]
+pub(crate) struct TLBTag {
+    pub(crate) value: usize,
+    pub(crate) bits_len: usize,
+}
+
+pub(crate) struct TLBField {
+    pub(crate) name: Option<String>,
+    pub(crate) field_type: TLBFieldType,
+}
+
+pub(crate) enum TLBFieldType {
+    Nat {
+        bits_len: usize,
+        signed: bool,
+    }, // (## n), (#<= n), (#< n), #, uintN, intN
+    Bool,
+    Bits256,
+    Cell, // only as ^Cell
+    Named(String),
+    Ref(Box<TLBFieldType>),
+    Maybe(Box<TLBFieldType>),
+    Either(Box<TLBFieldType>, Box<TLBFieldType>),
+    HashmapE {
+        key_bits_len: usize,
+        value: Box<TLBFieldType>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Tag(String), // #hex or $bin, placed right after constructor name
+    Sym(&'static str),
+}
+
+/// Returns types in order of first appearance, constructors of each type are grouped together
+pub(crate) fn parse_tlb_schema(source: &str) -> Result<Vec<TLBSchemaType>, String> {
+    let source = strip_comments(source)?;
+    let mut types: Vec<TLBSchemaType> = vec![];
+    let mut declarations = source.split(';').map(str::trim).collect::<Vec<_>>();
+    match declarations.pop() {
+        Some(rest) if !rest.is_empty() => return Err(format!("missing ';' after '{rest}'")),
+        _ => {}
+    }
+    for declaration in declarations {
+        if declaration.is_empty() {
+            continue;
+        }
+        let (type_name, constructor) = parse_declaration(declaration)
+            .map_err(|err| format!("{err} in '{}'", declaration.split_whitespace().collect::<Vec<_>>().join(" ")))?;
+        match types.iter_mut().find(|x| x.name == type_name) {
+            Some(schema_type) => schema_type.constructors.push(constructor),
+            None => types.push(TLBSchemaType {
+                name: type_name,
+                constructors: vec![constructor],
+            }),
+        }
+    }
+    Ok(types)
+}
+
+fn strip_comments(source: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.find('\n').map(|pos| &comment[pos..]).unwrap_or("");
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let end = comment.find("*/").ok_or("unterminated comment")?;
+            rest = &comment[end + 2..];
+            result.push(' ');
+        } else {
+            let char = rest.chars().next().unwrap();
+            result.push(char);
+            rest = &rest[char.len_utf8()..];
+        }
+    }
+    Ok(result)
+}
+
+fn tokenize(declaration: &str) -> Result<Vec<Token>, String> {
+    let chars = declaration.chars().collect::<Vec<_>>();
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut tokens = vec![];
+    let mut pos = 0;
+    while pos < chars.len() {
+        let char = chars[pos];
+        if char.is_whitespace() {
+            pos += 1;
+        } else if is_ident_char(char) {
+            let start = pos;
+            while pos < chars.len() && is_ident_char(chars[pos]) {
+                pos += 1;
+            }
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+            if pos < chars.len() && (chars[pos] == '#' || chars[pos] == '$') {
+                let start = pos;
+                pos += 1;
+                while pos < chars.len() && is_ident_char(chars[pos]) {
+                    pos += 1;
+                }
+                tokens.push(Token::Tag(chars[start..pos].iter().collect()));
+            }
+        } else {
+            let rest = chars[pos..].iter().take(3).collect::<String>();
+            let sym = ["##", "#<=", "#<", "#", "^", "(", ")", ":", "=", "{", "}", "[", "]", "~"]
+                .into_iter()
+                .find(|sym| rest.starts_with(sym))
+                .ok_or(format!("unexpected symbol '{char}'"))?;
+            tokens.push(Token::Sym(sym));
+            pos += sym.len();
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_declaration(declaration: &str) -> Result<(String, TLBConstructor), String> {
+    let tokens = tokenize(declaration)?;
+    let mut tokens = tokens.iter().peekable();
+    let name = match tokens.next() {
+        Some(Token::Ident(name)) => name.clone(),
+        _ => return Err("constructor name is expected".to_string()),
+    };
+    let tag = match tokens.next_if(|x| matches!(x, Token::Tag(_))) {
+        Some(Token::Tag(tag)) => parse_tag(tag)?,
+        _ => TLBTag::default(),
+    };
+
+    let mut fields = vec![];
+    loop {
+        match tokens.peek() {
+            Some(Token::Sym("=")) => {
+                tokens.next();
+                break;
+            }
+            Some(Token::Sym("{")) => return Err("implicit fields are not supported".to_string()),
+            Some(Token::Sym("[")) => return Err("anonymous constructors are not supported".to_string()),
+            None => return Err("'=' is expected".to_string()),
+            _ => {}
+        }
+        let mut field_name = None;
+        if let Some(Token::Ident(ident)) = tokens.peek() {
+            let mut lookahead = tokens.clone();
+            lookahead.next();
+            if lookahead.next() == Some(&Token::Sym(":")) {
+                field_name = Some(ident.clone()).filter(|x| x != "_");
+                tokens = lookahead;
+            }
+        }
+        let field_type = parse_type(&mut tokens)?;
+        fields.push(TLBField {
+            name: field_name,
+            field_type,
+        });
+    }
+
+    let type_name = match tokens.next() {
+        Some(Token::Ident(type_name)) if type_name.starts_with(|c: char| c.is_ascii_uppercase()) => type_name.clone(),
+        _ => return Err("type name is expected after '='".to_string()),
+    };
+    if tokens.next().is_some() {
+        return Err(format!("parametrized type {type_name} is not supported"));
+    }
+    let constructor = TLBConstructor {
+        name,
+        tag,
+        fields,
+        source: format!("{};", declaration.split_whitespace().collect::<Vec<_>>().join(" ")),
+    };
+    Ok((type_name, constructor))
+}
+
+fn parse_tag(tag: &str) -> Result<TLBTag, String> {
+    let (radix, digits) = match tag.split_at(1) {
+        ("#", digits) => (16, digits),
+        (_, digits) => (2, digits),
+    };
+    let (digits, completion_tag) = match digits.strip_suffix('_') {
+        Some(digits) => (digits, true),
+        None => (digits, false),
+    };
+    let bits_per_digit = if radix == 16 { 4 } else { 1 };
+    let mut bits_len = digits.len() * bits_per_digit;
+    if bits_len > usize::BITS as usize {
+        return Err(format!("tag {tag} is too long"));
+    }
+    let mut value = match digits.is_empty() {
+        true => 0,
+        false => usize::from_str_radix(digits, radix).map_err(|_| format!("invalid tag {tag}"))?,
+    };
+    if completion_tag && bits_len > 0 {
+        // drop trailing zeros and the completion bit itself
+        let trailing_zeros = (value.trailing_zeros() as usize).min(bits_len);
+        if trailing_zeros == bits_len {
+            return Err(format!("invalid tag {tag}: completion bit is not found"));
+        }
+        bits_len -= trailing_zeros + 1;
+        value >>= trailing_zeros + 1;
+    }
+    Ok(TLBTag { value, bits_len })
+}
+
+fn parse_type<'a, I: Iterator<Item = &'a Token>>(tokens: &mut std::iter::Peekable<I>) -> Result<TLBFieldType, String> {
+    match tokens.next() {
+        Some(Token::Sym("^")) => Ok(TLBFieldType::Ref(Box::new(parse_type(tokens)?))),
+        Some(Token::Sym("#")) => Ok(TLBFieldType::Nat {
+            bits_len: 32,
+            signed: false,
+        }),
+        Some(Token::Sym("(")) => {
+            let field_type = parse_type_application(tokens)?;
+            match tokens.next() {
+                Some(Token::Sym(")")) => Ok(field_type),
+                _ => Err("')' is expected".to_string()),
+            }
+        }
+        Some(Token::Ident(name)) => parse_simple_type(name),
+        Some(token) => Err(format!("unexpected token {token:?}")),
+        None => Err("type is expected".to_string()),
+    }
+}
+
+fn parse_type_application<'a, I: Iterator<Item = &'a Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+) -> Result<TLBFieldType, String> {
+    let nat = |bits_len| TLBFieldType::Nat {
+        bits_len,
+        signed: false,
+    };
+    let field_type = match tokens.next() {
+        Some(Token::Sym("##")) => nat(parse_number(tokens)?),
+        // #<= n: number of bits to store n, #< n: number of bits to store n - 1
+        Some(Token::Sym("#<=")) => nat(bits_to_store(parse_number(tokens)?)),
+        Some(Token::Sym("#<")) => nat(bits_to_store(parse_number(tokens)?.saturating_sub(1))),
+        Some(Token::Ident(name)) if name == "Maybe" => TLBFieldType::Maybe(Box::new(parse_type(tokens)?)),
+        Some(Token::Ident(name)) if name == "Either" => {
+            TLBFieldType::Either(Box::new(parse_type(tokens)?), Box::new(parse_type(tokens)?))
+        }
+        Some(Token::Ident(name)) if name == "HashmapE" => TLBFieldType::HashmapE {
+            key_bits_len: parse_number(tokens)?,
+            value: Box::new(parse_type(tokens)?),
+        },
+        Some(Token::Ident(name)) if name == "bits" => match parse_number(tokens)? {
+            256 => TLBFieldType::Bits256,
+            bits_len => return Err(format!("bits {bits_len} is not supported, only bits256 is")),
+        },
+        Some(Token::Ident(name)) => match tokens.peek() {
+            Some(Token::Sym(")")) => parse_simple_type(name)?,
+            _ => return Err(format!("type application {name} is not supported")),
+        },
+        Some(Token::Sym("^")) => TLBFieldType::Ref(Box::new(parse_type(tokens)?)),
+        Some(token) => return Err(format!("unexpected token {token:?}")),
+        None => return Err("type is expected".to_string()),
+    };
+    Ok(field_type)
+}
+
+fn parse_simple_type(name: &str) -> Result<TLBFieldType, String> {
+    let field_type = match name {
+        "Bool" | "Bit" => TLBFieldType::Bool,
+        "Cell" => TLBFieldType::Cell,
+        "bits256" => TLBFieldType::Bits256,
+        _ if name.starts_with("uint") || name.starts_with("int") => {
+            let signed = name.starts_with("int");
+            let bits_len = name.trim_start_matches('u').trim_start_matches("int");
+            TLBFieldType::Nat {
+                bits_len: bits_len.parse().map_err(|_| format!("invalid integer type {name}"))?,
+                signed,
+            }
+        }
+        _ if name.starts_with(|c: char| c.is_ascii_uppercase()) => TLBFieldType::Named(name.to_string()),
+        _ => return Err(format!("unknown type {name}")),
+    };
+    Ok(field_type)
+}
+
+fn parse_number<'a, I: Iterator<Item = &'a Token>>(tokens: &mut I) -> Result<usize, String> {
+    match tokens.next() {
+        Some(Token::Ident(number)) => number.parse().map_err(|_| format!("number is expected, got {number}")),
+        _ => Err("number is expected".to_string()),
+    }
+}
+
+fn bits_to_store(value: usize) -> usize { (usize::BITS - value.leading_zeros()) as usize }
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use ton_lib::block_tlb::{SimpleLib, StateInit, StoragePrices, TickTock};
+use ton_lib_core::cell::{TonCell, TonHash};
+use ton_lib_core::traits::tlb::TLB;
+use ton_lib_core::types::tlb_core::TLBEither;
+
+ton_lib_core::tlb_schema!(
+    r#"
+    // https://github.com/ton-blockchain/ton/blob/59a8cf0ae5c3062d14ec4c89a04fee80b5fd05c1/crypto/block/block.tlb#L281
+    _ split_depth:(Maybe (## 5)) special:(Maybe TickTock) code:(Maybe ^Cell) data:(Maybe ^Cell)
+      library:(HashmapE 256 SimpleLib) = SchemaStateInit;
+    _#cc utime_since:uint32 bit_price_ps:uint64 cell_price_ps:uint64 mc_bit_price_ps:uint64 mc_cell_price_ps:uint64
+      = SchemaStoragePrices;
+    "#
+);
+
+ton_lib_core::tlb_schema_file!("../resources/tests/test_schema.tlb");
+
+#[test]
+fn test_tlb_schema_state_init() -> anyhow::Result<()> {
+    let library = HashMap::from([(
+        TonHash::from([1u8; 32]),
+        SimpleLib {
+            public: true,
+            root: TonCell::EMPTY.into_ref(),
+        },
+    )]);
+    let state_init = StateInit {
+        split_depth: Some(3),
+        tick_tock: Some(TickTock {
+            tick: true,
+            tock: false,
+        }),
+        code: Some(TonCell::EMPTY.into_ref()),
+        data: None,
+        library: library.clone(),
+    };
+    let schema_state_init = SchemaStateInit::from_cell(&state_init.to_cell()?)?;
+    assert_eq!(schema_state_init.split_depth, Some(3));
+    assert_eq!(schema_state_init.special, state_init.tick_tock);
+    assert_eq!(schema_state_init.code, state_init.code);
+    assert_eq!(schema_state_init.data, None);
+    assert_eq!(schema_state_init.library, library);
+    assert_eq!(schema_state_init.to_cell()?, state_init.to_cell()?);
+    Ok(())
+}
+
+#[test]
+fn test_tlb_schema_storage_prices() -> anyhow::Result<()> {
+    let prices = StoragePrices {
+        utime_since: 1,
+        bit_price_ps: 2,
+        cell_price_ps: 3,
+        mc_bit_price_ps: 4,
+        mc_cell_price_ps: 5,
+    };
+    let schema_prices = SchemaStoragePrices::from_cell(&prices.to_cell()?)?;
+    assert_eq!(SchemaStoragePrices::PREFIX.value, 0xcc);
+    assert_eq!(schema_prices.utime_since, 1);
+    assert_eq!(schema_prices.mc_cell_price_ps, 5);
+    assert_eq!(schema_prices.to_cell()?, prices.to_cell()?);
+    Ok(())
+}
+
+#[test]
+fn test_tlb_schema_file() -> anyhow::Result<()> {
+    assert_eq!(Transfer::PREFIX.value, 0x0f8a7ea5);
+    assert_eq!(Transfer::PREFIX.bits_len, 32);
+    // implicit tag: crc32("excesses query_id:uint64 = TestMsg")
+    assert_eq!(Excesses::PREFIX.value, 0xa6689370);
+    assert_eq!(Excesses::PREFIX.bits_len, 32);
+    assert_eq!(TestBurnInfo::PREFIX.bits_len, 0);
+
+    let transfer = TestMsg::Transfer(Transfer {
+        query_id: 1,
+        amount: 2,
+        destination: TonCell::EMPTY.into_ref(),
+        payload: TLBEither::Right(TonCell::EMPTY.into_ref()),
+    });
+    let cell = transfer.to_cell()?;
+    assert_eq!(cell.data_bits_len, 32 + 64 + 16 + 1);
+    assert_eq!(cell.refs.len(), 2);
+    assert_eq!(TestMsg::from_cell(&cell)?, transfer);
+
+    let burn = TestMsg::Burn(Burn {
+        query_id: 3,
+        amount: 1000,
+        notify: Some(TestBurnInfo {
+            r#type: -1,
+            flags: HashMap::from([(0, true), (255, false)]),
+        }),
+    });
+    let cell = burn.to_cell()?;
+    assert_eq!(cell.data_bits_len, 32 + 64 + 10 + 1);
+    assert_eq!(TestMsg::from_cell(&cell)?, burn);
+    assert_eq!(TestMsg::from_cell(&cell)?.as_burn().map(|x| x.amount), Some(1000));
+
+    let mut builder = TonCell::builder();
+    builder.write_num(&0xDEADBEEFu32, 32)?;
+    assert!(TestMsg::from_cell(&builder.build()?).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_tlb_schema_cond() -> anyhow::Result<()> {
+    let info = TestBurnInfo {
+        r#type: 1,
+        flags: HashMap::new(),
+    };
+    let with_values = TestCond {
+        has_value: true,
+        flags: 0b10,
+        value: Some(7),
+        info: Some(info),
+    };
+    let cell = with_values.to_cell()?;
+    assert_eq!(cell.data_bits_len, 1 + 8 + 16);
+    assert_eq!(cell.refs.len(), 1);
+    assert_eq!(TestCond::from_cell(&cell)?, with_values);
+
+    let without_values = TestCond {
+        has_value: false,
+        flags: 0b01,
+        value: None,
+        info: None,
+    };
+    let cell = without_values.to_cell()?;
+    assert_eq!(cell.data_bits_len, 1 + 8);
+    assert_eq!(TestCond::from_cell(&cell)?, without_values);
+
+    let inconsistent = TestCond {
+        value: Some(1),
+        ..without_values
+    };
+    assert!(inconsistent.to_cell().is_err());
+    Ok(())
+}
@@ -0,0 +1,13 @@
+//! TL-B schema parser shared by `tlb_schema!` macro and runtime `TLBSchema` decoder
+//!
+//! https://docs.ton.org/v3/documentation/data-formats/tlb/tl-b-language
+mod parser;
+
+pub use parser::*;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("line {line}: {msg}")]
+pub struct TLBSchemaError {
+    pub line: usize,
+    pub msg: String,
+}
@@ -0,0 +1,490 @@
+use crate::TLBSchemaError;
+use crc::{Crc, CRC_32_ISO_HDLC};
+use std::fmt::{Display, Formatter};
+
+const CRC_32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+#[derive(Debug)]
+pub struct Constructor {
+    pub name: String,
+    /// Explicit tag, or implicit one computed by `implicit_tag`
+    pub tag: Tag,
+    pub params: Vec<Param>,
+    pub fields: Vec<Field>,
+    pub result_args: Vec<TypeExpr>,
+    /// Declaration with collapsed whitespaces, like `a$0 x:uint8 = A;`
+    pub source: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Tag {
+    pub value: u128,
+    pub bits_len: usize,
+}
+
+// implicit field: {X:Type} or {n:#}
+#[derive(Debug)]
+pub struct Param {
+    pub name: String,
+    pub is_type: bool,
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: Option<String>,
+    pub expr: TypeExpr,
+}
+
+/// Numbers and nat variables are represented as `Apply` without args, `+` and `*` - as `Apply` with 2 args
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeExpr {
+    Apply {
+        name: String,
+        args: Vec<TypeExpr>,
+    },
+    Ref(Box<TypeExpr>),
+    Cond {
+        cond: Box<TypeExpr>,
+        bit: Option<u32>,
+        inner: Box<TypeExpr>,
+    }, // cond?X, cond.bit?X
+}
+
+impl TypeExpr {
+    pub fn name(name: &str) -> Self {
+        TypeExpr::Apply {
+            name: name.to_string(),
+            args: vec![],
+        }
+    }
+}
+
+// top-level expression is printed without parentheses: `## 8`, `Maybe ^Cell`, `n + 1`
+impl Display for TypeExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeExpr::Apply { name, args } if args.len() == 2 && (name == "+" || name == "*") => {
+                write!(f, "{} {name} {}", Nested(&args[0]), Nested(&args[1]))
+            }
+            TypeExpr::Apply { name, args } => {
+                write!(f, "{name}")?;
+                args.iter().try_for_each(|arg| write!(f, " {}", Nested(arg)))
+            }
+            TypeExpr::Ref(inner) => write!(f, "^{}", Nested(inner)),
+            TypeExpr::Cond { cond, bit, inner } => match bit {
+                Some(bit) => write!(f, "{}.{bit}?{}", Nested(cond), Nested(inner)),
+                None => write!(f, "{}?{}", Nested(cond), Nested(inner)),
+            },
+        }
+    }
+}
+
+// nested expression: applications are wrapped in parentheses
+struct Nested<'a>(&'a TypeExpr);
+
+impl Display for Nested<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            TypeExpr::Apply { args, .. } if !args.is_empty() => write!(f, "({})", self.0),
+            expr => write!(f, "{expr}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Tag(String), // #hex or $bin, placed right after constructor name
+    Sym(&'static str),
+}
+
+type Tokens<'a> = std::iter::Peekable<std::slice::Iter<'a, Token>>;
+
+/// Returns (type_name, constructor) pairs in order of appearance
+pub fn parse_schema(source: &str) -> Result<Vec<(String, Constructor)>, TLBSchemaError> {
+    let source = strip_comments(source)?;
+    let mut result = vec![];
+    let mut line = 1;
+    for declaration in source.split_inclusive(';') {
+        let leading_spaces_len = declaration.len() - declaration.trim_start().len();
+        let decl_line = line + declaration[..leading_spaces_len].matches('\n').count();
+        line += declaration.matches('\n').count();
+        if declaration.trim().is_empty() {
+            continue;
+        }
+        let Some(declaration) = declaration.trim().strip_suffix(';') else {
+            return Err(wrong_format(decl_line, "';' is expected at the end of declaration"));
+        };
+        result.push(parse_declaration(declaration).map_err(|err| wrong_format(decl_line, &err))?);
+    }
+    Ok(result)
+}
+
+/// Parses standalone type expression, like `Block` or `HashmapE 32 ^Cell`
+pub fn parse_type_expr(source: &str) -> Result<TypeExpr, TLBSchemaError> {
+    let tokens = tokenize(source).map_err(|err| wrong_format(1, &err))?;
+    let mut tokens = tokens.iter().peekable();
+    let expr = parse_application(&mut tokens).map_err(|err| wrong_format(1, &err))?;
+    match tokens.next() {
+        None => Ok(expr),
+        Some(token) => Err(wrong_format(1, &format!("unexpected token {token:?}"))),
+    }
+}
+
+fn wrong_format(line: usize, msg: &str) -> TLBSchemaError {
+    TLBSchemaError {
+        line,
+        msg: msg.to_string(),
+    }
+}
+
+// comments are replaced by spaces, but new lines are kept to report proper line numbers
+fn strip_comments(source: &str) -> Result<String, TLBSchemaError> {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.find('\n').map(|pos| &comment[pos..]).unwrap_or("");
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let line = source[..source.len() - rest.len()].matches('\n').count() + 1;
+            let end = comment.find("*/").ok_or_else(|| wrong_format(line, "unterminated comment"))?;
+            result.push(' ');
+            result.extend(comment[..end].matches('\n'));
+            rest = &comment[end + 2..];
+        } else {
+            let char = rest.chars().next().unwrap();
+            result.push(char);
+            rest = &rest[char.len_utf8()..];
+        }
+    }
+    Ok(result)
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut tokens = vec![];
+    let mut pos = 0;
+    while pos < chars.len() {
+        let char = chars[pos];
+        if char.is_whitespace() {
+            pos += 1;
+        } else if is_ident_char(char) {
+            let start = pos;
+            while pos < chars.len() && is_ident_char(chars[pos]) {
+                pos += 1;
+            }
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+            if pos < chars.len() && (chars[pos] == '#' || chars[pos] == '$') {
+                let start = pos;
+                pos += 1;
+                while pos < chars.len() && is_ident_char(chars[pos]) {
+                    pos += 1;
+                }
+                tokens.push(Token::Tag(chars[start..pos].iter().collect()));
+            }
+        } else {
+            let rest = chars[pos..].iter().take(3).collect::<String>();
+            let sym = [
+                "##", "#<=", "#<", "#", "^", "(", ")", ":", "=", "{", "}", "[", "]", "~", "?", ".", "+", "*", "<=",
+                "<", ">=", ">", "!",
+            ]
+            .into_iter()
+            .find(|sym| rest.starts_with(sym))
+            .ok_or(format!("unexpected symbol '{char}'"))?;
+            tokens.push(Token::Sym(sym));
+            pos += sym.len();
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_declaration(declaration: &str) -> Result<(String, Constructor), String> {
+    let tokens = tokenize(declaration)?;
+    let mut tokens = tokens.iter().peekable();
+    let name = match tokens.next() {
+        Some(Token::Ident(name)) => name.clone(),
+        _ => return Err("constructor name is expected".to_string()),
+    };
+    let tag = match tokens.next_if(|x| matches!(x, Token::Tag(_))) {
+        Some(Token::Tag(tag)) => Some(parse_tag(tag)?),
+        _ => None,
+    };
+
+    let mut params = vec![];
+    let mut fields = vec![];
+    loop {
+        match tokens.peek() {
+            Some(Token::Sym("=")) => {
+                tokens.next();
+                break;
+            }
+            Some(Token::Sym("{")) => {
+                tokens.next();
+                params.push(parse_param(&mut tokens)?);
+                continue;
+            }
+            Some(Token::Sym("[")) => return Err("anonymous constructors are not supported".to_string()),
+            None => return Err("'=' is expected".to_string()),
+            _ => {}
+        }
+        let mut name = None;
+        if let Some(Token::Ident(ident)) = tokens.peek() {
+            let mut lookahead = tokens.clone();
+            lookahead.next();
+            if lookahead.next() == Some(&Token::Sym(":")) {
+                name = Some(ident.clone()).filter(|x| x != "_");
+                tokens = lookahead;
+            }
+        }
+        fields.push(Field {
+            name,
+            expr: parse_field_type(&mut tokens)?,
+        });
+    }
+
+    let type_name = match tokens.next() {
+        Some(Token::Ident(type_name)) if type_name.starts_with(|c: char| c.is_ascii_uppercase()) => type_name.clone(),
+        _ => return Err("type name is expected after '='".to_string()),
+    };
+    let mut result_args = vec![];
+    while tokens.peek().is_some() {
+        result_args.push(parse_atom(&mut tokens)?);
+    }
+    let mut constructor = Constructor {
+        name,
+        tag: Tag::default(),
+        params,
+        fields,
+        result_args,
+        source: format!("{};", declaration.split_whitespace().collect::<Vec<_>>().join(" ")),
+    };
+    constructor.tag = match tag {
+        Some(tag) => tag,
+        None => implicit_tag(&type_name, &constructor),
+    };
+    Ok((type_name, constructor))
+}
+
+/// CRC32 of normalized declaration, like tlbc computes it. `_` constructor has empty tag
+/// ```text
+/// transfer query_id:uint64 amount:(VarUInteger 16) = Msg;  ->  transfer query_id:uint64 amount:VarUInteger 16 = Msg
+/// ```
+pub fn implicit_tag(type_name: &str, constructor: &Constructor) -> Tag {
+    if constructor.name == "_" {
+        return Tag::default();
+    }
+    let mut normalized = constructor.name.clone();
+    for param in &constructor.params {
+        let kind = if param.is_type { "Type" } else { "#" };
+        normalized.push_str(&format!(" {{{}:{kind}}}", param.name));
+    }
+    for field in &constructor.fields {
+        normalized.push(' ');
+        if let Some(name) = &field.name {
+            normalized.push_str(&format!("{name}:"));
+        }
+        normalized.push_str(&field.expr.to_string());
+    }
+    normalized.push_str(&format!(" = {type_name}"));
+    for arg in &constructor.result_args {
+        normalized.push_str(&format!(" {}", Nested(arg)));
+    }
+    Tag {
+        value: CRC_32.checksum(normalized.as_bytes()) as u128,
+        bits_len: 32,
+    }
+}
+
+fn parse_tag(tag: &str) -> Result<Tag, String> {
+    let (radix, digits) = match tag.split_at(1) {
+        ("#", digits) => (16, digits),
+        (_, digits) => (2, digits),
+    };
+    let (digits, completion_tag) = match digits.strip_suffix('_') {
+        Some(digits) => (digits, true),
+        None => (digits, false),
+    };
+    let mut bits_len = digits.len() * if radix == 16 { 4 } else { 1 };
+    if bits_len > 64 {
+        return Err(format!("tag {tag} is too long"));
+    }
+    let mut value = match digits.is_empty() {
+        true => 0,
+        false => u128::from_str_radix(digits, radix).map_err(|_| format!("invalid tag {tag}"))?,
+    };
+    if completion_tag && bits_len > 0 {
+        // drop trailing zeros and the completion bit itself
+        let trailing_zeros = (value.trailing_zeros() as usize).min(bits_len);
+        if trailing_zeros == bits_len {
+            return Err(format!("invalid tag {tag}: completion bit is not found"));
+        }
+        bits_len -= trailing_zeros + 1;
+        value >>= trailing_zeros + 1;
+    }
+    Ok(Tag { value, bits_len })
+}
+
+// {X:Type} or {n:#}, opening brace is already consumed
+fn parse_param(tokens: &mut Tokens) -> Result<Param, String> {
+    let param = match (tokens.next(), tokens.next(), tokens.next(), tokens.next()) {
+        (Some(Token::Ident(name)), Some(Token::Sym(":")), Some(Token::Ident(kind)), Some(Token::Sym("}")))
+            if kind == "Type" =>
+        {
+            Param {
+                name: name.clone(),
+                is_type: true,
+            }
+        }
+        (Some(Token::Ident(name)), Some(Token::Sym(":")), Some(Token::Sym("#")), Some(Token::Sym("}"))) => Param {
+            name: name.clone(),
+            is_type: false,
+        },
+        _ => return Err("only {X:Type} and {n:#} implicit fields are supported".to_string()),
+    };
+    Ok(param)
+}
+
+// X or cond?X or cond.bit?X
+fn parse_field_type(tokens: &mut Tokens) -> Result<TypeExpr, String> {
+    if let Some(Token::Ident(cond)) = tokens.peek().cloned() {
+        let mut lookahead = tokens.clone();
+        lookahead.next();
+        let bit = match lookahead.next() {
+            Some(Token::Sym("?")) => Some(None),
+            Some(Token::Sym(".")) => match (lookahead.next(), lookahead.next()) {
+                (Some(Token::Ident(bit)), Some(Token::Sym("?"))) => {
+                    Some(Some(bit.parse().map_err(|_| format!("invalid bit number {bit}"))?))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(bit) = bit {
+            *tokens = lookahead;
+            return Ok(TypeExpr::Cond {
+                cond: Box::new(TypeExpr::name(cond)),
+                bit,
+                inner: Box::new(parse_atom(tokens)?),
+            });
+        }
+    }
+    parse_atom(tokens)
+}
+
+fn parse_atom(tokens: &mut Tokens) -> Result<TypeExpr, String> {
+    match tokens.next() {
+        Some(Token::Sym("^")) => Ok(TypeExpr::Ref(Box::new(parse_atom(tokens)?))),
+        Some(Token::Sym("#")) => Ok(TypeExpr::name("#")),
+        Some(Token::Sym("(")) => {
+            let expr = parse_application(tokens)?;
+            match tokens.next() {
+                Some(Token::Sym(")")) => Ok(expr),
+                _ => Err("')' is expected".to_string()),
+            }
+        }
+        Some(Token::Ident(name)) => Ok(TypeExpr::name(name)),
+        Some(Token::Sym("~")) => Err("negated (~) expressions are not supported".to_string()),
+        Some(token) => Err(format!("unexpected token {token:?}")),
+        None => Err("type is expected".to_string()),
+    }
+}
+
+// (## n), (n + 1), (Maybe X) etc. Opening parenthesis is already consumed
+fn parse_application(tokens: &mut Tokens) -> Result<TypeExpr, String> {
+    if let Some(Token::Sym(op @ ("##" | "#<=" | "#<"))) = tokens.peek() {
+        tokens.next();
+        return Ok(TypeExpr::Apply {
+            name: op.to_string(),
+            args: vec![parse_atom(tokens)?],
+        });
+    }
+    let head = parse_atom(tokens)?;
+    if let Some(Token::Sym(op @ ("+" | "*"))) = tokens.peek() {
+        tokens.next();
+        return Ok(TypeExpr::Apply {
+            name: op.to_string(),
+            args: vec![head, parse_atom(tokens)?],
+        });
+    }
+    let mut args = vec![];
+    while !matches!(tokens.peek(), None | Some(Token::Sym(")"))) {
+        args.push(parse_atom(tokens)?);
+    }
+    match head {
+        TypeExpr::Apply { name, args: head_args } if head_args.is_empty() => Ok(TypeExpr::Apply { name, args }),
+        head if args.is_empty() => Ok(head),
+        _ => Err("only named types can be applied to arguments".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tlb_schema_parser() -> anyhow::Result<()> {
+        let schema = r#"
+            // comment
+            just$1 {X:Type} value:X = Maybe X;
+            var_uint$_ {n:#} len:(#< n) value:(uint (len * 8)) = VarUInteger n;
+            /* multiline
+               comment */
+            block_info#9bc7a987 flags:(## 8) not_master:(## 1)
+              master_ref:not_master?^BlkMasterInfo ext:flags.0?Cell = BlockInfo;
+        "#;
+        let constructors = parse_schema(schema)?;
+        assert_eq!(constructors.len(), 3);
+        let (type_name, just) = &constructors[0];
+        assert_eq!(type_name, "Maybe");
+        assert_eq!((just.tag.value, just.tag.bits_len), (1, 1));
+        assert!(just.params[0].is_type);
+        assert_eq!(just.result_args, vec![TypeExpr::name("X")]);
+
+        let (_, var_uint) = &constructors[1];
+        let expected = TypeExpr::Apply {
+            name: "uint".to_string(),
+            args: vec![TypeExpr::Apply {
+                name: "*".to_string(),
+                args: vec![TypeExpr::name("len"), TypeExpr::name("8")],
+            }],
+        };
+        assert_eq!(var_uint.fields[1].expr, expected);
+
+        let (_, block_info) = &constructors[2];
+        assert_eq!((block_info.tag.value, block_info.tag.bits_len), (0x9bc7a987, 32));
+        assert!(
+            matches!(&block_info.fields[2].expr, TypeExpr::Cond { bit: None, inner, .. } if matches!(**inner, TypeExpr::Ref(_)))
+        );
+        assert!(matches!(&block_info.fields[3].expr, TypeExpr::Cond { bit: Some(0), .. }));
+
+        let err = parse_schema("a$0 = A;\n\nb$1 x:~n = A;").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(parse_schema("a$0 = A").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tlb_schema_parser_implicit_tag() -> anyhow::Result<()> {
+        // https://github.com/ton-blockchain/TEPs/blob/master/text/0074-jettons-standard.md
+        let schema = r#"
+            transfer query_id:uint64 amount:(VarUInteger 16) destination:MsgAddress
+              response_destination:MsgAddress custom_payload:(Maybe ^Cell)
+              forward_ton_amount:(VarUInteger 16) forward_payload:(Either Cell ^Cell) = InternalMsgBody;
+            burn query_id:uint64 amount:(VarUInteger 16)
+              response_destination:MsgAddress custom_payload:(Maybe ^Cell) = InternalMsgBody;
+            internal_transfer query_id:uint64 amount:(VarUInteger 16) from:MsgAddress
+              response_address:MsgAddress forward_ton_amount:(VarUInteger 16)
+              forward_payload:(Either Cell ^Cell) = InternalMsgBody;
+            _ a:uint8 = Anonymous;
+            empty$_ a:uint8 = Empty;
+            empty_hex#_ a:uint8 = EmptyHex;
+        "#;
+        let tags = parse_schema(schema)?.into_iter().map(|(_, x)| (x.tag.value, x.tag.bits_len)).collect::<Vec<_>>();
+        assert_eq!(tags[0], (0x8f8a7ea5, 32));
+        assert_eq!(tags[1], (0x595f07bc, 32));
+        assert_eq!(tags[2], (0x978d4519, 32));
+        assert_eq!(&tags[3..], &[(0, 0), (0, 0), (0, 0)]);
+        Ok(())
+    }
+}